@@ -0,0 +1,54 @@
+//! Compares the latency of reloading an edited document with and without tessellating it
+//! incrementally.
+//!
+//! Run with `cargo bench --bench reload`.
+use std::{fmt::Write as _, hint::black_box, sync::Arc, time::Instant};
+
+use bevy_svg::{prelude::*, usvg};
+
+const ITERATIONS: u32 = 50;
+
+/// A grid of stroked circles, with the color of the first one.
+fn document(first_color: &str) -> String {
+    let mut text =
+        String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="400">"#);
+    for i in 0..400 {
+        let color = if i == 0 { first_color } else { "#3060a0" };
+        let _ = write!(
+            text,
+            r##"<circle cx="{}" cy="{}" r="8" fill="{color}" stroke="#000000"/>"##,
+            10 + i % 20 * 20,
+            10 + i / 20 * 20,
+        );
+    }
+    text.push_str("</svg>");
+    text
+}
+
+fn main() {
+    let fontdb = Arc::new(usvg::fontdb::Database::new());
+    let parse = |text: &str| {
+        Svg::from_bytes_with_fontdb(text.as_bytes(), "grid.svg", fontdb.clone()).unwrap()
+    };
+    let (_, _, previous) = parse(&document("#3060a0")).tessellate_incremental(None);
+    // An edit of a single color, as when tweaking a document in an editor.
+    let edited = document("#a03060");
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(parse(&edited).tessellate());
+    }
+    let full = start.elapsed() / ITERATIONS;
+
+    let start = Instant::now();
+    let mut reused = 0;
+    for _ in 0..ITERATIONS {
+        let (mesh, _, paths) = parse(&edited).tessellate_incremental(Some(&previous));
+        reused = paths.reused_count();
+        black_box(mesh);
+    }
+    let incremental = start.elapsed() / ITERATIONS;
+
+    println!("full reload:        {full:?} per edit");
+    println!("incremental reload: {incremental:?} per edit, {reused} of 800 paths reused");
+}
@@ -0,0 +1,58 @@
+//! Compares the flat segment buffer of `Svg` with storing the segments of each path in its
+//! own `Vec`, as before, for the operations that touch all segments.
+//!
+//! Run with `cargo bench --bench segments`.
+use std::{hint::black_box, sync::Arc, time::Instant};
+
+use bevy_svg::{prelude::*, usvg};
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let bytes = include_bytes!("../assets/asteroid_field.svg");
+    let fontdb = Arc::new(usvg::fontdb::Database::new());
+    let svg = Svg::from_bytes_with_fontdb(bytes, "asteroid_field.svg", fontdb).unwrap();
+    // The layout of the segments before they were moved into one buffer.
+    let per_path: Vec<Vec<_>> = svg
+        .paths
+        .iter()
+        .map(|path| svg.path_segments(path).to_vec())
+        .collect();
+    println!("{} paths, {} segments", svg.paths.len(), svg.segments.len());
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(svg.segments.clone());
+    }
+    let flat_clone = start.elapsed() / ITERATIONS;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(per_path.clone());
+    }
+    let per_path_clone = start.elapsed() / ITERATIONS;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let count: usize = svg
+            .paths
+            .iter()
+            .map(|path| black_box(svg.path_segments(path)).len())
+            .sum();
+        black_box(count);
+    }
+    let flat_walk = start.elapsed() / ITERATIONS;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let count: usize = per_path
+            .iter()
+            .map(|segments| black_box(segments.as_slice()).len())
+            .sum();
+        black_box(count);
+    }
+    let per_path_walk = start.elapsed() / ITERATIONS;
+
+    println!("clone, flat buffer:     {flat_clone:?} per iteration");
+    println!("clone, Vec per path:    {per_path_clone:?} per iteration");
+    println!("walk, flat buffer:      {flat_walk:?} per iteration");
+    println!("walk, Vec per path:     {per_path_walk:?} per iteration");
+}
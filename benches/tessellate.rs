@@ -0,0 +1,30 @@
+//! Compares tessellating into a new mesh with tessellating into an existing one.
+//!
+//! Run with `cargo bench --bench tessellate`.
+use std::{hint::black_box, sync::Arc, time::Instant};
+
+use bevy_svg::{prelude::*, usvg};
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let bytes = include_bytes!("../assets/neutron_star.svg");
+    let fontdb = Arc::new(usvg::fontdb::Database::new());
+    let svg = Svg::from_bytes_with_fontdb(bytes, "neutron_star.svg", fontdb).unwrap();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(svg.tessellate());
+    }
+    let allocating = start.elapsed() / ITERATIONS;
+
+    let mut mesh = svg.tessellate();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        svg.tessellate_into(black_box(&mut mesh));
+    }
+    let reusing = start.elapsed() / ITERATIONS;
+
+    println!("tessellate:      {allocating:?} per iteration");
+    println!("tessellate_into: {reusing:?} per iteration");
+}
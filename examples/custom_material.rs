@@ -0,0 +1,75 @@
+//! Draws the mesh of an SVG with a custom material, which reads the vertex attributes of the
+//! SVG mesh through the layout constants of `bevy_svg`.
+use bevy::{
+    mesh::MeshVertexBufferLayoutRef,
+    prelude::*,
+    render::render_resource::{AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    shader::{ShaderDefVal, ShaderRef},
+    sprite_render::{AlphaMode2d, Material2d, Material2dKey, Material2dPlugin},
+};
+use bevy_svg::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            SvgPlugin::default(),
+            Material2dPlugin::<TintedSvgMaterial>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// Multiplies the vertex colors of an SVG mesh with a tint.
+#[derive(Asset, AsBindGroup, Clone, TypePath)]
+struct TintedSvgMaterial {
+    #[uniform(0)]
+    tint: LinearRgba,
+}
+
+impl Material2d for TintedSvgMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/tinted_svg.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/tinted_svg.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[
+            SVG_ATTRIBUTE_POSITION.at_shader_location(SVG_LOCATION_POSITION),
+            SVG_ATTRIBUTE_COLOR.at_shader_location(SVG_2D_LOCATION_COLOR),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        // The shader takes its locations from these definitions instead of hard-coding them.
+        descriptor.vertex.shader_defs.extend([
+            ShaderDefVal::UInt("SVG_LOCATION_POSITION".into(), SVG_LOCATION_POSITION),
+            ShaderDefVal::UInt("SVG_2D_LOCATION_COLOR".into(), SVG_2D_LOCATION_COLOR),
+        ]);
+        Ok(())
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<TintedSvgMaterial>>,
+) {
+    commands.spawn(Camera2d);
+    // Every loaded SVG has its tessellated mesh as `mesh` sub-asset.
+    commands.spawn((
+        Mesh2d(asset_server.load("neutron_star.svg#mesh")),
+        MeshMaterial2d(materials.add(TintedSvgMaterial {
+            tint: LinearRgba::rgb(0.4, 0.8, 1.0),
+        })),
+    ));
+}
@@ -0,0 +1,45 @@
+//! A grid of sheet music that keeps its thin staff lines at least one pixel wide while the
+//! camera zooms out and in. The left half of the grid has no minimum, to compare.
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+const COLUMNS: i32 = 8;
+const ROWS: i32 = 4;
+/// Distance between the SVGs, a bit more than the 526 by 507 units of `twinkle.svg`.
+const SPACING: f32 = 600.0;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SvgPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, zoom)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d);
+    let svg = asset_server.load("twinkle.svg");
+    for column in 0..COLUMNS {
+        for row in 0..ROWS {
+            let position = Vec2::new(
+                (column as f32 - COLUMNS as f32 / 2.0) * SPACING,
+                (row as f32 - ROWS as f32 / 2.0) * SPACING,
+            );
+            let mut entity = commands.spawn((
+                Svg2d(svg.clone()),
+                Transform::from_translation(position.extend(0.0)),
+            ));
+            if column >= COLUMNS / 2 {
+                entity.insert(SvgMinStrokePixels(1.0));
+            }
+        }
+    }
+}
+
+/// Zooms out until the whole grid fits in the window and back in, over and over.
+fn zoom(time: Res<Time>, mut projection: Single<&mut Projection>) {
+    if let Projection::Orthographic(ortho) = &mut **projection {
+        let zoom_out = (time.elapsed_secs() * 0.3).sin() * 0.5 + 0.5;
+        ortho.scale = 0.5 + zoom_out * 7.5;
+    }
+}
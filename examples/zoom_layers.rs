@@ -0,0 +1,32 @@
+//! Shows the "Details" layer of an Inkscape drawing only when zoomed in. Scroll to zoom.
+use bevy::{input::mouse::AccumulatedMouseScroll, prelude::*};
+use bevy_svg::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SvgPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, zoom)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let camera = commands.spawn(Camera2d).id();
+    // With a single camera this is optional, it matters once there are several.
+    commands.insert_resource(SvgCamera(Some(camera)));
+    commands.spawn((
+        Svg2d(asset_server.load("inkscape_layers.svg")),
+        Origin::Center,
+        Transform::from_scale(Vec3::splat(4.0)),
+        SvgZoomLayers::default().with_layer("Details", 1.5, f32::INFINITY),
+    ));
+}
+
+fn zoom(scroll: Res<AccumulatedMouseScroll>, mut projection: Single<&mut Projection>) {
+    if scroll.delta.y == 0.0 {
+        return;
+    }
+    if let Projection::Orthographic(ortho) = &mut **projection {
+        ortho.scale = (ortho.scale * (1.0 - 0.1 * scroll.delta.y.signum())).clamp(0.1, 10.0);
+    }
+}
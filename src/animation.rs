@@ -0,0 +1,569 @@
+//! Support for SMIL animations (`<animate>` and `<animateTransform>`).
+//!
+//! `usvg` only renders a static frame and drops all animation elements, so they are read with
+//! a separate pass over the raw XML. Opacity and transform animations of elements with an `id`
+//! are supported.
+use bevy::{
+    asset::{Assets, RenderAssetUsages},
+    math::{Mat4, Quat, Vec3},
+    mesh::{Mesh, VertexAttributeValues},
+    prelude::*,
+};
+
+use crate::{
+    render::{
+        vertex_buffer::{SVG_ATTRIBUTE_COLOR, SVG_ATTRIBUTE_POSITION},
+        SvgComponent,
+    },
+    svg::{Svg, SvgNode},
+    xml,
+};
+
+/// The animated property of an element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimatedAttribute {
+    /// `opacity`, with one value.
+    Opacity,
+    /// `translate` transform, with the values `tx ty`.
+    Translate,
+    /// `scale` transform, with the values `sx sy`.
+    Scale,
+    /// `rotate` transform, with the values `angle cx cy`.
+    Rotate,
+    /// `skewX` transform, with one angle.
+    SkewX,
+    /// `skewY` transform, with one angle.
+    SkewY,
+}
+
+/// The value of an animated attribute at a point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyframe {
+    /// Time of the keyframe in seconds, relative to the start of the animation.
+    pub time: f32,
+    /// The values of the attribute, see [`AnimatedAttribute`].
+    pub values: Vec<f32>,
+}
+
+/// A single animated attribute of an element, with its timeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgAnimation {
+    /// The `id` of the animated element.
+    pub target: String,
+    /// The animated attribute.
+    pub attribute: AnimatedAttribute,
+    /// The keyframes, sorted by time. Values are interpolated linearly between them.
+    pub keyframes: Vec<Keyframe>,
+    /// Start of the animation in seconds.
+    pub begin: f32,
+    /// Duration of one iteration in seconds.
+    pub duration: f32,
+    /// Number of iterations, [`f32::INFINITY`] for `indefinite`.
+    pub repeat_count: f32,
+    /// Keep the last value after the animation ended, instead of reverting to the static one.
+    pub freeze: bool,
+    /// Add the value to the static one and to earlier animations of the element
+    /// (`additive="sum"`), instead of replacing them. Transforms are added by multiplying them.
+    pub additive: bool,
+}
+
+impl SvgAnimation {
+    /// Total length of the animation in seconds, including its begin offset.
+    #[must_use]
+    pub fn end(&self) -> f32 {
+        self.begin + self.duration * self.repeat_count
+    }
+
+    /// Samples the attribute at `time` seconds. Returns `None` while the animation doesn't
+    /// affect the element.
+    #[must_use]
+    pub fn sample(&self, time: f32) -> Option<Vec<f32>> {
+        let local = time - self.begin;
+        if local < 0.0 || self.keyframes.is_empty() {
+            return None;
+        }
+        if self.duration <= 0.0 || local >= self.duration * self.repeat_count {
+            return self
+                .freeze
+                .then(|| self.keyframes[self.keyframes.len() - 1].values.clone());
+        }
+
+        let t = local % self.duration;
+        let next = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time > t)
+            .unwrap_or(self.keyframes.len() - 1);
+        let Some(prev) = next.checked_sub(1) else {
+            return Some(self.keyframes[next].values.clone());
+        };
+        let (a, b) = (&self.keyframes[prev], &self.keyframes[next]);
+        let span = b.time - a.time;
+        let factor = if span > 0.0 { (t - a.time) / span } else { 1.0 };
+        Some(
+            a.values
+                .iter()
+                .zip(&b.values)
+                .map(|(a, b)| a + (b - a) * factor)
+                .collect(),
+        )
+    }
+
+    /// The transform this animation gives its target, from the target's coordinate system to
+    /// its parent's, or `None` for non-transform attributes.
+    fn transform(&self, values: &[f32]) -> Option<Mat4> {
+        let value = |index: usize| values.get(index).copied().unwrap_or(0.0);
+        Some(match self.attribute {
+            AnimatedAttribute::Opacity => return None,
+            AnimatedAttribute::Translate => {
+                Mat4::from_translation(Vec3::new(value(0), value(1), 0.0))
+            }
+            AnimatedAttribute::Scale => {
+                let sx = values.first().copied().unwrap_or(1.0);
+                Mat4::from_scale(Vec3::new(sx, values.get(1).copied().unwrap_or(sx), 1.0))
+            }
+            AnimatedAttribute::Rotate => {
+                let center = Vec3::new(value(1), value(2), 0.0);
+                Mat4::from_translation(center)
+                    * Mat4::from_quat(Quat::from_rotation_z(value(0).to_radians()))
+                    * Mat4::from_translation(-center)
+            }
+            AnimatedAttribute::SkewX => {
+                let mut skew = Mat4::IDENTITY;
+                skew.y_axis.x = value(0).to_radians().tan();
+                skew
+            }
+            AnimatedAttribute::SkewY => {
+                let mut skew = Mat4::IDENTITY;
+                skew.x_axis.y = value(0).to_radians().tan();
+                skew
+            }
+        })
+    }
+}
+
+/// What an [`SvgAnimationPlayer`] does when it reaches the end of the animations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SvgAnimationLoop {
+    /// Stop at the end and keep showing the last frame.
+    Once,
+    /// Start over from the beginning.
+    #[default]
+    Repeat,
+}
+
+/// Controls the playback of the animations of an entity's SVG. Each entity plays on its own,
+/// even if it shares the SVG asset with others.
+///
+/// Entities with animations get a default player when they are first drawn, so their
+/// animations start then, not at the start of the app.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgAnimationPlayer {
+    /// The current time of the animations in seconds. Set it to seek.
+    pub elapsed: f32,
+    /// Factor the elapsed time is multiplied with, e.g. `0.5` for half speed.
+    pub speed: f32,
+    /// Whether the time stands still.
+    pub paused: bool,
+    /// What happens at the end of the animations.
+    pub loop_mode: SvgAnimationLoop,
+}
+
+impl Default for SvgAnimationPlayer {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+            loop_mode: SvgAnimationLoop::default(),
+        }
+    }
+}
+
+impl SvgAnimationPlayer {
+    /// Resumes playback.
+    pub const fn play(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pauses playback, keeping the current frame.
+    pub const fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Jumps to `time` seconds.
+    pub const fn set_time(&mut self, time: f32) {
+        self.elapsed = time;
+    }
+
+    /// Advances the time by `delta` seconds and applies the loop mode, given the
+    /// [`duration`](Svg::animation_duration) of the animations.
+    fn advance(&mut self, delta: f32, duration: f32) {
+        if !self.paused {
+            self.elapsed += delta * self.speed;
+        }
+        if !duration.is_finite() || duration <= 0.0 {
+            self.elapsed = self.elapsed.max(0.0);
+            return;
+        }
+        match self.loop_mode {
+            SvgAnimationLoop::Once => {
+                if self.elapsed >= duration && self.speed >= 0.0 {
+                    self.paused = true;
+                }
+                self.elapsed = self.elapsed.clamp(0.0, duration);
+            }
+            SvgAnimationLoop::Repeat => self.elapsed = self.elapsed.rem_euclid(duration),
+        }
+    }
+}
+
+/// Parses all supported SMIL animations in the raw SVG document. Animations whose target
+/// has no `id` are skipped.
+#[must_use]
+pub fn parse_animations(bytes: &[u8]) -> Vec<SvgAnimation> {
+    let Some(text) = xml::document_text(bytes) else {
+        return Vec::new();
+    };
+    let Ok(document) = xml::parse_document(&text) else {
+        return Vec::new();
+    };
+
+    document
+        .descendants()
+        .filter(roxmltree::Node::is_element)
+        .filter_map(|node| parse_animation(&node))
+        .collect()
+}
+
+fn parse_animation(node: &roxmltree::Node<'_, '_>) -> Option<SvgAnimation> {
+    let attribute = match node.tag_name().name() {
+        "animate" => match node.attribute("attributeName")? {
+            "opacity" => AnimatedAttribute::Opacity,
+            _ => return None,
+        },
+        "animateTransform" => match node.attribute("type").unwrap_or("translate") {
+            "translate" => AnimatedAttribute::Translate,
+            "scale" => AnimatedAttribute::Scale,
+            "rotate" => AnimatedAttribute::Rotate,
+            "skewX" => AnimatedAttribute::SkewX,
+            "skewY" => AnimatedAttribute::SkewY,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let has_href = node.has_attribute("href") || node.has_attribute((xml::XLINK_NS, "href"));
+    let target = if has_href {
+        xml::href(node)?.to_owned()
+    } else {
+        node.parent_element()?.attribute("id")?.to_owned()
+    };
+
+    let values: Vec<Vec<f32>> = if let Some(values) = node.attribute("values") {
+        values.split(';').map(parse_numbers).collect()
+    } else {
+        let to = node.attribute("to").map(parse_numbers)?;
+        let from = node.attribute("from").map_or_else(
+            || {
+                let element = node
+                    .document()
+                    .descendants()
+                    .find(|element| element.attribute("id") == Some(target.as_str()));
+                base_value(element, attribute, &to)
+            },
+            parse_numbers,
+        );
+        vec![from, to]
+    };
+    if values.is_empty() {
+        return None;
+    }
+
+    let duration = node.attribute("dur").and_then(parse_clock).unwrap_or(0.0);
+    let key_times: Vec<f32> = node
+        .attribute("keyTimes")
+        .map(|times| {
+            times
+                .split(';')
+                .filter_map(|t| t.trim().parse().ok())
+                .collect()
+        })
+        .filter(|times: &Vec<f32>| times.len() == values.len())
+        .unwrap_or_else(|| {
+            #[allow(clippy::cast_precision_loss)]
+            let steps = (values.len() - 1).max(1) as f32;
+            #[allow(clippy::cast_precision_loss)]
+            (0..values.len()).map(|i| i as f32 / steps).collect()
+        });
+    let keyframes = key_times
+        .into_iter()
+        .zip(values)
+        .map(|(time, values)| Keyframe {
+            time: time * duration,
+            values,
+        })
+        .collect();
+
+    Some(SvgAnimation {
+        target,
+        attribute,
+        keyframes,
+        begin: node.attribute("begin").and_then(parse_clock).unwrap_or(0.0),
+        duration,
+        repeat_count: match node.attribute("repeatCount") {
+            Some("indefinite") => f32::INFINITY,
+            Some(count) => count.trim().parse().unwrap_or(1.0),
+            None => 1.0,
+        },
+        freeze: node.attribute("fill") == Some("freeze"),
+        additive: node.attribute("additive") == Some("sum"),
+    })
+}
+
+/// The static value of `attribute` of `element`, where animations without a `from` start.
+///
+/// Only a `transform` of a single function of the animated type has a value to start from,
+/// other transforms start at the neutral value, like an identity `scale` of `1`.
+fn base_value(
+    element: Option<roxmltree::Node<'_, '_>>,
+    attribute: AnimatedAttribute,
+    to: &[f32],
+) -> Vec<f32> {
+    let function = match attribute {
+        AnimatedAttribute::Opacity => {
+            let opacity = element
+                .and_then(|element| element.attribute("opacity"))
+                .and_then(|opacity| opacity.trim().parse().ok());
+            return vec![opacity.unwrap_or(1.0)];
+        }
+        AnimatedAttribute::Translate => "translate",
+        AnimatedAttribute::Scale => "scale",
+        AnimatedAttribute::Rotate => "rotate",
+        AnimatedAttribute::SkewX => "skewX",
+        AnimatedAttribute::SkewY => "skewY",
+    };
+    let base = element
+        .and_then(|element| element.attribute("transform"))
+        .and_then(|transform| {
+            transform
+                .trim()
+                .strip_prefix(function)?
+                .trim_start()
+                .strip_prefix('(')?
+                .strip_suffix(')')
+        })
+        .filter(|arguments| !arguments.contains('('))
+        .map(parse_numbers);
+    base.unwrap_or_else(|| match attribute {
+        AnimatedAttribute::Scale => vec![1.0; to.len()],
+        // The center of the rotation stays where it's going to be.
+        AnimatedAttribute::Rotate => to
+            .iter()
+            .enumerate()
+            .map(|(index, value)| if index == 0 { 0.0 } else { *value })
+            .collect(),
+        _ => vec![0.0; to.len()],
+    })
+}
+
+fn parse_numbers(text: &str) -> Vec<f32> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+/// Parses a SMIL clock value like `2s`, `500ms` or `1.5` into seconds.
+fn parse_clock(text: &str) -> Option<f32> {
+    let text = text.trim();
+    let (number, factor) = if let Some(ms) = text.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = text.strip_suffix('s') {
+        (s, 1.0)
+    } else if let Some(min) = text.strip_suffix("min") {
+        (min, 60.0)
+    } else if let Some(h) = text.strip_suffix('h') {
+        (h, 3600.0)
+    } else {
+        (text, 1.0)
+    };
+    number
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .map(|number| number * factor)
+}
+
+/// The per-entity mesh an animated SVG is drawn with, together with the unanimated vertex data.
+#[derive(Component)]
+pub struct SvgAnimatedMesh {
+    mesh: Handle<Mesh>,
+    svg: AssetId<Svg>,
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    vertex_ranges: Vec<core::ops::Range<u32>>,
+    /// The time the mesh was last animated at.
+    time: Option<f32>,
+}
+
+/// Moves and fades the paths of animated SVGs according to their [`SvgAnimation`]s, at the time
+/// of each entity's [`SvgAnimationPlayer`].
+pub fn apply_animations<C: SvgComponent>(
+    mut commands: Commands,
+    time: Res<Time>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(
+        Entity,
+        &C,
+        &mut C::MeshComponent,
+        Option<&mut SvgAnimatedMesh>,
+        Option<&mut SvgAnimationPlayer>,
+    )>,
+) {
+    for (entity, svg_component, mut mesh, animated, player) in &mut query {
+        let id = svg_component.get_handle().id();
+        let Some(svg) = svgs.get(id) else {
+            continue;
+        };
+        if svg.animations.is_empty() {
+            continue;
+        }
+        let Some(mut player) = player else {
+            commands
+                .entity(entity)
+                .insert(SvgAnimationPlayer::default());
+            continue;
+        };
+
+        let mut animated = match animated {
+            Some(animated) if animated.svg == id => animated,
+            _ => {
+                let (mut base, vertex_ranges) =
+                    svg.tessellate_with_ranges(&svg.tessellation_options);
+                let positions = attribute(&base, SVG_ATTRIBUTE_POSITION, |v| match v {
+                    VertexAttributeValues::Float32x3(v) => Some(v.clone()),
+                    _ => None,
+                });
+                let colors = attribute(&base, SVG_ATTRIBUTE_COLOR, |v| match v {
+                    VertexAttributeValues::Float32x4(v) => Some(v.clone()),
+                    _ => None,
+                });
+                // The mesh is rewritten every frame, so it has to stay in the main world.
+                base.asset_usage = RenderAssetUsages::default();
+                let handle = meshes.add(base);
+                *C::get_mesh_mut(&mut mesh) = handle.clone();
+                commands.entity(entity).insert(SvgAnimatedMesh {
+                    mesh: handle,
+                    svg: id,
+                    positions,
+                    colors,
+                    vertex_ranges,
+                    time: None,
+                });
+                continue;
+            }
+        };
+        if *C::get_mesh_mut(mesh.bypass_change_detection()) != animated.mesh {
+            *C::get_mesh_mut(&mut mesh) = animated.mesh.clone();
+        }
+
+        // Only mutate the player when it changes, so it can be watched for changes.
+        let mut advanced = *player;
+        advanced.advance(time.delta_secs(), svg.animation_duration());
+        if advanced != *player {
+            *player = advanced;
+        }
+        let time = advanced.elapsed;
+        // A paused animation doesn't need to be rewritten every frame.
+        if animated.time == Some(time) {
+            continue;
+        }
+        animated.time = Some(time);
+
+        let (positions, colors) = animate(svg, &animated, time);
+        if let Some(mesh) = meshes.get_mut(&animated.mesh) {
+            mesh.insert_attribute(SVG_ATTRIBUTE_POSITION, positions);
+            mesh.insert_attribute(SVG_ATTRIBUTE_COLOR, colors);
+        }
+    }
+}
+
+fn attribute<T: Default>(
+    mesh: &Mesh,
+    id: bevy::mesh::MeshVertexAttribute,
+    get: impl Fn(&VertexAttributeValues) -> Option<T>,
+) -> T {
+    mesh.attribute(id).and_then(get).unwrap_or_default()
+}
+
+/// Computes the animated vertex data of `svg` at `time`.
+fn animate(svg: &Svg, animated: &SvgAnimatedMesh, time: f32) -> (Vec<[f32; 3]>, Vec<[f32; 4]>) {
+    let mut positions = animated.positions.clone();
+    let mut colors = animated.colors.clone();
+
+    // The animated transform and opacity of each target, replacing its static ones.
+    let mut targets: Vec<(&SvgNode, Option<Mat4>, Option<f32>)> = Vec::new();
+    for animation in &svg.animations {
+        let (Some(node), Some(values)) = (svg.nodes.get(&animation.target), animation.sample(time))
+        else {
+            continue;
+        };
+        let index = targets
+            .iter()
+            .position(|(target, ..)| core::ptr::eq(*target, node))
+            .unwrap_or_else(|| {
+                targets.push((node, None, None));
+                targets.len() - 1
+            });
+        let (_, transform, opacity) = &mut targets[index];
+        match animation.transform(&values) {
+            Some(animation_transform) if animation.additive => {
+                *transform = Some(
+                    transform.unwrap_or_else(|| node.transform.to_matrix()) * animation_transform,
+                );
+            }
+            Some(animation_transform) => *transform = Some(animation_transform),
+            None => {
+                let value = values.first().copied().unwrap_or(1.0);
+                *opacity = Some(if animation.additive {
+                    opacity.unwrap_or(node.opacity) + value
+                } else {
+                    value
+                });
+            }
+        }
+    }
+    // Inner elements have to be transformed before the elements containing them.
+    targets.sort_by_key(|(node, ..)| core::cmp::Reverse(node.depth));
+
+    let flip_y = Mat4::from_scale(Vec3::new(1.0, -1.0, 1.0));
+    for (node, transform, opacity) in targets {
+        let node_transform = node.abs_transform.to_matrix();
+        // Swaps the static transform of the element for the animated one.
+        let mesh_transform = transform.map_or(Mat4::IDENTITY, |transform| {
+            flip_y
+                * node_transform
+                * node.transform.to_matrix().inverse()
+                * transform
+                * node_transform.inverse()
+                * flip_y
+        });
+        // The static opacity is baked into the colors. An element that is fully transparent
+        // in the static frame has lost its colors, so it stays transparent.
+        let opacity = opacity
+            .filter(|_| node.opacity > 0.0)
+            .map_or(1.0, |opacity| opacity.clamp(0.0, 1.0) / node.opacity);
+        for path in node.paths.clone() {
+            let Some(range) = animated.vertex_ranges.get(path) else {
+                continue;
+            };
+            for vertex in range.start as usize..range.end as usize {
+                let position = Vec3::from(positions[vertex]);
+                positions[vertex] = mesh_transform.transform_point3(position).into();
+                colors[vertex][3] *= opacity;
+            }
+        }
+    }
+    (positions, colors)
+}
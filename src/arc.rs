@@ -0,0 +1,101 @@
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use lyon_path::{
+    math::{vector, Point, Vector},
+    PathEvent,
+};
+
+/// Maximum relative difference between the two radii of a cubic to be considered circular.
+const RADIUS_TOLERANCE: f32 = 1e-3;
+/// Radial error of the standard cubic approximation of a quarter circle with radius one.
+const QUARTER_CIRCLE_ERROR: f32 = 2.7e-4;
+
+/// If the cubic from `from` to `to` approximates a circular arc, returns a finer approximation
+/// of the same arc, whose radial error is below `epsilon`. Returns `None` if the cubic isn't
+/// circular or its approximation is already precise enough.
+///
+/// `usvg` converts elliptical arcs into cubics of up to 90°, which deviate by about
+/// `2.7e-4 * radius` from the true circle. That is clearly visible for large radii.
+pub fn refit_arc(
+    from: Point,
+    ctrl1: Point,
+    ctrl2: Point,
+    to: Point,
+    epsilon: f32,
+) -> Option<Vec<PathEvent>> {
+    let start_tangent = ctrl1 - from;
+    let end_tangent = to - ctrl2;
+    if start_tangent.square_length() <= f32::EPSILON || end_tangent.square_length() <= f32::EPSILON
+    {
+        return None;
+    }
+
+    // The center lies on both normals at the end points.
+    let n0 = vector(-start_tangent.y, start_tangent.x);
+    let n1 = vector(-end_tangent.y, end_tangent.x);
+    let det = n1.x * n0.y - n0.x * n1.y;
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    let d = to - from;
+    let a = (n1.x * d.y - d.x * n1.y) / det;
+    let center = from + n0 * a;
+
+    let radius = (from - center).length();
+    if radius <= f32::EPSILON || ((to - center).length() - radius).abs() > radius * RADIUS_TOLERANCE
+    {
+        return None;
+    }
+    // Counter-clockwise in the mathematical sense, which is clockwise on screen.
+    let direction = if (from - center).cross(start_tangent) >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    };
+    let start_angle = (from - center).angle_from_x_axis().radians;
+    let mut sweep = ((to - center).angle_from_x_axis().radians - start_angle) * direction;
+    sweep = sweep.rem_euclid(TAU);
+
+    // The control points have to match those of a circle approximation, otherwise this is
+    // just a curve that happens to start and end on a circle.
+    let expected = radius * 4.0 / 3.0 * (sweep / 4.0).tan();
+    if (start_tangent.length() - expected).abs() > expected * 0.05 + epsilon
+        || (end_tangent.length() - expected).abs() > expected * 0.05 + epsilon
+    {
+        return None;
+    }
+
+    let error =
+        |segments: f32| radius * QUARTER_CIRCLE_ERROR * (sweep / segments / FRAC_PI_2).powi(6);
+    if error(1.0) <= epsilon {
+        return None;
+    }
+    let mut segments = 2.0;
+    while error(segments) > epsilon && segments < 256.0 {
+        segments += 1.0;
+    }
+
+    let step = sweep / segments * direction;
+    let handle = radius * 4.0 / 3.0 * (step.abs() / 4.0).tan();
+    let on_circle = |angle: f32| center + vector(angle.cos(), angle.sin()) * radius;
+    let tangent = |angle: f32| -> Vector { vector(-angle.sin(), angle.cos()) * direction };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = segments as usize;
+    let mut events = Vec::with_capacity(count);
+    let mut segment_from = from;
+    for i in 0..count {
+        #[allow(clippy::cast_precision_loss)]
+        let angle = start_angle + step * i as f32;
+        let next = angle + step;
+        let segment_to = if i + 1 == count { to } else { on_circle(next) };
+        events.push(PathEvent::Cubic {
+            from: segment_from,
+            ctrl1: segment_from + tangent(angle) * handle,
+            ctrl2: segment_to - tangent(next) * handle,
+            to: segment_to,
+        });
+        segment_from = segment_to;
+    }
+    Some(events)
+}
@@ -0,0 +1,107 @@
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock, PoisonError, RwLock,
+    },
+};
+
+use bevy::{platform::collections::HashMap, tasks::futures_lite::future};
+
+/// A map that is shared between the loader threads, in which every value is computed once.
+///
+/// The lock only guards the lookup of an entry. Values are computed outside of it, so slow
+/// work like parsing and tessellating never blocks loads of other keys. Concurrent loads of
+/// the same key wait for the first one instead of computing a duplicate. They wait by yielding
+/// to the executor, so they don't block the threads of the IO task pool, which the load they
+/// wait for may need.
+pub(crate) struct SharedCache<K, V> {
+    entries: Arc<RwLock<HashMap<K, Arc<Entry<V>>>>>,
+}
+
+struct Entry<V> {
+    value: OnceLock<V>,
+    /// Whether a load is computing the value.
+    computing: AtomicBool,
+}
+
+impl<V> Default for Entry<V> {
+    fn default() -> Self {
+        Self {
+            value: OnceLock::new(),
+            computing: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Releases the claim to compute an entry, also if the computation panicked, so a waiting
+/// load can take over.
+struct Claim<'a>(&'a AtomicBool);
+
+impl Drop for Claim<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<K, V> Clone for SharedCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for SharedCache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Send + Sync, V: Clone + Send + Sync> SharedCache<K, V> {
+    /// Returns the value of `key`, computing it with `init` if no other thread did so yet.
+    pub(crate) async fn get_or_init(&self, key: K, init: impl FnOnce() -> V + Send) -> V {
+        let existing = self
+            .entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .cloned();
+        let entry = existing.unwrap_or_else(|| {
+            self.entries
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(key)
+                .or_default()
+                .clone()
+        });
+        loop {
+            if let Some(value) = entry.value.get() {
+                return value.clone();
+            }
+            if !entry.computing.swap(true, Ordering::AcqRel) {
+                let _claim = Claim(&entry.computing);
+                return entry.value.get_or_init(init).clone();
+            }
+            future::yield_now().await;
+        }
+    }
+
+    /// Number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Removes all entries. Computations that are in flight finish, but aren't kept.
+    pub(crate) fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
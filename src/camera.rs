@@ -0,0 +1,62 @@
+use bevy::{
+    camera::{Camera, OrthographicProjection, Projection},
+    log::warn_once,
+    prelude::{Component, Entity, Query, Resource, Without},
+};
+
+/// The camera that camera-dependent SVG features track, like [`SvgZoomLayers`] and
+/// [`SvgMinStrokePixels`].
+///
+/// Without one, they track the first active camera with an orthographic projection, which is
+/// arbitrary when there are several of them. The cameras of `SvgRenderToTexture` entities are
+/// never tracked.
+///
+/// [`SvgZoomLayers`]: crate::prelude::SvgZoomLayers
+/// [`SvgMinStrokePixels`]: crate::prelude::SvgMinStrokePixels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource)]
+pub struct SvgCamera(pub Option<Entity>);
+
+/// Marks a camera that renders an SVG into a texture, which camera-dependent SVG features don't
+/// track.
+#[derive(Clone, Copy, Component, Debug, Default)]
+pub(crate) struct SvgTextureCamera;
+
+/// The cameras camera-dependent SVG features can track.
+pub(crate) type TrackableCameras<'w, 's> =
+    Query<'w, 's, (Entity, &'static Camera, &'static Projection), Without<SvgTextureCamera>>;
+
+/// Returns the camera that camera-dependent SVG features track: the [`SvgCamera`], or the
+/// first active camera with an orthographic projection.
+pub fn tracked_camera<'a>(
+    tracked: SvgCamera,
+    cameras: &'a TrackableCameras,
+) -> Option<(&'a Camera, &'a OrthographicProjection)> {
+    let orthographic = |(camera, projection): (&'a Camera, &'a Projection)| match projection {
+        Projection::Orthographic(ortho) => Some((camera, ortho)),
+        _ => None,
+    };
+    if let Some(entity) = tracked.0 {
+        return cameras
+            .get(entity)
+            .ok()
+            .and_then(|(_, camera, projection)| orthographic((camera, projection)));
+    }
+    let mut active = cameras
+        .iter()
+        .filter(|(_, camera, _)| camera.is_active)
+        .filter_map(|(_, camera, projection)| orthographic((camera, projection)));
+    let first = active.next();
+    if active.next().is_some() {
+        warn_once!(
+            "There are several active orthographic cameras, SVGs track an arbitrary one. Set `SvgCamera` to choose it."
+        );
+    }
+    first
+}
+
+/// Returns the size of a physical pixel of `camera` in world units.
+pub fn world_per_pixel(camera: &Camera, ortho: &OrthographicProjection) -> Option<f32> {
+    let viewport = camera.physical_viewport_size()?;
+    #[allow(clippy::cast_precision_loss)]
+    (viewport.x > 0).then(|| ortho.area.width() / viewport.x as f32)
+}
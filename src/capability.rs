@@ -0,0 +1,188 @@
+//! Features of SVG documents that can't be drawn, and what is drawn instead.
+use core::fmt;
+
+use bevy::color::{Alpha as _, Color};
+
+use crate::{
+    svg::{Svg, SvgGradientStop},
+    xml,
+};
+
+/// A feature of SVG documents that isn't supported, see [`Svg::missing_capabilities`].
+///
+/// Elements using it aren't silently drawn wrong, they get the documented
+/// [`fallback`](Self::fallback) instead.
+///
+/// [`Svg::missing_capabilities`]: crate::prelude::Svg::missing_capabilities
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SvgCapability {
+    /// Linear and radial gradients, drawn in the mean color along the gradient.
+    Gradient,
+    /// Pattern paints, drawn in a half-transparent gray.
+    Pattern,
+    /// Embedded raster images, drawn as a placeholder rectangle with their bounds.
+    Image,
+    /// Clip paths, the clipped elements are drawn whole.
+    ClipPath,
+    /// Masks, the masked elements aren't drawn.
+    Mask,
+    /// Filter effects, the filtered elements are drawn without them.
+    Filter,
+}
+
+/// What is drawn instead of an element that uses a missing [`SvgCapability`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SvgFallback {
+    /// The element is drawn without the effect.
+    Ignore,
+    /// The element and its children aren't drawn.
+    SkipSubtree,
+    /// The element is filled with a single color.
+    FlatColor,
+    /// The bounds of the element are filled with [`PLACEHOLDER_COLOR`].
+    Placeholder,
+}
+
+/// Color of the rectangles drawn in place of elements with the [`SvgFallback::Placeholder`].
+pub const PLACEHOLDER_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, 0.5);
+
+impl SvgCapability {
+    /// What is drawn instead of elements using this capability.
+    #[must_use]
+    pub const fn fallback(self) -> SvgFallback {
+        match self {
+            Self::Gradient | Self::Pattern => SvgFallback::FlatColor,
+            Self::Image => SvgFallback::Placeholder,
+            Self::ClipPath | Self::Filter => SvgFallback::Ignore,
+            Self::Mask => SvgFallback::SkipSubtree,
+        }
+    }
+
+    /// The capability `paint` needs, if it isn't a plain color.
+    pub(crate) const fn of_paint(paint: &usvg::Paint) -> Option<Self> {
+        match paint {
+            usvg::Paint::Color(_) => None,
+            usvg::Paint::LinearGradient(_) | usvg::Paint::RadialGradient(_) => Some(Self::Gradient),
+            usvg::Paint::Pattern(_) => Some(Self::Pattern),
+        }
+    }
+}
+
+impl fmt::Display for SvgCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, fallback) = match self {
+            Self::Gradient => ("gradients", "drawn in their mean color"),
+            Self::Pattern => ("patterns", "drawn in gray"),
+            Self::Image => ("images", "drawn as placeholders"),
+            Self::ClipPath => ("clip paths", "drawn unclipped"),
+            Self::Mask => ("masks", "masked elements skipped"),
+            Self::Filter => ("filters", "drawn unfiltered"),
+        };
+        write!(f, "{name} ({fallback})")
+    }
+}
+
+/// The features a document uses, found by [`Svg::inspect`] without converting it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SvgFeatures {
+    /// Linear, radial or mesh gradients.
+    pub gradients: bool,
+    /// Pattern paints.
+    pub patterns: bool,
+    /// Filter effects, as `<filter>` elements or CSS filter functions.
+    pub filters: bool,
+    /// Masks.
+    pub masks: bool,
+    /// Clip paths.
+    pub clips: bool,
+    /// Text, which is drawn with the fonts available to the loader.
+    pub text: bool,
+    /// Embedded or linked raster images and nested SVG documents.
+    pub images: bool,
+    /// SMIL animations, which are only parsed when enabled in the
+    /// [`SvgSettings`](crate::prelude::SvgSettings).
+    pub animations: bool,
+}
+
+impl SvgFeatures {
+    /// The used features that aren't drawn as authored, sorted.
+    #[must_use]
+    pub fn missing_capabilities(&self) -> Vec<SvgCapability> {
+        [
+            (self.gradients, SvgCapability::Gradient),
+            (self.patterns, SvgCapability::Pattern),
+            (self.images, SvgCapability::Image),
+            (self.clips, SvgCapability::ClipPath),
+            (self.masks, SvgCapability::Mask),
+            (self.filters, SvgCapability::Filter),
+        ]
+        .into_iter()
+        .filter_map(|(used, capability)| used.then_some(capability))
+        .collect()
+    }
+}
+
+impl Svg {
+    /// Scans the document in `bytes` for the features it uses, e.g. to reject unsupported
+    /// documents in a content pipeline before loading them.
+    ///
+    /// This only parses the XML, nothing is converted or tessellated, so it is much cheaper
+    /// than loading. It reports elements that are present, even if `usvg` would drop them as
+    /// unused or invisible. Returns `None` if the document isn't well-formed XML.
+    #[must_use]
+    pub fn inspect(bytes: &[u8]) -> Option<SvgFeatures> {
+        let text = xml::document_text(bytes)?;
+        let document = xml::parse_document(&text).ok()?;
+        let mut features = SvgFeatures::default();
+        for node in document.descendants().filter(roxmltree::Node::is_element) {
+            match node.tag_name().name() {
+                "linearGradient" | "radialGradient" | "meshgradient" => features.gradients = true,
+                "pattern" => features.patterns = true,
+                "filter" => features.filters = true,
+                "mask" => features.masks = true,
+                "clipPath" => features.clips = true,
+                "text" => features.text = true,
+                "image" => features.images = true,
+                "animate" | "animateTransform" | "animateMotion" | "animateColor" | "set" => {
+                    features.animations = true;
+                }
+                _ => {}
+            }
+            // Filter functions like `blur(2px)` don't need a `<filter>` element.
+            if node
+                .attribute("filter")
+                .is_some_and(|filter| filter.trim() != "none")
+            {
+                features.filters = true;
+            }
+        }
+        Some(features)
+    }
+}
+
+/// The color `paint` is drawn with, using the fallback of its [`SvgCapability`] if it isn't a
+/// plain color.
+pub(crate) fn paint_color(paint: &usvg::Paint, opacity: usvg::Opacity) -> Color {
+    match paint {
+        usvg::Paint::Color(c) => Color::srgba_u8(c.red, c.green, c.blue, opacity.to_u8()),
+        usvg::Paint::LinearGradient(gradient) => mean_stop_color(gradient.stops(), opacity),
+        usvg::Paint::RadialGradient(gradient) => mean_stop_color(gradient.stops(), opacity),
+        usvg::Paint::Pattern(_) => Color::srgba(0.5, 0.5, 0.5, 0.5 * opacity.get()),
+    }
+}
+
+fn mean_stop_color(stops: &[usvg::Stop], opacity: usvg::Opacity) -> Color {
+    let stops: Vec<SvgGradientStop> = stops
+        .iter()
+        .map(|stop| {
+            let color = stop.color();
+            SvgGradientStop {
+                offset: stop.offset().get(),
+                color: Color::srgba_u8(color.red, color.green, color.blue, 255)
+                    .with_alpha(stop.opacity().get() * opacity.get()),
+            }
+        })
+        .collect();
+    SvgGradientStop::mean(&stops)
+}
@@ -0,0 +1,80 @@
+//! Outlines of SVG entities for physics colliders, without depending on a physics crate.
+use bevy::{
+    asset::{AssetEvent, Assets},
+    prelude::*,
+};
+
+use crate::{
+    render::SvgComponent,
+    svg::{DrawType, Svg},
+};
+
+/// The outlines of the fills of an entity's SVG as closed polygons, ready to build a collider
+/// of any physics backend from, e.g. as polylines or by convex decomposition.
+///
+/// Inserted and kept up to date for every SVG entity when the `collider` feature is enabled.
+/// The points are in entity-local space, i.e. in the space of the mesh with its y-axis
+/// pointing up, so they line up with the drawn SVG, including the offset of an
+/// [`Origin`](crate::prelude::Origin). Each polygon lists its points once, without repeating
+/// the first point at the end. Strokes aren't included.
+#[derive(Clone, Component, Debug, Default, PartialEq)]
+pub struct SvgColliderOutline(pub Vec<Vec<Vec2>>);
+
+impl SvgColliderOutline {
+    /// Collects the outlines of the fills of `svg`, flattened with the tolerance they are
+    /// tessellated with.
+    #[must_use]
+    pub fn from_svg(svg: &Svg) -> Self {
+        let polygons = svg
+            .paths
+            .iter()
+            .enumerate()
+            .filter_map(|(index, path)| match path.draw_type {
+                DrawType::Fill(options) => Some((index, options.tolerance)),
+                DrawType::Stroke(_) => None,
+            })
+            .flat_map(|(index, tolerance)| svg.flattened_outline(index, tolerance))
+            .filter_map(|mut points| {
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+                (points.len() >= 3).then(|| {
+                    points
+                        .into_iter()
+                        .map(|point| Vec2::new(point.x, -point.y))
+                        .collect()
+                })
+            })
+            .collect();
+        Self(polygons)
+    }
+}
+
+/// Inserts the [`SvgColliderOutline`] of SVG entities once their asset is loaded, and updates
+/// it when the asset or the entity's handle changes.
+pub fn update_collider_outlines<C: SvgComponent>(
+    mut commands: Commands,
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    query: Query<(Entity, Ref<C>, Has<SvgColliderOutline>)>,
+) {
+    let changed_handles = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, svg_component, has_outline) in &query {
+        let handle = svg_component.get_handle();
+        if has_outline && !svg_component.is_changed() && !changed_handles.contains(&handle.id()) {
+            continue;
+        }
+        if let Some(svg) = svgs.get(handle) {
+            commands
+                .entity(entity)
+                .insert(SvgColliderOutline::from_svg(svg));
+        }
+    }
+}
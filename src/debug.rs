@@ -0,0 +1,142 @@
+//! Debug drawing of SVG entities with [`Gizmos`].
+use bevy::{
+    asset::Assets,
+    camera::primitives::Aabb,
+    color::palettes::css::{LIME, MAGENTA, YELLOW},
+    math::Rect,
+    prelude::*,
+};
+
+use crate::{render::SvgComponent, svg::Svg};
+
+/// Plugin that draws debug information for all SVG entities, configured by [`SvgDebugSettings`].
+pub struct SvgDebugPlugin;
+
+impl Plugin for SvgDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SvgDebugSettings>();
+        #[cfg(feature = "2d")]
+        app.add_systems(
+            PostUpdate,
+            (
+                draw_bounds::<crate::render::svg2d::Svg2d>,
+                update_labels::<crate::render::svg2d::Svg2d>,
+            )
+                .after(TransformSystems::Propagate),
+        );
+        #[cfg(feature = "3d")]
+        app.add_systems(
+            PostUpdate,
+            draw_bounds::<crate::render::svg3d::Svg3d>.after(TransformSystems::Propagate),
+        );
+    }
+}
+
+/// Toggles for the debug drawing of the [`SvgDebugPlugin`].
+#[derive(Clone, Debug, Resource)]
+pub struct SvgDebugSettings {
+    /// Draw the [`Svg::size`] rectangle in yellow, the tight content bounds in lime and the
+    /// [`Aabb`] in magenta.
+    pub bounds: bool,
+    /// Label every SVG entity with its entity index, 2D only.
+    pub labels: bool,
+}
+
+impl Default for SvgDebugSettings {
+    fn default() -> Self {
+        Self {
+            bounds: true,
+            labels: false,
+        }
+    }
+}
+
+/// Returns the rectangles drawn for `svg`: its size and its content bounds.
+///
+/// Both are in mesh space, i.e. the space the tessellated mesh lives in. Transforming them with
+/// the entity's [`GlobalTransform`] places them exactly where the renderer draws the SVG,
+/// including the origin offset.
+#[must_use]
+pub fn debug_rects(svg: &Svg) -> (Rect, Option<Rect>) {
+    // Tessellation flips the y-axis, so the document's top left lands at the mesh origin.
+    let flip = |rect: Rect| Rect::new(rect.min.x, -rect.min.y, rect.max.x, -rect.max.y);
+    (
+        flip(Rect::from_corners(Vec2::ZERO, svg.size)),
+        svg.content_bounds().map(flip),
+    )
+}
+
+fn draw_rect(gizmos: &mut Gizmos, transform: &GlobalTransform, rect: Rect, z: f32, color: Color) {
+    let corners = [
+        Vec2::new(rect.min.x, rect.min.y),
+        Vec2::new(rect.max.x, rect.min.y),
+        Vec2::new(rect.max.x, rect.max.y),
+        Vec2::new(rect.min.x, rect.max.y),
+        Vec2::new(rect.min.x, rect.min.y),
+    ]
+    .map(|corner| transform.transform_point(corner.extend(z)));
+    gizmos.linestrip(corners, color);
+}
+
+fn draw_bounds<C: SvgComponent>(
+    settings: Res<SvgDebugSettings>,
+    svgs: Res<Assets<Svg>>,
+    mut gizmos: Gizmos,
+    query: Query<(&C, &GlobalTransform, Option<&Aabb>)>,
+) {
+    if !settings.bounds {
+        return;
+    }
+    for (svg_component, transform, aabb) in &query {
+        let Some(svg) = svgs.get(svg_component.get_handle()) else {
+            continue;
+        };
+        let (size, content) = debug_rects(svg);
+        draw_rect(&mut gizmos, transform, size, 0.0, YELLOW.into());
+        if let Some(content) = content {
+            draw_rect(&mut gizmos, transform, content, 0.0, LIME.into());
+        }
+        if let Some(aabb) = aabb {
+            let center = Vec3::from(aabb.center);
+            let half_extents = Vec3::from(aabb.half_extents).xy();
+            let rect = Rect::from_center_half_size(center.xy(), half_extents);
+            draw_rect(&mut gizmos, transform, rect, center.z, MAGENTA.into());
+        }
+    }
+}
+
+/// Label of an SVG entity, pointing to the labeled entity.
+#[cfg(feature = "2d")]
+#[derive(Component)]
+struct SvgDebugLabel(Entity);
+
+#[cfg(feature = "2d")]
+fn update_labels<C: SvgComponent>(
+    mut commands: Commands,
+    settings: Res<SvgDebugSettings>,
+    svgs: Query<(Entity, &GlobalTransform), With<C>>,
+    mut labels: Query<(Entity, &SvgDebugLabel, &mut Transform)>,
+) {
+    let mut labeled = bevy::platform::collections::HashSet::new();
+    for (label, target, mut transform) in &mut labels {
+        match svgs.get(target.0) {
+            Ok((_, target_transform)) if settings.labels => {
+                transform.translation = target_transform.translation();
+                labeled.insert(target.0);
+            }
+            _ => commands.entity(label).despawn(),
+        }
+    }
+    if !settings.labels {
+        return;
+    }
+    for (entity, transform) in &svgs {
+        if !labeled.contains(&entity) {
+            commands.spawn((
+                SvgDebugLabel(entity),
+                Text2d::new(entity.index().to_string()),
+                Transform::from_translation(transform.translation()),
+            ));
+        }
+    }
+}
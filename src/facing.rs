@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::render::tessellation::Facing;
+
+/// The side the triangles of an SVG entity face, overriding [`DefaultSvgFacing`].
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SvgFacing(pub Facing);
+
+/// The side the triangles of SVG entities without a [`SvgFacing`] face.
+///
+/// Assets are tessellated with the facing of their [`SvgSettings`](crate::prelude::SvgSettings),
+/// entities only get a rewound mesh variant if it differs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource)]
+pub struct DefaultSvgFacing(pub Facing);
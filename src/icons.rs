@@ -0,0 +1,73 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{loader::SvgSettings, svg::Svg};
+
+/// A source file of an icon, drawn for display sizes up to `size`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgIconVariant {
+    /// Asset path of the SVG.
+    pub path: String,
+    /// Largest display size in pixels the variant has enough detail for.
+    pub size: f32,
+}
+
+/// Icons by logical name, each with variants authored for different display sizes, e.g. a
+/// simplified 16 px drawing and a detailed 64 px one.
+///
+/// [`load`](Self::load) picks the variant for a display size and tessellates it for that size
+/// with [`SvgSettings::display_size`], so small icons get coarser meshes.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct SvgIconSet {
+    /// The variants of each icon, sorted by size.
+    pub icons: HashMap<String, Vec<SvgIconVariant>>,
+}
+
+impl SvgIconSet {
+    /// Adds the variant of `name` at `path`, drawn for display sizes up to `size`.
+    #[must_use]
+    pub fn with_variant(
+        mut self,
+        name: impl Into<String>,
+        size: f32,
+        path: impl Into<String>,
+    ) -> Self {
+        self.add_variant(name, size, path);
+        self
+    }
+
+    /// Adds the variant of `name` at `path`, drawn for display sizes up to `size`.
+    pub fn add_variant(&mut self, name: impl Into<String>, size: f32, path: impl Into<String>) {
+        let variants = self.icons.entry(name.into()).or_default();
+        variants.push(SvgIconVariant {
+            path: path.into(),
+            size,
+        });
+        variants.sort_by(|a, b| a.size.total_cmp(&b.size));
+    }
+
+    /// Returns the variant of `name` for a display size: the smallest one authored for at
+    /// least `size`, or the largest one if all are smaller.
+    #[must_use]
+    pub fn pick(&self, name: &str, size: f32) -> Option<&SvgIconVariant> {
+        let variants = self.icons.get(name)?;
+        variants
+            .iter()
+            .find(|variant| variant.size >= size)
+            .or_else(|| variants.last())
+    }
+
+    /// Loads the variant of `name` for a display size, tessellated for that size.
+    ///
+    /// Loads of the same file share one asset, so the first requested size decides how finely
+    /// it is tessellated.
+    #[must_use]
+    pub fn load(&self, asset_server: &AssetServer, name: &str, size: f32) -> Option<Handle<Svg>> {
+        let variant = self.pick(name, size)?;
+        Some(asset_server.load_with_settings(
+            variant.path.clone(),
+            move |settings: &mut SvgSettings| {
+                settings.display_size = Some(size);
+            },
+        ))
+    }
+}
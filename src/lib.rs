@@ -5,12 +5,12 @@
 //! ## Usage
 //! Simply add the crate in your `Cargo.toml` and add the plugin to your app
 //!
-//! ```rust
+//! ```no_run
 //! use bevy::prelude::*;
 //! fn main() {
 //!     App::new()
 //!         .add_plugins(DefaultPlugins)
-//!         .add_plugins(bevy_svg::prelude::SvgPlugin);
+//!         .add_plugins(bevy_svg::prelude::SvgPlugin::default());
 //! }
 //! ```
 
@@ -19,53 +19,201 @@
 #![warn(missing_docs, rust_2018_idioms, unused)]
 #![allow(elided_lifetimes_in_paths)]
 // clippy
-#![warn(
-    clippy::all,
-    clippy::restriction,
-    clippy::pedantic,
-    clippy::nursery,
-    clippy::cargo
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+// Bevy systems take their parameters by value, their queries spell out complex types, and
+// splitting a long system only threads its parameters through helpers.
+#![allow(
+    clippy::needless_pass_by_value,
+    clippy::type_complexity,
+    clippy::too_many_arguments,
+    clippy::too_many_lines
 )]
+// Contradicts the `unreachable_pub` style of marking crate-internal items `pub(crate)`.
+#![allow(clippy::redundant_pub_crate)]
+// Duplicates in the dependency tree of Bevy, which this crate can't resolve.
+#![allow(clippy::multiple_crate_versions)]
+// Geometry reads as the textbook formulas; fusing them would change results between targets.
+#![allow(clippy::suboptimal_flops)]
+// A `match` on an `Option` often reads better than `map_or_else` with two closures.
+#![allow(clippy::option_if_let_else)]
 
+mod animation;
+mod arc;
+mod cache;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod camera;
+mod capability;
+#[cfg(all(feature = "collider", any(feature = "2d", feature = "3d")))]
+mod collider;
+#[cfg(all(feature = "debug", any(feature = "2d", feature = "3d")))]
+pub mod debug;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod facing;
+mod icons;
 mod loader;
+mod merge;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod nine_slice;
 #[cfg(any(feature = "2d", feature = "3d"))]
 mod origin;
 #[cfg(any(feature = "2d", feature = "3d"))]
 mod plugin;
+mod raster;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod regen;
 mod render;
 mod resources;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod shadow;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod stroke;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod style;
 mod svg;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "2d")]
+mod texture;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod theme;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod variants;
+mod xml;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod zoom;
 
 /// Import this module as `use bevy_svg::prelude::*` to get convenient imports.
 pub mod prelude {
     pub use super::SvgPlugin;
+    pub use crate::animation::{
+        AnimatedAttribute, Keyframe, SvgAnimation, SvgAnimationLoop, SvgAnimationPlayer,
+    };
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::camera::SvgCamera;
+    pub use crate::capability::{SvgCapability, SvgFallback, SvgFeatures, PLACEHOLDER_COLOR};
+    #[cfg(all(feature = "collider", any(feature = "2d", feature = "3d")))]
+    pub use crate::collider::SvgColliderOutline;
+    #[cfg(all(feature = "debug", any(feature = "2d", feature = "3d")))]
+    pub use crate::debug::{SvgDebugPlugin, SvgDebugSettings};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::facing::{DefaultSvgFacing, SvgFacing};
+    pub use crate::icons::{SvgIconSet, SvgIconVariant};
+    pub use crate::loader::{
+        SvgEmpty, SvgFonts, SvgLoadCache, SvgSettings, MAX_INCREMENTAL_RELOADS,
+    };
     #[cfg(any(feature = "2d", feature = "3d"))]
-    pub use crate::origin::Origin;
+    pub use crate::nine_slice::SvgNineSlice;
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::origin::{Origin, OriginTween, SvgManualOrigin, SvgPivot};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::regen::{SvgRegenPriority, SvgRegenQueue, SVG_REGEN_QUEUE_LENGTH};
     #[cfg(feature = "2d")]
     pub use crate::render::svg2d::Svg2d;
     #[cfg(feature = "3d")]
-    pub use crate::render::svg3d::Svg3d;
-    pub use crate::svg::Svg;
+    pub use crate::render::svg3d::{Svg3d, SvgPbr};
+    pub use crate::render::{
+        tessellation::{Facing, SvgPathBuffers, TessellationOptions, VertexColorSpace},
+        vertex_buffer::{
+            SVG_2D_LOCATION_COLOR, SVG_3D_LOCATION_COLOR, SVG_ATTRIBUTE_BARYCENTRIC,
+            SVG_ATTRIBUTE_COLOR, SVG_ATTRIBUTE_POSITION, SVG_LOCATION_BARYCENTRIC,
+            SVG_LOCATION_POSITION,
+        },
+    };
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::shadow::{SvgShadow, SvgShadowEntity};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::stroke::{SvgMinStrokePixels, SvgStrokeScale};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::style::{SvgFillOverride, SvgStrokeStyleOverride};
+    pub use crate::svg::{
+        Svg, SvgBlendMode, SvgConvertOptions, SvgGradientStop, SvgLayerInfo, SvgNode, SvgStats,
+        PRECISION_WARNING_SCALE,
+    };
+    #[cfg(feature = "2d")]
+    pub use crate::texture::{SvgRenderToTexture, SvgTexture};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::theme::{CurrentTheme, SvgTheme};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::variants::SvgMeshVariants;
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::zoom::{SvgZoomLayers, ZoomLayer};
     pub use lyon_tessellation::{
-        FillOptions, FillRule, LineCap, LineJoin, Orientation, StrokeOptions,
+        FillOptions, FillRule, LineCap, LineJoin, Orientation, StrokeOptions, TessellationError,
     };
 }
 
 #[cfg(any(feature = "2d", feature = "3d"))]
 use crate::plugin::SvgRenderPlugin;
-use crate::{loader::SvgAssetLoader, svg::Svg};
+use crate::{
+    loader::{
+        detect_empty_svgs, recolor_svgs, retessellate_dirty_svgs, SvgAssetLoader,
+        SvgDefaultOptions, SvgEmpty, SvgFonts, SvgLoadCache,
+    },
+    svg::{Svg, DEFAULT_FILL_OPTIONS, DEFAULT_STROKE_OPTIONS},
+};
 use bevy::{
-    app::{App, Plugin},
+    app::{App, Plugin, PostUpdate},
     asset::AssetApp as _,
+    prelude::IntoScheduleConfigs as _,
 };
+use lyon_tessellation::{FillOptions, StrokeOptions};
+
+/// The `usvg` types needed to call [`Svg::from_usvg_tree`].
+///
+/// This crate uses `usvg` 0.45, trees of other `usvg` versions are different types. The version
+/// only changes in breaking releases of this crate, which also raise the MSRV with Bevy.
+pub mod usvg {
+    pub use ::usvg::{fontdb, Options, Tree};
+}
 
 /// A plugin that provides resources and a system to draw [`Svg`]s.
-pub struct SvgPlugin;
+#[derive(Clone, Copy, Debug)]
+pub struct SvgPlugin {
+    fill_options: FillOptions,
+    stroke_options: StrokeOptions,
+}
+
+impl Default for SvgPlugin {
+    fn default() -> Self {
+        Self {
+            fill_options: DEFAULT_FILL_OPTIONS,
+            stroke_options: DEFAULT_STROKE_OPTIONS,
+        }
+    }
+}
+
+impl SvgPlugin {
+    /// Creates the plugin with the options loaded SVGs are tessellated with, e.g. a coarser
+    /// tolerance for fewer vertices. The fill rule, stroke width, caps and joins always come
+    /// from the SVG.
+    #[must_use]
+    pub const fn with_options(fill_options: FillOptions, stroke_options: StrokeOptions) -> Self {
+        Self {
+            fill_options,
+            stroke_options,
+        }
+    }
+}
 
 impl Plugin for SvgPlugin {
     fn build(&self, app: &mut App) {
-        app.init_asset::<Svg>()
-            .init_asset_loader::<SvgAssetLoader>();
+        app.insert_resource(SvgDefaultOptions {
+            fill: self.fill_options,
+            stroke: self.stroke_options,
+        })
+        .init_resource::<SvgFonts>()
+        .init_resource::<SvgLoadCache>()
+        .init_asset::<Svg>()
+        .init_asset_loader::<SvgAssetLoader>()
+        .add_message::<SvgEmpty>()
+        .add_systems(
+            PostUpdate,
+            (
+                detect_empty_svgs,
+                recolor_svgs.before(retessellate_dirty_svgs),
+                retessellate_dirty_svgs,
+            ),
+        );
         #[cfg(feature = "2d")]
         app.add_plugins(SvgRenderPlugin::<prelude::Svg2d>::default());
         #[cfg(feature = "3d")]
@@ -1,29 +1,500 @@
+use std::{
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    sync::{Arc, PoisonError, RwLock},
+};
+
 use bevy::{
-    asset::{io::Reader, AssetLoader, LoadContext},
-    log::debug,
+    asset::{io::Reader, AssetEvent, AssetId, AssetLoader, Assets, LoadContext, RenderAssetUsages},
+    ecs::{
+        message::{Message, MessageReader, MessageWriter},
+        resource::Resource,
+        system::{Res, ResMut},
+        world::{FromWorld, World},
+    },
+    log::{debug, warn},
+    mesh::Mesh,
+    platform::collections::HashMap,
     reflect::TypePath,
     tasks::ConditionalSendFuture,
 };
+use lyon_tessellation::{FillOptions, StrokeOptions};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::svg::Svg;
+use crate::{
+    animation,
+    cache::SharedCache,
+    render::tessellation::{Facing, SvgPathBuffers, TessellationOptions, VertexColorSpace},
+    svg::{
+        Svg, SvgBlendMode, SvgConvertOptions, DEFAULT_FILL_OPTIONS, DEFAULT_STROKE_OPTIONS,
+        PRECISION_WARNING_SCALE,
+    },
+    xml,
+};
+
+/// Settings used by the [`SvgAssetLoader`] when loading an [`Svg`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SvgSettings {
+    /// Maximum number of vertices the tessellated mesh is allowed to have.
+    pub max_vertex_count: usize,
+    /// Maximum number of vertices of text in the tessellated mesh, see [`Svg::stats`]. Text
+    /// is outlined glyph by glyph, so large blocks of it take many vertices.
+    pub max_text_vertex_count: usize,
+    /// Draw text with [`Svg::simplify_text`] instead of warning or failing when it exceeds
+    /// [`max_text_vertex_count`](Self::max_text_vertex_count).
+    pub simplify_text: bool,
+    /// Maximum width and height, in pixels, of an embedded raster image.
+    pub max_texture_size: u32,
+    /// Maximum size in bytes of the document after expanding its XML entities. Loads of larger
+    /// documents fail, this protects against "billion laughs" documents, which expand a few
+    /// nested entities to gigabytes.
+    pub max_expanded_size: usize,
+    /// Fail the load with an error instead of only logging a warning when a limit is exceeded.
+    pub error_on_exceeded_limits: bool,
+    /// Width of the anti-aliasing fringe around fills, see [`TessellationOptions::edge_aa_width`].
+    pub edge_aa_width: f32,
+    /// Expansion of fills against cracks between neighbors, see
+    /// [`TessellationOptions::seam_overdraw`].
+    pub seam_overdraw: f32,
+    /// The side the triangles of the mesh face, see [`TessellationOptions::facing`].
+    pub facing: Facing,
+    /// The color space of the vertex colors, see [`TessellationOptions::color_space`].
+    pub vertex_color_space: VertexColorSpace,
+    /// Parse SMIL animations into [`Svg::animations`].
+    pub parse_animations: bool,
+    /// Tessellate with barycentric coordinates, see [`TessellationOptions::barycentric`].
+    pub barycentric: bool,
+    /// How the SVG is blended with what is behind it, see [`SvgBlendMode`].
+    pub blend_mode: SvgBlendMode,
+    /// Flatten curves to the tolerance in document units, see
+    /// [`TessellationOptions::scale_tolerance`]. Without it, SVGs with a tiny view box log a
+    /// warning.
+    pub scale_tolerance: bool,
+    /// The color `currentColor` resolves to when no ancestor element sets a `color`, as CSS
+    /// color, e.g. `"#ff8000"`. `None` keeps the SVG default of black.
+    ///
+    /// Elements with a `color` attribute override it for their descendants, like in browsers.
+    pub current_color: Option<String>,
+    /// Size in pixels the SVG is displayed at along its larger side. Curves are then flattened
+    /// to a quarter pixel at that size instead of with the plugin's tolerances, see
+    /// [`Svg::fit_tolerance`]. [`SvgIconSet`](crate::prelude::SvgIconSet) sets it.
+    pub display_size: Option<f32>,
+    /// Maximum deviation of circular arcs from the true circle, in SVG units. `usvg`
+    /// approximates arcs coarsely, which shows as flat spots on large radii. `None` keeps its
+    /// approximation.
+    pub arc_tolerance: Option<f32>,
+    /// Unite consecutive fills of the same color before tessellating, so no anti-aliasing seams
+    /// show where they overlap. Expensive for documents with many fills, see
+    /// [`Svg::merge_same_color_fills`].
+    pub merge_fills: bool,
+    /// Mirror the content vertically, for SVGs authored with a y-axis pointing up. See
+    /// [`Svg::flip_vertically`].
+    pub flip_y: bool,
+    /// Worlds in which the tessellated mesh is kept. The default keeps it in both, so the
+    /// renderer can upload it again after the GPU device was recreated, e.g. when an Android
+    /// app resumes. [`RenderAssetUsages::RENDER_WORLD`] saves memory, but the mesh is lost
+    /// with the device.
+    pub asset_usage: RenderAssetUsages,
+    /// Share the parsed and tessellated data between all files with the same content, see
+    /// [`SvgLoadCache`].
+    pub deduplicate: bool,
+    /// Keep the tessellated paths of each file, so a hot reload only tessellates the paths
+    /// that changed, see [`Svg::tessellate_incremental`]. Speeds up live editing of large
+    /// documents, at the cost of keeping the paths of the last [`MAX_INCREMENTAL_RELOADS`]
+    /// loaded files in memory.
+    pub incremental_reload: bool,
+}
+
+impl Default for SvgSettings {
+    fn default() -> Self {
+        Self {
+            // Roughly what low-end mobile GPUs handle in a single draw call.
+            max_vertex_count: 1 << 20,
+            // A few pages of text.
+            max_text_vertex_count: 1 << 18,
+            simplify_text: false,
+            // Guaranteed minimum of WebGL2 and most mobile GPUs.
+            max_texture_size: 2048,
+            max_expanded_size: 1 << 26,
+            error_on_exceeded_limits: false,
+            edge_aa_width: 0.0,
+            seam_overdraw: 0.0,
+            facing: Facing::PositiveZ,
+            vertex_color_space: VertexColorSpace::Linear,
+            parse_animations: false,
+            barycentric: false,
+            blend_mode: SvgBlendMode::Blend,
+            scale_tolerance: false,
+            current_color: None,
+            display_size: None,
+            arc_tolerance: None,
+            merge_fills: false,
+            flip_y: false,
+            asset_usage: RenderAssetUsages::default(),
+            deduplicate: false,
+            incremental_reload: false,
+        }
+    }
+}
+
+impl SvgSettings {
+    /// The [`TessellationOptions`] used for the mesh of the loaded asset.
+    fn tessellation_options(&self) -> TessellationOptions {
+        TessellationOptions {
+            edge_aa_width: self.edge_aa_width,
+            barycentric: self.barycentric,
+            seam_overdraw: self.seam_overdraw,
+            facing: self.facing,
+            color_space: self.vertex_color_space,
+            scale_tolerance: self.scale_tolerance,
+            ..Default::default()
+        }
+    }
+
+    /// Makes sure the document doesn't expand beyond [`max_expanded_size`](Self::max_expanded_size).
+    fn check_entity_expansion(&self, bytes: &[u8], path: &str) -> Result<(), FileSvgError> {
+        let decompressed;
+        let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+            decompressed = usvg::decompress_svgz(bytes).unwrap_or_default();
+            &decompressed
+        } else {
+            bytes
+        };
+        // Without an entity declaration, the document is only as large as it is.
+        let Some(text) = core::str::from_utf8(bytes)
+            .ok()
+            .filter(|text| text.contains("<!ENTITY"))
+        else {
+            return Ok(());
+        };
+        let size = xml::entity_expansion_size(text, self.max_expanded_size);
+        if size > self.max_expanded_size {
+            return Err(FileSvgError {
+                error: SvgError::ExpandedSizeExceeded {
+                    limit: self.max_expanded_size,
+                },
+                path: path.to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Key of a load in the [`SvgLoadCache`]. Includes the directory, as relative paths of
+    /// embedded images are resolved against it.
+    fn cache_key(&self, bytes: &[u8], dir: Option<&std::path::Path>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        dir.hash(&mut hasher);
+        self.edge_aa_width.to_bits().hash(&mut hasher);
+        self.seam_overdraw.to_bits().hash(&mut hasher);
+        self.facing.hash(&mut hasher);
+        self.vertex_color_space.hash(&mut hasher);
+        self.parse_animations.hash(&mut hasher);
+        self.barycentric.hash(&mut hasher);
+        self.scale_tolerance.hash(&mut hasher);
+        self.display_size.map(f32::to_bits).hash(&mut hasher);
+        self.arc_tolerance.map(f32::to_bits).hash(&mut hasher);
+        self.merge_fills.hash(&mut hasher);
+        self.flip_y.hash(&mut hasher);
+        self.simplify_text.hash(&mut hasher);
+        self.max_text_vertex_count.hash(&mut hasher);
+        hasher.finish()
+    }
 
-#[derive(Default, TypePath)]
-pub struct SvgAssetLoader;
+    /// Checks the tessellated `mesh` and the embedded images of `svg` against the configured limits.
+    fn validate(&self, svg: &Svg, mesh: &Mesh, path: &str) -> Result<(), FileSvgError> {
+        let mut errors = Vec::new();
+
+        let vertex_count = mesh.count_vertices();
+        if vertex_count > self.max_vertex_count {
+            errors.push(SvgError::VertexLimitExceeded {
+                count: vertex_count,
+                limit: self.max_vertex_count,
+            });
+        }
+        let text_vertex_count = svg.stats().text_vertex_count;
+        if text_vertex_count > self.max_text_vertex_count {
+            errors.push(SvgError::TextVertexLimitExceeded {
+                count: text_vertex_count,
+                limit: self.max_text_vertex_count,
+            });
+        }
+        for size in &svg.image_sizes {
+            if size.x > self.max_texture_size || size.y > self.max_texture_size {
+                errors.push(SvgError::TextureLimitExceeded {
+                    width: size.x,
+                    height: size.y,
+                    limit: self.max_texture_size,
+                });
+            }
+        }
+
+        if self.error_on_exceeded_limits {
+            if let Some(error) = errors.into_iter().next() {
+                return Err(FileSvgError {
+                    error,
+                    path: path.to_owned(),
+                });
+            }
+        } else {
+            for error in errors {
+                warn!("SVG {path} may not render correctly on all platforms: {error}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fonts available to text in SVGs loaded through the [`SvgAssetLoader`].
+///
+/// The loader never accesses the filesystem for fonts on its own, every font has to be
+/// added here. Clones share the same fonts, and fonts added later are used by all loads that
+/// start afterwards.
+#[derive(Clone, Default, Resource)]
+pub struct SvgFonts {
+    // Loads take a snapshot of the inner `Arc` and parse without holding the lock. Adding a
+    // font copies the database if a load still uses the snapshot.
+    db: Arc<RwLock<Arc<usvg::fontdb::Database>>>,
+}
+
+impl SvgFonts {
+    /// Adds a font from its raw TTF/OTF data, e.g. from `include_bytes!`.
+    pub fn load_font_data(&mut self, data: Vec<u8>) {
+        self.modify(|db| db.load_font_data(data));
+    }
+
+    /// Adds all fonts in the given directory.
+    pub fn load_fonts_dir(&mut self, dir: impl AsRef<std::path::Path>) {
+        self.modify(|db| db.load_fonts_dir(dir));
+    }
+
+    /// Adds all fonts installed on the system.
+    pub fn load_system_fonts(&mut self) {
+        self.modify(usvg::fontdb::Database::load_system_fonts);
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut usvg::fontdb::Database)) {
+        let mut db = self.db.write().unwrap_or_else(PoisonError::into_inner);
+        f(Arc::make_mut(&mut db));
+    }
+
+    /// The current fonts. The snapshot isn't affected by fonts added afterwards.
+    fn snapshot(&self) -> Arc<usvg::fontdb::Database> {
+        self.db
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Loads that are shared between all SVGs loaded with [`SvgSettings::deduplicate`].
+///
+/// Files with the same content, directory and settings are only parsed and tessellated once,
+/// even when they are loaded concurrently. The cache keeps the parsed data until it is
+/// [cleared](Self::clear), which should also be done after adding [`SvgFonts`].
+#[derive(Clone, Default, Resource)]
+pub struct SvgLoadCache {
+    // `None` marks a failed load, which is repeated to report its error.
+    loads: SharedCache<u64, Option<Arc<(Svg, Mesh)>>>,
+}
+
+impl SvgLoadCache {
+    /// Number of distinct loads in the cache.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.loads.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all loads from the cache.
+    pub fn clear(&self) {
+        self.loads.clear();
+    }
+}
+
+/// The tessellation options paths are loaded with, set up by the
+/// [`SvgPlugin`](crate::prelude::SvgPlugin). Read once when the loader is created.
+#[derive(Clone, Copy, Debug, Resource)]
+pub(crate) struct SvgDefaultOptions {
+    pub fill: FillOptions,
+    pub stroke: StrokeOptions,
+}
+
+impl Default for SvgDefaultOptions {
+    fn default() -> Self {
+        Self {
+            fill: DEFAULT_FILL_OPTIONS,
+            stroke: DEFAULT_STROKE_OPTIONS,
+        }
+    }
+}
+
+/// Number of files whose tessellated paths are kept for [`SvgSettings::incremental_reload`].
+///
+/// Beyond it, the paths of the file that was loaded least recently are dropped, so a hot
+/// reload of that file tessellates all its paths again.
+pub const MAX_INCREMENTAL_RELOADS: usize = 64;
+
+/// The tessellated paths of the last load of each file, by asset path, kept for
+/// [`SvgSettings::incremental_reload`].
+#[derive(Default)]
+struct Reloads {
+    paths: HashMap<String, (u64, Arc<SvgPathBuffers>)>,
+    /// Counts the loads, to find the least recent one.
+    loads: u64,
+}
+
+impl Reloads {
+    fn get(&self, asset_path: &str) -> Option<Arc<SvgPathBuffers>> {
+        self.paths.get(asset_path).map(|(_, paths)| paths.clone())
+    }
+
+    fn insert(&mut self, asset_path: &str, paths: SvgPathBuffers) {
+        self.loads += 1;
+        self.paths
+            .insert(asset_path.to_owned(), (self.loads, Arc::new(paths)));
+        if self.paths.len() > MAX_INCREMENTAL_RELOADS {
+            let oldest = self
+                .paths
+                .iter()
+                .min_by_key(|(_, (load, _))| *load)
+                .map(|(path, _)| path.clone());
+            if let Some(oldest) = oldest {
+                self.paths.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[derive(TypePath)]
+pub struct SvgAssetLoader {
+    fonts: SvgFonts,
+    cache: SvgLoadCache,
+    options: SvgDefaultOptions,
+    reloads: RwLock<Reloads>,
+}
+
+impl FromWorld for SvgAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            fonts: world
+                .get_resource::<SvgFonts>()
+                .cloned()
+                .unwrap_or_default(),
+            cache: world
+                .get_resource::<SvgLoadCache>()
+                .cloned()
+                .unwrap_or_default(),
+            options: world
+                .get_resource::<SvgDefaultOptions>()
+                .copied()
+                .unwrap_or_default(),
+            reloads: RwLock::default(),
+        }
+    }
+}
+
+impl SvgAssetLoader {
+    /// Parses and tessellates `bytes`, the contents of the file at `asset_path`.
+    fn parse(
+        &self,
+        bytes: &[u8],
+        settings: &SvgSettings,
+        asset_path: &str,
+    ) -> Result<(Svg, Mesh), FileSvgError> {
+        debug!("Parsing SVG: {asset_path} ...");
+        let options = SvgConvertOptions {
+            arc_tolerance: settings.arc_tolerance,
+            merge_fills: settings.merge_fills,
+            flip_y: settings.flip_y,
+            fill: self.options.fill,
+            stroke: self.options.stroke,
+            ..Default::default()
+        };
+        let mut svg =
+            Svg::from_bytes_with_options(bytes, asset_path, self.fonts.snapshot(), &options)?;
+        if !svg.missing_capabilities().is_empty() {
+            let capabilities: Vec<String> = svg
+                .missing_capabilities()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            warn!(
+                "SVG {asset_path} uses unsupported features, which are drawn with fallbacks: {}",
+                capabilities.join(", ")
+            );
+        }
+        if svg.is_empty() {
+            warn!("SVG {asset_path} loaded, but has nothing to draw");
+        }
+        let scale = svg.view_box_scale();
+        if scale > PRECISION_WARNING_SCALE
+            && !settings.scale_tolerance
+            && settings.display_size.is_none()
+        {
+            warn!(
+                "SVG {asset_path} scales its view box up {scale}x, so its curves are flattened \
+                 coarsely. Enable `SvgSettings::scale_tolerance` or author it with a larger view box"
+            );
+        }
+        if settings.parse_animations {
+            svg.animations = animation::parse_animations(bytes);
+        }
+        debug!("Parsing SVG: {asset_path} ... Done");
+
+        debug!("Tessellating SVG: {asset_path} ...");
+        svg.tessellation_options = settings.tessellation_options();
+        if let Some(display_size) = settings.display_size {
+            svg.fit_tolerance(display_size);
+        }
+        let mut mesh = if settings.incremental_reload {
+            let previous = self
+                .reloads
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(asset_path);
+            let (mesh, vertex_ranges, paths) = svg.tessellate_incremental(previous.as_deref());
+            svg.vertex_ranges = vertex_ranges;
+            svg.tessellation_errors = paths.errors().to_vec();
+            self.reloads
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(asset_path, paths);
+            mesh
+        } else {
+            svg.tessellate_asset()
+        };
+        if settings.simplify_text {
+            let text_vertex_count = svg.stats().text_vertex_count;
+            if text_vertex_count > settings.max_text_vertex_count {
+                warn!("SVG {asset_path} has {text_vertex_count} vertices of text, simplifying it");
+                svg.simplify_text();
+                mesh = svg.tessellate_asset();
+            }
+        }
+        debug!("Tessellating SVG: {asset_path} ... Done");
+        Ok((svg, mesh))
+    }
+}
 
 impl AssetLoader for SvgAssetLoader {
     type Asset = Svg;
-    type Settings = ();
+    type Settings = SvgSettings;
     type Error = FileSvgError;
 
     fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
+        settings: &SvgSettings,
         load_context: &mut LoadContext,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
-            debug!("Parsing SVG: {} ...", load_context.path());
             let mut bytes = Vec::new();
             reader
                 .read_to_end(&mut bytes)
@@ -33,25 +504,38 @@ impl AssetLoader for SvgAssetLoader {
                     path: load_context.path().to_string(),
                 })?;
 
-            let mut svg = Svg::from_bytes(&bytes, load_context.path().path(), None::<&std::path::Path>)?;
-            let name = &load_context
+            let asset_path = load_context.path().to_string();
+            settings.check_entity_expansion(&bytes, &asset_path)?;
+            if let Some(color) = &settings.current_color {
+                bytes = with_root_color(bytes, color);
+            }
+            let (mut svg, mut mesh) = if settings.deduplicate {
+                let key = settings.cache_key(&bytes, load_context.path().path().parent());
+                let cached = self
+                    .cache
+                    .loads
+                    .get_or_init(key, || {
+                        self.parse(&bytes, settings, &asset_path).ok().map(Arc::new)
+                    })
+                    .await;
+                match cached {
+                    Some(cached) => cached.as_ref().clone(),
+                    None => self.parse(&bytes, settings, &asset_path)?,
+                }
+            } else {
+                self.parse(&bytes, settings, &asset_path)?
+            };
+            // Not every asset source has file names, e.g. `embedded://` or custom ones,
+            // so fall back to the full asset path.
+            svg.name = load_context
                 .path()
                 .path()
                 .file_name()
-                .ok_or_else(|| FileSvgError {
-                    error: SvgError::InvalidFileName(load_context.path().to_string()),
-                    path: load_context.path().to_string(),
-                })?
-                .to_string_lossy();
-            svg.name = name.to_string();
-            debug!("Parsing SVG: {} ... Done", load_context.path());
-
-            debug!("Tessellating SVG: {} ...", load_context.path());
-            let mesh = svg.tessellate();
-            debug!(
-                "Tessellating SVG: {} ... Done",
-                load_context.path()
-            );
+                .map_or(asset_path, |name| name.to_string_lossy().into_owned());
+            svg.blend_mode = settings.blend_mode;
+
+            settings.validate(&svg, &mesh, &load_context.path().to_string())?;
+            mesh.asset_usage = settings.asset_usage;
             let mesh_handle = load_context.add_labeled_asset("mesh".to_owned(), mesh);
             svg.mesh = mesh_handle;
 
@@ -64,15 +548,136 @@ impl AssetLoader for SvgAssetLoader {
     }
 }
 
+/// Sets `color` on the root element, unless it already has one, so `currentColor` falls back
+/// to it. Resolving `currentColor` against the nearest ancestor's `color` is done by `usvg`.
+fn with_root_color(bytes: Vec<u8>, color: &str) -> Vec<u8> {
+    let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+        usvg::decompress_svgz(&bytes).unwrap_or(bytes)
+    } else {
+        bytes
+    };
+    let Some((text, insert_at)) = core::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|text| Some((text, root_attribute_position(text)?)))
+    else {
+        return bytes;
+    };
+
+    let color = color.replace(['"', '<', '&'], "");
+    let mut text = text.to_owned();
+    text.insert_str(insert_at, &format!(" color=\"{color}\""));
+    text.into_bytes()
+}
+
+/// Returns the position right behind the tag name of the root element, or `None` if the root
+/// element already has a `color`.
+fn root_attribute_position(text: &str) -> Option<usize> {
+    let document = xml::parse_document(text).ok()?;
+    let root = document.root_element();
+    if root.attribute("color").is_some() {
+        return None;
+    }
+    let start = root.range().start;
+    text[start..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .map(|offset| start + offset)
+}
+
+/// Sent when an [`Svg`] that has nothing to draw is loaded or modified, see [`Svg::is_empty`].
+///
+/// Its mesh is empty, so entities showing it stay blank although the load didn't fail.
+#[derive(Clone, Copy, Debug, Message, PartialEq, Eq)]
+pub struct SvgEmpty {
+    /// The empty SVG.
+    pub id: AssetId<Svg>,
+}
+
+/// Sends [`SvgEmpty`] for loaded SVGs that have nothing to draw.
+pub(crate) fn detect_empty_svgs(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut empty: MessageWriter<SvgEmpty>,
+) {
+    for event in svg_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            if svgs.get(*id).is_some_and(Svg::is_empty) {
+                empty.write(SvgEmpty { id: *id });
+            }
+        }
+    }
+}
+
+/// Recolors the vertices of the paths recolored with [`Svg::set_gradient_stops`] in the meshes
+/// of the SVGs, or marks them dirty if their meshes don't match their paths anymore.
+pub(crate) fn recolor_svgs(mut svgs: ResMut<Assets<Svg>>, mut meshes: ResMut<Assets<Mesh>>) {
+    // Only the recolored SVGs are borrowed mutably, which marks them as modified.
+    let recolored = svgs
+        .iter()
+        .filter(|(_, svg)| !svg.recolored.is_empty())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in recolored {
+        let Some(svg) = svgs.get_mut(id) else {
+            continue;
+        };
+        let handle = svg.mesh.clone();
+        let recolored = !svg.dirty
+            && meshes
+                .get_mut(&handle)
+                .is_some_and(|mesh| svg.recolor_mesh(mesh));
+        if !recolored {
+            svg.recolored.clear();
+            svg.dirty = true;
+        }
+    }
+}
+
+/// Tessellates the SVGs marked with [`Svg::mark_dirty`] again, replacing their meshes in place.
+pub(crate) fn retessellate_dirty_svgs(
+    mut svgs: ResMut<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    // Only the dirty SVGs are borrowed mutably, which marks them as modified.
+    let dirty = svgs
+        .iter()
+        .filter(|(_, svg)| svg.dirty)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in dirty {
+        let Some(svg) = svgs.get_mut(id) else {
+            continue;
+        };
+        svg.dirty = false;
+        let mut mesh = svg.tessellate_asset();
+        if let Some(current) = meshes.get_mut(&svg.mesh) {
+            mesh.asset_usage = current.asset_usage;
+            *current = mesh;
+        } else {
+            // Meshes only kept in the render world are gone from the main world.
+            svg.mesh = meshes.add(mesh);
+        }
+    }
+}
+
 /// An error that occurs when loading a texture
 #[derive(Error, Debug)]
 pub enum SvgError {
-    #[error("invalid file name")]
-    InvalidFileName(String),
     #[error("could not read file: {0}")]
     IoError(#[from] std::io::Error),
     #[error("failed to load an SVG: {0}")]
+    #[allow(
+        clippy::enum_variant_names,
+        reason = "renaming a public variant breaks users"
+    )]
     SvgError(#[from] usvg::Error),
+    #[error("tessellated mesh has {count} vertices, exceeding the limit of {limit}")]
+    VertexLimitExceeded { count: usize, limit: usize },
+    #[error("text has {count} vertices, exceeding the limit of {limit}")]
+    TextVertexLimitExceeded { count: usize, limit: usize },
+    #[error("document exceeds {limit} bytes after expanding its XML entities")]
+    ExpandedSizeExceeded { limit: usize },
+    #[error("embedded image of size {width}x{height} exceeds the texture size limit of {limit}")]
+    TextureLimitExceeded { width: u32, height: u32, limit: u32 },
 }
 
 /// An error that occurs when loading a texture from a file.
@@ -0,0 +1,283 @@
+//! Merging of overlapping fills of the same color, and of whole documents into silhouettes.
+use core::ops::Range;
+
+use bevy::{
+    color::Color,
+    math::{Vec3, Vec3Swizzles as _},
+    transform::components::Transform,
+};
+use geo::{unary_union, BooleanOps as _, Coord, LineString, MultiPolygon, Polygon};
+use lyon_path::PathEvent;
+use lyon_tessellation::{
+    math::Point, BuffersBuilder, FillOptions, FillRule, StrokeOptions, StrokeTessellator,
+    StrokeVertex, VertexBuffers,
+};
+
+use crate::svg::{DrawType, PathDescriptor, Svg};
+
+impl Svg {
+    /// Merges runs of consecutive fills with the same color into one fill each, by uniting their
+    /// outlines. Their anti-aliasing fringes and seam overdraw then only follow the outside of
+    /// the union, so no seams show where the shapes overlap or touch.
+    ///
+    /// Only consecutive fills are merged, so the paint order stays the same. The merged fills
+    /// are flattened with their tolerance and stored in document space. Translucent overlaps
+    /// are blended once instead of twice. This is expensive for documents with many fills, so
+    /// it is opt-in with [`SvgSettings::merge_fills`](crate::prelude::SvgSettings::merge_fills).
+    pub fn merge_same_color_fills(&mut self) {
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for (index, path) in self.paths.iter().enumerate() {
+            let mergeable = |run: &Range<usize>| {
+                let previous = &self.paths[run.end - 1];
+                matches!(path.draw_type, DrawType::Fill(_))
+                    && matches!(previous.draw_type, DrawType::Fill(_))
+                    && previous.color == path.color
+            };
+            match runs.last_mut() {
+                Some(run) if mergeable(run) => run.end = index + 1,
+                _ => runs.push(index..index + 1),
+            }
+        }
+        if runs.iter().all(|run| run.len() == 1) {
+            return;
+        }
+
+        let mut paths = Vec::with_capacity(runs.len());
+        let mut new_index = vec![0; self.paths.len()];
+        for run in runs {
+            for index in run.clone() {
+                new_index[index] = paths.len();
+            }
+            if run.len() == 1 {
+                paths.push(self.paths[run.start].clone());
+                continue;
+            }
+
+            let first = self.paths[run.start].clone();
+            let DrawType::Fill(options) = first.draw_type else {
+                continue;
+            };
+            let union = run
+                .clone()
+                .fold(MultiPolygon::new(Vec::new()), |union, index| {
+                    let DrawType::Fill(options) = self.paths[index].draw_type else {
+                        return union;
+                    };
+                    union.union(&self.fill_area(index, options.tolerance, options.fill_rule))
+                });
+
+            let start = self.segments.len();
+            for polygon in &union {
+                for ring in core::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                    push_ring(&mut self.segments, ring);
+                }
+            }
+            paths.push(PathDescriptor {
+                segments: start..self.segments.len(),
+                abs_transform: Transform::IDENTITY,
+                draw_type: DrawType::Fill(options.with_fill_rule(FillRule::EvenOdd)),
+                ..first
+            });
+        }
+
+        for node in self.nodes.values_mut() {
+            if node.paths.is_empty() {
+                let start = new_index
+                    .get(node.paths.start)
+                    .copied()
+                    .unwrap_or(paths.len());
+                node.paths = start..start;
+            } else {
+                node.paths = new_index[node.paths.start]..new_index[node.paths.end - 1] + 1;
+            }
+        }
+        self.paths = paths;
+        self.vertex_ranges.clear();
+    }
+
+    /// Returns a copy of `svg` that is a single fill of `color`, e.g. for the silhouette of an
+    /// icon or its shadow.
+    ///
+    /// The areas of all fills and strokes are united, so holes are kept and a translucent
+    /// `color` is blended once, also where paths overlap. The silhouette is flattened with the
+    /// tolerance of the fills and stored in document space, and every node maps to it.
+    /// [`mesh`](Self::mesh) isn't updated, tessellate the copy to draw it, or
+    /// [`mark_dirty`](Self::mark_dirty) it when it replaces the asset.
+    #[must_use]
+    pub fn flatten_to_color(&self, color: Color) -> Self {
+        let mut options = FillOptions::DEFAULT;
+        let areas: Vec<MultiPolygon<f64>> = (0..self.paths.len())
+            .map(|index| match self.paths[index].draw_type {
+                DrawType::Fill(fill) => {
+                    options = fill;
+                    self.fill_area(index, fill.tolerance, fill.fill_rule)
+                }
+                DrawType::Stroke(stroke) => self.stroke_area(index, &stroke),
+            })
+            .collect();
+        let union = unary_union(&areas);
+
+        let mut segments = Vec::new();
+        for polygon in &union {
+            for ring in core::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                push_ring(&mut segments, ring);
+            }
+        }
+        let paths = if segments.is_empty() {
+            Vec::new()
+        } else {
+            vec![PathDescriptor {
+                segments: 0..segments.len(),
+                abs_transform: Transform::IDENTITY,
+                color,
+                draw_type: DrawType::Fill(options.with_fill_rule(FillRule::EvenOdd)),
+                source_path_index: 0,
+                text: false,
+                gradient: false,
+            }]
+        };
+        let mut flattened = Self {
+            paths,
+            segments,
+            vertex_ranges: Vec::new(),
+            ..self.clone()
+        };
+        let silhouette = 0..flattened.paths.len();
+        for node in flattened.nodes.values_mut() {
+            node.paths = if node.paths.is_empty() {
+                0..0
+            } else {
+                silhouette.clone()
+            };
+        }
+        flattened
+    }
+
+    /// The area the fill at `index` covers, in document space.
+    ///
+    /// With the non-zero rule, subpaths wound like the largest one add to the area and the
+    /// others cut holes, which covers the usual outlines with holes, but not subpaths that
+    /// overlap themselves several times.
+    fn fill_area(&self, index: usize, tolerance: f32, fill_rule: FillRule) -> MultiPolygon<f64> {
+        let rings: Vec<(f64, Polygon<f64>)> = self
+            .flattened_outline(index, tolerance)
+            .into_iter()
+            .filter(|points| points.len() >= 3)
+            .map(|points| {
+                let ring: LineString<f64> = points
+                    .iter()
+                    .map(|point| Coord {
+                        x: f64::from(point.x),
+                        y: f64::from(point.y),
+                    })
+                    .collect();
+                (signed_area(&ring), Polygon::new(ring, Vec::new()))
+            })
+            .collect();
+
+        let empty = MultiPolygon::new(Vec::new());
+        match fill_rule {
+            FillRule::EvenOdd => rings.iter().fold(empty, |area, (_, ring)| area.xor(ring)),
+            FillRule::NonZero => {
+                let outer = rings
+                    .iter()
+                    .map(|(area, _)| *area)
+                    .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+                    .unwrap_or_default();
+                let (filled, holes): (Vec<_>, Vec<_>) = rings
+                    .iter()
+                    .partition(|(area, _)| area.signum() == outer.signum());
+                let area = filled
+                    .iter()
+                    .fold(empty, |area, (_, ring)| area.union(ring));
+                holes
+                    .iter()
+                    .fold(area, |area, (_, hole)| area.difference(hole))
+            }
+        }
+    }
+
+    /// The area the stroke at `index` covers, in document space, with the width it's
+    /// tessellated with.
+    fn stroke_area(&self, index: usize, options: &StrokeOptions) -> MultiPolygon<f64> {
+        let path = &self.paths[index];
+        let mut options = *options;
+        let tessellation = &self.tessellation_options;
+        let path_scale = path.abs_transform.scale.xy().abs().max_element();
+        options.line_width *= tessellation.stroke_scale;
+        if path_scale > 0.0 {
+            options.line_width = options
+                .line_width
+                .max(tessellation.min_stroke_width / path_scale);
+        }
+
+        let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+        if StrokeTessellator::new()
+            .tessellate(
+                self.path_segments(path).iter().copied(),
+                &options,
+                &mut BuffersBuilder::new(&mut buffers, |vertex: StrokeVertex| vertex.position()),
+            )
+            .is_err()
+        {
+            return MultiPolygon::new(Vec::new());
+        }
+        let triangles: Vec<Polygon<f64>> = buffers
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let ring: LineString<f64> = triangle
+                    .iter()
+                    .map(|&vertex| {
+                        let point = buffers.vertices[vertex as usize];
+                        let point = path
+                            .abs_transform
+                            .transform_point(Vec3::new(point.x, point.y, 0.0));
+                        Coord {
+                            x: f64::from(point.x),
+                            y: f64::from(point.y),
+                        }
+                    })
+                    .collect();
+                Polygon::new(ring, Vec::new())
+            })
+            .filter(|triangle| signed_area(triangle.exterior()) != 0.0)
+            .collect();
+        unary_union(&triangles)
+    }
+}
+
+/// Signed area of a ring by the shoelace formula, the sign gives its winding.
+fn signed_area(ring: &LineString<f64>) -> f64 {
+    ring.lines()
+        .map(|line| line.start.x * line.end.y - line.end.x * line.start.y)
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Appends the closed `ring` as a subpath.
+fn push_ring(segments: &mut Vec<PathEvent>, ring: &LineString<f64>) {
+    #[allow(clippy::cast_possible_truncation)]
+    let point = |coord: &Coord<f64>| Point::new(coord.x as f32, coord.y as f32);
+    // Rings repeat their first point at the end.
+    let Some((first, rest)) = ring.0.split_first() else {
+        return;
+    };
+    let rest = rest.strip_suffix(&[*first]).unwrap_or(rest);
+    if rest.len() < 2 {
+        return;
+    }
+    let first = point(first);
+    segments.push(PathEvent::Begin { at: first });
+    let mut from = first;
+    for coord in rest {
+        let to = point(coord);
+        segments.push(PathEvent::Line { from, to });
+        from = to;
+    }
+    segments.push(PathEvent::End {
+        last: from,
+        first,
+        close: true,
+    });
+}
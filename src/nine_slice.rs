@@ -0,0 +1,74 @@
+use core::hash::{Hash as _, Hasher};
+
+use bevy::{
+    mesh::{Mesh, VertexAttributeValues},
+    prelude::*,
+};
+
+use crate::{render::vertex_buffer::SVG_ATTRIBUTE_POSITION, svg::Svg};
+
+/// Resizes an SVG to `size` by nine-slice scaling, e.g. for resizable panel backgrounds: the
+/// corners keep their size, the edges stretch along their side and the center stretches both
+/// ways.
+///
+/// `border` holds the widths of the borders in SVG units, `min` those of the left and top
+/// border, `max` those of the right and bottom border. Build it field by field, since
+/// [`Rect::new`] would sort the widths. The mesh is stretched vertex by vertex, so the borders
+/// should only be crossed by straight edges, as with the sides of a rounded rectangle. The
+/// [`Origin`](crate::prelude::Origin) is still computed from the authored size of the SVG.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgNineSlice {
+    /// Widths of the borders, `min` for the left and top, `max` for the right and bottom.
+    pub border: Rect,
+    /// Size to draw the SVG at, in SVG units.
+    pub size: Vec2,
+}
+
+impl SvgNineSlice {
+    /// Hashes the border and size, for the key of the mesh variant.
+    pub(crate) fn hash_into(&self, hasher: &mut impl Hasher) {
+        [self.border.min, self.border.max, self.size]
+            .map(|v| v.to_array().map(f32::to_bits))
+            .hash(hasher);
+    }
+
+    /// Moves the vertices of `mesh`, tessellated from `svg`, to their nine-sliced positions.
+    pub fn apply(&self, svg: &Svg, mesh: &mut Mesh) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(SVG_ATTRIBUTE_POSITION)
+        else {
+            return;
+        };
+        for position in positions {
+            position[0] = slice(
+                position[0],
+                svg.size.x,
+                self.border.min.x,
+                self.border.max.x,
+                self.size.x,
+            );
+            // The mesh has a flipped y-axis, the top border is at zero.
+            position[1] = -slice(
+                -position[1],
+                svg.size.y,
+                self.border.min.y,
+                self.border.max.y,
+                self.size.y,
+            );
+        }
+    }
+}
+
+/// Maps `x` along an axis of length `length` to one of length `target`, keeping the `start`
+/// and `end` borders and stretching what is between them.
+fn slice(x: f32, length: f32, start: f32, end: f32, target: f32) -> f32 {
+    if x <= start {
+        x
+    } else if x >= length - end {
+        x + target - length
+    } else {
+        let center = length - start - end;
+        let stretched = (target - start - end).max(0.0);
+        start + (x - start) * stretched / center
+    }
+}
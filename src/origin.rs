@@ -1,15 +1,21 @@
 use bevy::{
-    prelude::*,
     asset::Assets,
     math::{Vec2, Vec3},
-    prelude::Ref,
+    prelude::*,
     transform::components::{GlobalTransform, Transform},
 };
 
-use crate::{render::SvgComponent, svg::Svg};
+use crate::{
+    render::{SvgComponent, SvgInert},
+    svg::Svg,
+};
 
 #[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq)]
 /// Origin of the coordinate system.
+///
+/// The plugin only touches the [`GlobalTransform`] of SVG entities that have this component,
+/// an [`SvgPivot`] or an [`OriginTween`]. Leave them out, or remove them, to keep full control
+/// over the transforms of an entity.
 pub enum Origin {
     /// Bottom left of the image or viewbox.
     BottomLeft,
@@ -27,7 +33,7 @@ pub enum Origin {
 impl Origin {
     /// Computes the translation for an origin. The resulting translation needs to be added
     /// to the translation of the SVG.
-    #[must_use] 
+    #[must_use]
     pub fn compute_translation(&self, scaled_size: Vec2) -> Vec3 {
         match self {
             Self::BottomLeft => Vec3::new(0.0, scaled_size.y, 0.0),
@@ -38,72 +44,238 @@ impl Origin {
             Self::TopRight => Vec3::new(-scaled_size.x, 0.0, 0.0),
         }
     }
+
+    /// Computes the offset this origin applies to an SVG of `svg_size` drawn with `scale`.
+    ///
+    /// This is the same offset the plugin adds to the [`GlobalTransform`], meant for entities
+    /// marked with [`SvgManualOrigin`] which apply it themselves.
+    #[must_use]
+    pub fn offset_for(&self, svg_size: Vec2, scale: Vec2) -> Vec3 {
+        self.compute_translation(svg_size * scale)
+    }
+}
+
+impl Svg {
+    /// Maps `point` in SVG document space, with the y-axis pointing down like
+    /// [`Svg::transformed_segments`], to the world position it is drawn at by an entity with
+    /// `transform` and `origin`.
+    ///
+    /// `transform` is the entity's [`Transform`], so for child entities the result is in the
+    /// space of the parent. The offset of an [`SvgPivot`] isn't included.
+    #[must_use]
+    pub fn svg_to_world(&self, point: Vec2, transform: &Transform, origin: Origin) -> Vec2 {
+        // The tessellated mesh has a flipped y-axis.
+        let local = Vec3::new(point.x, -point.y, 0.0);
+        let offset = origin.offset_for(self.size, transform.scale.xy());
+        (transform.transform_point(local) + offset).xy()
+    }
+
+    /// Maps the world position `point` to SVG document space, the inverse of
+    /// [`Svg::svg_to_world`].
+    #[must_use]
+    pub fn world_to_svg(&self, point: Vec2, transform: &Transform, origin: Origin) -> Vec2 {
+        let offset = origin.offset_for(self.size, transform.scale.xy());
+        let local = transform
+            .compute_affine()
+            .inverse()
+            .transform_point3(point.extend(0.0) - offset);
+        Vec2::new(local.x, -local.y)
+    }
 }
 
-#[derive(Clone, Component, Copy, Debug, PartialEq, Eq)]
+/// Marker component that opts an entity out of the plugin's origin handling.
+///
+/// The plugin will neither read nor write the [`GlobalTransform`] of an entity with this
+/// component. Use [`Origin::offset_for`] to apply the offset manually where appropriate.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SvgManualOrigin;
+
+/// Point of the SVG, in SVG units, around which the entity's rotation is applied.
+///
+/// Without it, an SVG rotates around its top left corner, independent of its [`Origin`].
+/// For example, set it to the hub of a gear to spin the gear in place. Without an [`Origin`],
+/// only the offset of the pivot is applied.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq)]
+pub struct SvgPivot(pub Vec2);
+
+impl SvgPivot {
+    /// Computes the translation that makes `transform` rotate around this pivot instead of
+    /// around the top left corner.
+    #[must_use]
+    pub fn offset_for(&self, transform: &Transform) -> Vec3 {
+        // The tessellated mesh has a flipped y-axis.
+        let pivot = Vec3::new(self.0.x, -self.0.y, 0.0) * transform.scale;
+        pivot - transform.rotation * pivot
+    }
+}
+
+/// Moves an SVG smoothly from one [`Origin`] to another over `duration` seconds, instead of
+/// snapping to the new anchor like changing the [`Origin`] does.
+///
+/// While the tween runs, it decides the offset instead of the entity's [`Origin`], easing in
+/// and out. When it finishes, the [`Origin`] is set to `to`, or added if the entity has none,
+/// and the tween is removed.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct OriginTween {
+    /// The origin the tween starts at.
+    pub from: Origin,
+    /// The origin the tween ends at.
+    pub to: Origin,
+    /// Length of the tween in seconds.
+    pub duration: f32,
+    /// Seconds since the tween started.
+    pub elapsed: f32,
+}
+
+impl OriginTween {
+    /// Creates a tween from `from` to `to` over `duration` seconds.
+    #[must_use]
+    pub const fn new(from: Origin, to: Origin, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Eased progress of the tween, from `0.0` to `1.0`.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        EaseFunction::SmoothStep.sample_clamped(t)
+    }
+
+    /// Computes the offset of the tween at its current progress, like [`Origin::offset_for`].
+    #[must_use]
+    pub fn offset_for(&self, svg_size: Vec2, scale: Vec2) -> Vec3 {
+        self.from
+            .offset_for(svg_size, scale)
+            .lerp(self.to.offset_for(svg_size, scale), self.progress())
+    }
+}
+
+/// Advances the [`OriginTween`]s of SVGs, and replaces finished ones with their target
+/// [`Origin`].
+pub fn advance_origin_tweens<C: SvgComponent>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut OriginTween), With<C>>,
+) {
+    for (entity, mut tween) in &mut query {
+        tween.elapsed += time.delta_secs();
+        if tween.elapsed >= tween.duration {
+            commands
+                .entity(entity)
+                .insert(tween.to)
+                .remove::<OriginTween>();
+        }
+    }
+}
+
+/// Tracks the origin offset the plugin added to the [`GlobalTransform`] of an entity.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq)]
 pub struct OriginState {
-    previous: Origin,
+    /// The offset that was added.
+    applied: Vec3,
+    /// The [`GlobalTransform`] right after the offset was added. If it differs, transform
+    /// propagation has recomputed it and the offset is gone.
+    written: GlobalTransform,
 }
 
 /// Checkes if a "new" SVG bundle was added by looking for a missing `OriginState`
 /// and then adds it to the entity.
 pub fn add_origin_state<C: SvgComponent>(
     mut commands: Commands,
-    query: Query<Entity, (With<C>, With<C::MeshComponent>, Without<OriginState>)>,
+    query: Query<
+        Entity,
+        (
+            With<C>,
+            With<C::MeshComponent>,
+            Or<(With<Origin>, With<SvgPivot>, With<OriginTween>)>,
+            Without<OriginState>,
+            Without<SvgManualOrigin>,
+            Without<SvgInert>,
+        ),
+    >,
 ) {
     for entity in &query {
-        commands.entity(entity).insert(OriginState {
-            previous: Origin::default(),
-        });
+        commands.entity(entity).insert(OriginState::default());
     }
 }
 
-/// Gets all SVGs with a changed origin or transform and checks if the origin offset
-/// needs to be applied.
+/// Removes the `OriginState` of SVGs that opted out of origin handling, by removing their
+/// [`Origin`], [`SvgPivot`] and [`OriginTween`] or adding [`SvgManualOrigin`], and lets
+/// transform propagation drop the offset.
+pub fn remove_origin_state<C: SvgComponent>(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &mut Transform),
+        (
+            With<C>,
+            With<OriginState>,
+            Or<(
+                (Without<Origin>, Without<SvgPivot>, Without<OriginTween>),
+                With<SvgManualOrigin>,
+            )>,
+        ),
+    >,
+) {
+    for (entity, mut transform) in &mut query {
+        commands.entity(entity).remove::<OriginState>();
+        transform.set_changed();
+    }
+}
+
+/// Makes sure the [`GlobalTransform`] of every SVG contains the offset of its current origin,
+/// or [`OriginTween`], and [`SvgPivot`]. A missing [`Origin`] adds no offset.
+///
+/// Instead of reacting to specific changes, this compares the desired offset with the one
+/// that is currently applied. So the offset is also corrected when the asset finishes loading
+/// after the entity was spawned, or is reloaded with a different size.
 pub fn apply_origin<C: SvgComponent>(
     svgs: Res<Assets<Svg>>,
     mut query: Query<
         (
-            Entity,
             &C,
-            &Origin,
+            Option<&Origin>,
+            Option<&OriginTween>,
+            Option<&SvgPivot>,
             &mut OriginState,
-            Ref<Transform>,
+            &Transform,
             &mut GlobalTransform,
         ),
-        Or<(
-            Changed<Origin>,
-            Changed<Transform>,
-            Changed<C::MeshComponent>,
-        )>,
+        (Without<SvgManualOrigin>, Without<SvgInert>),
     >,
 ) {
-    for (_, svg_component, origin, mut origin_state, transform, mut global_transform) in &mut query
+    for (svg_component, origin, tween, pivot, mut origin_state, transform, mut global_transform) in
+        &mut query
     {
-        if let Some(svg) = svgs.get(svg_component.get_handle()) {
-            if origin_state.previous != *origin {
-                let scaled_size = svg.size * transform.scale.xy();
-                let reverse_origin_translation =
-                    origin_state.previous.compute_translation(scaled_size);
-                let origin_translation = origin.compute_translation(scaled_size);
-
-                let mut gtransf = global_transform.compute_transform();
-                gtransf.translation.x += origin_translation.x - reverse_origin_translation.x;
-                gtransf.translation.y += origin_translation.y - reverse_origin_translation.y;
-                gtransf.translation.z += origin_translation.z - reverse_origin_translation.z;
-                *global_transform = GlobalTransform::from(gtransf);
-
-                origin_state.previous = *origin;
-            } else if transform.is_changed() {
-                let scaled_size = svg.size * transform.scale.xy();
-                let origin_translation = origin.compute_translation(scaled_size);
-
-                let mut gtransf = global_transform.compute_transform();
-                gtransf.translation.x += origin_translation.x;
-                gtransf.translation.y += origin_translation.y;
-                gtransf.translation.z += origin_translation.z;
-                *global_transform = GlobalTransform::from(gtransf);
-            }
+        let Some(svg) = svgs.get(svg_component.get_handle()) else {
+            continue;
+        };
+        let applied = if *global_transform == origin_state.written {
+            origin_state.applied
+        } else {
+            Vec3::ZERO
+        };
+        let scale = transform.scale.xy();
+        let offset = tween.map_or_else(
+            || origin.map_or(Vec3::ZERO, |origin| origin.offset_for(svg.size, scale)),
+            |tween| tween.offset_for(svg.size, scale),
+        ) + pivot.map_or(Vec3::ZERO, |pivot| pivot.offset_for(transform));
+        if offset == applied && *global_transform == origin_state.written {
+            continue;
         }
+
+        *global_transform = GlobalTransform::from_translation(offset - applied) * *global_transform;
+        *origin_state = OriginState {
+            applied: offset,
+            written: *global_transform,
+        };
     }
 }
@@ -17,23 +17,34 @@ use bevy::{
     asset::{AssetEvent, Assets},
     ecs::{
         change_detection::DetectChanges as _,
-        schedule::{SystemSet},
+        schedule::SystemSet,
         system::{Query, Res},
         world::Ref,
     },
     prelude::{IntoScheduleConfigs as _, Last, MessageReader, PostUpdate},
+    transform::TransformSystems,
 };
 
 use crate::{
+    animation,
+    camera::SvgCamera,
+    facing::DefaultSvgFacing,
     origin,
     render::SvgComponent,
+    shadow, style,
     svg::Svg,
+    theme::{self, CurrentTheme},
+    variants,
 };
 
 /// Sets for this plugin.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum Set {
     /// Set in which [`Svg2dBundle`](crate::bundle::Svg2dBundle)s get drawn.
+    #[allow(
+        clippy::upper_case_acronyms,
+        reason = "renaming a public variant breaks users"
+    )]
     SVG,
 }
 
@@ -43,16 +54,42 @@ pub struct SvgRenderPlugin<C: SvgComponent>(PhantomData<C>);
 
 impl<C: SvgComponent> Plugin for SvgRenderPlugin<C> {
     fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentTheme>()
+            .init_resource::<DefaultSvgFacing>()
+            .init_resource::<SvgCamera>()
+            .add_systems(
+                PostUpdate,
+                (
+                    theme::apply_theme::<C>.in_set(Set::SVG),
+                    animation::apply_animations::<C>.in_set(Set::SVG),
+                    origin::add_origin_state::<C>.in_set(Set::SVG),
+                    origin::advance_origin_tweens::<C>.in_set(Set::SVG),
+                    origin::remove_origin_state::<C>
+                        .in_set(Set::SVG)
+                        .before(TransformSystems::Propagate),
+                    // Stroke widths depend on the scale of the entity.
+                    variants::apply_mesh_variants::<C>
+                        .in_set(Set::SVG)
+                        .after(TransformSystems::Propagate),
+                    // The offset of shadows depends on the rotation of the entity.
+                    shadow::apply_shadows::<C>
+                        .in_set(Set::SVG)
+                        .after(TransformSystems::Propagate),
+                ),
+            )
+            .add_systems(
+                Last,
+                (
+                    origin::apply_origin::<C>,
+                    svg_mesh_linker::<C>.in_set(Set::SVG),
+                    style::apply_material_overrides::<C>.after(svg_mesh_linker::<C>),
+                ),
+            );
+
+        #[cfg(feature = "collider")]
         app.add_systems(
             PostUpdate,
-            (origin::add_origin_state::<C>.in_set(Set::SVG),),
-        )
-        .add_systems(
-            Last,
-            (
-                origin::apply_origin::<C>,
-                svg_mesh_linker::<C>.in_set(Set::SVG),
-            ),
+            crate::collider::update_collider_outlines::<C>.in_set(Set::SVG),
         );
     }
 }
@@ -76,7 +113,9 @@ fn svg_mesh_linker<C: SvgComponent>(
         if svg_component.is_changed() {
             *material = C::new_material(svg_component.get_handle().clone());
         }
-        if changed_handles.contains(&svg_component.get_handle().id()) {
+        // A swapped handle may point to an asset that is already loaded and won't send an event.
+        if svg_component.is_changed() || changed_handles.contains(&svg_component.get_handle().id())
+        {
             if let Some(svg) = svgs.get(svg_component.get_handle()) {
                 *C::get_mesh_mut(&mut mesh) = svg.mesh.clone();
             }
@@ -0,0 +1,94 @@
+use usvg::ImageKind;
+
+/// Returns the width and height in pixels of an embedded image, read from the header of its
+/// data. Nested SVGs are rasterized at their size. `None` if the header can't be read.
+pub(crate) fn pixel_size(kind: &ImageKind) -> Option<(u32, u32)> {
+    match kind {
+        ImageKind::PNG(data) => png_size(data),
+        ImageKind::JPEG(data) => jpeg_size(data),
+        ImageKind::GIF(data) => gif_size(data),
+        ImageKind::WEBP(data) => webp_size(data),
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        ImageKind::SVG(tree) => Some((
+            tree.size().width().ceil() as u32,
+            tree.size().height().ceil() as u32,
+        )),
+    }
+}
+
+fn be_u16(data: &[u8], at: usize) -> Option<u32> {
+    let bytes = data.get(at..at + 2)?;
+    Some(u32::from(u16::from_be_bytes([bytes[0], bytes[1]])))
+}
+
+fn le_u16(data: &[u8], at: usize) -> Option<u32> {
+    let bytes = data.get(at..at + 2)?;
+    Some(u32::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+}
+
+fn be_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u24(data: &[u8], at: usize) -> Option<u32> {
+    let bytes = data.get(at..at + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+fn png_size(data: &[u8]) -> Option<(u32, u32)> {
+    // The `IHDR` chunk comes first, right after the signature.
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be_u32(data, 16)?, be_u32(data, 20)?))
+}
+
+fn gif_size(data: &[u8]) -> Option<(u32, u32)> {
+    if !data.starts_with(b"GIF8") {
+        return None;
+    }
+    Some((le_u16(data, 6)?, le_u16(data, 8)?))
+}
+
+fn jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+    if !data.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+    // Walk the segments up to the start of frame, which holds the size.
+    let mut at = 2;
+    loop {
+        if *data.get(at)? != 0xff {
+            return None;
+        }
+        let marker = *data.get(at + 1)?;
+        match marker {
+            // Fill bytes before a marker.
+            0xff => at += 1,
+            // Markers without a segment.
+            0x01 | 0xd0..=0xd9 => at += 2,
+            // Start of frame, except for the markers of Huffman and arithmetic coding tables.
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                return Some((be_u16(data, at + 7)?, be_u16(data, at + 5)?));
+            }
+            _ => at += 2 + usize::try_from(be_u16(data, at + 2)?).ok()?,
+        }
+    }
+}
+
+fn webp_size(data: &[u8]) -> Option<(u32, u32)> {
+    if data.get(0..4)? != b"RIFF" || data.get(8..12)? != b"WEBP" {
+        return None;
+    }
+    match data.get(12..16)? {
+        // Lossy, the frame header follows a start code.
+        b"VP8 " => Some((le_u16(data, 26)? & 0x3fff, le_u16(data, 28)? & 0x3fff)),
+        // Lossless, 14 bits for each dimension minus one.
+        b"VP8L" => {
+            let bits = le_u24(data, 21)? | (u32::from(*data.get(24)?) << 24);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        // Extended, 24 bits for each dimension minus one.
+        b"VP8X" => Some((le_u24(data, 24)? + 1, le_u24(data, 27)? + 1)),
+        _ => None,
+    }
+}
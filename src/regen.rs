@@ -0,0 +1,230 @@
+use bevy::{
+    asset::{AssetId, Assets},
+    diagnostic::{DiagnosticPath, Diagnostics},
+    ecs::entity::Entities,
+    mesh::Mesh,
+    platform::collections::HashMap,
+    prelude::*,
+    tasks::{futures::check_ready, AsyncComputeTaskPool, Task},
+};
+
+use crate::{svg::Svg, variants::SvgMeshVariants};
+
+/// Diagnostic with the number of mesh variants waiting for or in tessellation.
+pub const SVG_REGEN_QUEUE_LENGTH: DiagnosticPath =
+    DiagnosticPath::const_new("svg/regen_queue_length");
+
+/// Priority of an entity's mesh variants in the [`SvgRegenQueue`], higher is earlier. Entities
+/// without it have priority `0`.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SvgRegenPriority(pub i32);
+
+type Tessellate = Box<dyn FnOnce() -> Mesh + Send + Sync>;
+
+/// A variant of an SVG, identified by the asset and its variant key.
+type Variant = (AssetId<Svg>, u64);
+
+struct Job {
+    priority: i32,
+    sequence: u64,
+    tessellate: Tessellate,
+}
+
+struct Running {
+    variant: Variant,
+    task: Task<Mesh>,
+}
+
+/// Queue of mesh variants to tessellate in the background, so many changes in the same frame,
+/// e.g. restyling hundreds of icons, don't stall it.
+///
+/// Features request the variant an entity needs, which is tessellated on the
+/// [`AsyncComputeTaskPool`] and then added to the [`SvgMeshVariants`]. Until then, entities keep
+/// drawing their previous mesh. Entities that need the same variant share one job, which runs
+/// with the highest priority among them. Only the newest request of an entity counts: it stops
+/// waiting for its older variant, whose job is dropped once nothing waits for it anymore, as
+/// when all its entities are despawned. So everything that changes the mesh of an entity has to
+/// go into one request, with a key that includes the
+/// [`generation`](SvgMeshVariants::generation) of the asset.
+#[derive(Resource)]
+pub struct SvgRegenQueue {
+    /// Maximum number of jobs started per frame.
+    pub budget: usize,
+    pending: HashMap<Variant, Job>,
+    running: Vec<Running>,
+    /// The variant each entity requested last, until it is done.
+    requested: HashMap<Entity, Variant>,
+    sequence: u64,
+}
+
+impl Default for SvgRegenQueue {
+    fn default() -> Self {
+        Self {
+            budget: 16,
+            pending: HashMap::default(),
+            running: Vec::new(),
+            requested: HashMap::default(),
+            sequence: 0,
+        }
+    }
+}
+
+impl SvgRegenQueue {
+    /// Returns `true` if the variant `key` of `svg` is the one `entity` waits for.
+    #[must_use]
+    pub fn is_requested(&self, entity: Entity, svg: AssetId<Svg>, key: u64) -> bool {
+        self.requested.get(&entity) == Some(&(svg, key))
+    }
+
+    /// Returns `true` if the variant `key` of `svg` waits for or is in tessellation.
+    #[must_use]
+    pub fn is_queued(&self, svg: AssetId<Svg>, key: u64) -> bool {
+        self.pending.contains_key(&(svg, key))
+            || self
+                .running
+                .iter()
+                .any(|running| running.variant == (svg, key))
+    }
+
+    /// Lets `entity` wait for the variant `key` of `svg` if it is already queued, replacing any
+    /// earlier request of the entity. Returns `false` if it isn't, so it has to be
+    /// [requested](Self::request) with a way to tessellate it.
+    pub fn join(&mut self, entity: Entity, svg: AssetId<Svg>, key: u64, priority: i32) -> bool {
+        if !self.is_queued(svg, key) {
+            return false;
+        }
+        self.wait(entity, (svg, key));
+        if let Some(job) = self.pending.get_mut(&(svg, key)) {
+            job.priority = job.priority.max(priority);
+        }
+        true
+    }
+
+    /// Requests the variant `key` of `svg` for `entity`, created by `tessellate` unless it is
+    /// already queued for another entity. Replaces any earlier request of the entity.
+    pub fn request(
+        &mut self,
+        entity: Entity,
+        svg: AssetId<Svg>,
+        key: u64,
+        priority: i32,
+        tessellate: impl FnOnce() -> Mesh + Send + Sync + 'static,
+    ) {
+        if self.is_requested(entity, svg, key) || self.join(entity, svg, key, priority) {
+            return;
+        }
+        self.wait(entity, (svg, key));
+        self.sequence += 1;
+        self.pending.insert(
+            (svg, key),
+            Job {
+                priority,
+                sequence: self.sequence,
+                tessellate: Box::new(tessellate),
+            },
+        );
+    }
+
+    /// Withdraws the request of `entity`, e.g. because it doesn't need a variant anymore.
+    pub fn cancel(&mut self, entity: Entity) {
+        if let Some(variant) = self.requested.remove(&entity) {
+            self.drop_unwanted(variant);
+        }
+    }
+
+    /// Number of jobs that wait or run.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.running.len()
+    }
+
+    /// Returns `true` if no job waits or runs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Makes `entity` wait for `variant` instead of the one it requested before.
+    fn wait(&mut self, entity: Entity, variant: Variant) {
+        if let Some(previous) = self.requested.insert(entity, variant) {
+            if previous != variant {
+                self.drop_unwanted(previous);
+            }
+        }
+    }
+
+    fn is_wanted(&self, variant: Variant) -> bool {
+        self.requested
+            .values()
+            .any(|requested| *requested == variant)
+    }
+
+    /// Drops the pending job of `variant` if no entity waits for it anymore. A running job
+    /// can't be stopped, its result is dropped when it finishes.
+    fn drop_unwanted(&mut self, variant: Variant) {
+        if !self.is_wanted(variant) {
+            self.pending.remove(&variant);
+        }
+    }
+}
+
+/// Collects finished jobs of the [`SvgRegenQueue`] and starts as many pending ones as the
+/// budget allows, highest priority and oldest first. Requests of despawned entities are
+/// withdrawn first.
+pub fn process_regen_queue(
+    mut queue: ResMut<SvgRegenQueue>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut variants: ResMut<SvgMeshVariants>,
+    entities: &Entities,
+    mut diagnostics: Diagnostics,
+) {
+    let queue = &mut *queue;
+    let despawned: Vec<Entity> = queue
+        .requested
+        .keys()
+        .copied()
+        .filter(|entity| !entities.contains_spawned(*entity))
+        .collect();
+    for entity in despawned {
+        queue.cancel(entity);
+    }
+
+    let mut index = 0;
+    while index < queue.running.len() {
+        let Some(mesh) = check_ready(&mut queue.running[index].task) else {
+            index += 1;
+            continue;
+        };
+        let done = queue.running.swap_remove(index);
+        // Stale if all entities requested something else in the meantime.
+        if queue.is_wanted(done.variant) {
+            queue
+                .requested
+                .retain(|_, requested| *requested != done.variant);
+            let (svg, key) = done.variant;
+            variants.insert(svg, key, meshes.add(mesh));
+        }
+    }
+
+    let mut order: Vec<_> = queue
+        .pending
+        .iter()
+        .map(|(variant, job)| (core::cmp::Reverse(job.priority), job.sequence, *variant))
+        .collect();
+    order.sort_unstable();
+    let pool = AsyncComputeTaskPool::get();
+    for (_, _, variant) in order.into_iter().take(queue.budget) {
+        let Some(job) = queue.pending.remove(&variant) else {
+            continue;
+        };
+        let tessellate = job.tessellate;
+        queue.running.push(Running {
+            variant,
+            task: pool.spawn(async move { tessellate() }),
+        });
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let len = queue.len() as f64;
+    diagnostics.add_measurement(&SVG_REGEN_QUEUE_LENGTH, || len);
+}
@@ -1,6 +1,7 @@
+#[cfg(any(feature = "2d", feature = "3d"))]
 mod plugin;
 pub mod tessellation;
-mod vertex_buffer;
+pub mod vertex_buffer;
 use crate::svg::Svg;
 use bevy::{
     ecs::{component::Mutable, lifecycle::HookContext, world::DeferredWorld},
@@ -12,17 +13,25 @@ pub mod svg2d;
 #[cfg(feature = "3d")]
 pub mod svg3d;
 
+#[cfg(any(feature = "2d", feature = "3d"))]
 pub use plugin::SvgPlugin;
 
-pub trait SvgComponent: Component {
-    type MeshComponent: Component<Mutability = Mutable>;
+pub trait SvgComponent: Component<Mutability = Mutable> {
+    type MeshComponent: Component<Mutability = Mutable> + Default;
     type MaterialComponent: Component<Mutability = Mutable>;
 
     fn get_handle(&self) -> &Handle<Svg>;
+    fn get_handle_mut(&mut self) -> &mut Handle<Svg>;
     fn new_material(svg: Handle<Svg>) -> Self::MaterialComponent;
     fn get_mesh_mut(mesh: &mut Self::MeshComponent) -> &mut Handle<Mesh>;
+    fn get_material_mut(material: &mut Self::MaterialComponent) -> &mut Handle<Svg>;
 }
 
+/// Marker for SVG entities whose SVG side is disabled, because they conflict with another
+/// renderer on the same entity.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct SvgInert;
+
 fn svg_on_insert<C: SvgComponent>(mut world: DeferredWorld, context: HookContext) {
     let entity = context.entity;
     let component = world.entity(entity).get_components::<&C>().unwrap();
@@ -1,5 +1,19 @@
-use crate::resources::{FillTessellator, StrokeTessellator};
-use bevy::app::{App, Plugin};
+use crate::{
+    plugin::Set,
+    regen::{process_regen_queue, SvgRegenQueue, SVG_REGEN_QUEUE_LENGTH},
+    resources::{FillTessellator, StrokeTessellator},
+    style::{invalidate_material_overrides, SvgMaterialOverrides},
+    variants::{invalidate_mesh_variants, SvgMeshVariants},
+};
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    diagnostic::{Diagnostic, RegisterDiagnostic as _},
+    prelude::IntoScheduleConfigs as _,
+    render::RenderApp,
+};
+
+#[cfg(feature = "2d")]
+use bevy::transform::TransformSystems;
 
 #[cfg(feature = "2d")]
 use crate::render::svg2d;
@@ -13,7 +27,41 @@ impl Plugin for SvgPlugin {
     fn build(&self, app: &mut App) {
         let fill_tess = FillTessellator::default();
         let stroke_tess = StrokeTessellator::default();
-        app.insert_resource(fill_tess).insert_resource(stroke_tess);
+        app.insert_resource(fill_tess)
+            .insert_resource(stroke_tess)
+            .init_resource::<SvgMeshVariants>()
+            .init_resource::<SvgRegenQueue>()
+            .init_resource::<SvgMaterialOverrides>()
+            .register_diagnostic(Diagnostic::new(SVG_REGEN_QUEUE_LENGTH))
+            .add_systems(
+                PostUpdate,
+                (
+                    invalidate_mesh_variants.before(Set::SVG),
+                    invalidate_material_overrides.before(Set::SVG),
+                    process_regen_queue.after(Set::SVG),
+                ),
+            );
+
+        // Also without a renderer, so a sprite never gets the origin offset of its SVG, and
+        // textures are set up wherever there are images.
+        #[cfg(feature = "2d")]
+        app.add_systems(
+            PostUpdate,
+            (
+                svg2d::handle_sprite_conflicts
+                    .before(Set::SVG)
+                    .before(TransformSystems::Propagate),
+                crate::texture::update_svg_textures.in_set(Set::SVG),
+            ),
+        );
+
+        // The materials need the renderer. Without it, e.g. in headless tests with
+        // `MinimalPlugins`, SVGs still load and entities still get their meshes, they just aren't
+        // drawn. A renderer added after this plugin is caught in `finish`, which is too late to
+        // add the materials.
+        if !app.is_plugin_added::<bevy::render::RenderPlugin>() {
+            return;
+        }
 
         #[cfg(feature = "2d")]
         app.add_plugins(svg2d::RenderPlugin);
@@ -21,4 +69,15 @@ impl Plugin for SvgPlugin {
         #[cfg(feature = "3d")]
         app.add_plugins(svg3d::RenderPlugin);
     }
+
+    fn finish(&self, app: &mut App) {
+        #[cfg(feature = "2d")]
+        let materials_added = app.is_plugin_added::<svg2d::RenderPlugin>();
+        #[cfg(not(feature = "2d"))]
+        let materials_added = app.is_plugin_added::<svg3d::RenderPlugin>();
+        assert!(
+            materials_added || app.get_sub_app(RenderApp).is_none(),
+            "`SvgPlugin` was added before Bevy's `RenderPlugin`, so its materials are missing. Add it after `DefaultPlugins`."
+        );
+    }
 }
@@ -11,12 +11,13 @@ pub const SVG_2D_SHADER_HANDLE: Handle<Shader> =
 
 pub use plugin::RenderPlugin;
 
-use crate::{origin::Origin, svg::Svg};
+use crate::{origin::OriginState, svg::Svg};
 
-use super::{svg_on_insert, SvgComponent};
+use super::{svg_on_insert, SvgComponent, SvgInert};
 
+/// Draws an [`Svg`] in 2D.
 #[derive(Component, Default)]
-#[require(Mesh2d, Origin, Transform, Visibility)]
+#[require(Mesh2d, Transform, Visibility)]
 #[component(on_insert = svg_on_insert::<Svg2d>)]
 pub struct Svg2d(pub Handle<Svg>);
 
@@ -28,6 +29,10 @@ impl SvgComponent for Svg2d {
         &self.0
     }
 
+    fn get_handle_mut(&mut self) -> &mut Handle<Svg> {
+        &mut self.0
+    }
+
     fn new_material(svg: Handle<Svg>) -> Self::MaterialComponent {
         MeshMaterial2d(svg)
     }
@@ -35,4 +40,46 @@ impl SvgComponent for Svg2d {
     fn get_mesh_mut(mesh: &mut Self::MeshComponent) -> &mut Handle<Mesh> {
         &mut mesh.0
     }
+
+    fn get_material_mut(material: &mut Self::MaterialComponent) -> &mut Handle<Svg> {
+        &mut material.0
+    }
+}
+
+/// An entity can't be drawn as both a [`Sprite`] and an [`Svg2d`]. The SVG side of such an
+/// entity is made inert, i.e. it isn't drawn and its origin isn't applied, until the sprite
+/// is removed again.
+pub(crate) fn handle_sprite_conflicts(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut Svg2d,
+            &mut Transform,
+            Has<Sprite>,
+            Has<SvgInert>,
+        ),
+        Or<(With<Sprite>, With<SvgInert>)>,
+    >,
+) {
+    for (entity, mut svg, mut transform, has_sprite, is_inert) in &mut query {
+        if has_sprite && !is_inert {
+            warn!(
+                "Entity {entity} has both a `Sprite` and an `Svg2d`, the `Svg2d` will be ignored until the `Sprite` is removed"
+            );
+            commands
+                .entity(entity)
+                .insert(SvgInert)
+                .remove::<(MeshMaterial2d<Svg>, OriginState)>();
+            // Let transform propagation drop an origin offset that was already applied.
+            transform.set_changed();
+        } else if !has_sprite && is_inert {
+            commands
+                .entity(entity)
+                .remove::<SvgInert>()
+                .insert(Svg2d::new_material(svg.0.clone()));
+            svg.set_changed();
+            transform.set_changed();
+        }
+    }
 }
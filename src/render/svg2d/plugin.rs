@@ -1,11 +1,22 @@
 use bevy::{
     app::{App, Plugin},
     asset::{load_internal_asset, AssetApp as _},
+    mesh::MeshVertexBufferLayoutRef,
+    render::render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
     shader::{Shader, ShaderRef},
-    sprite_render::{Material2d, Material2dPlugin},
+    sprite_render::{AlphaMode2d, Material2d, Material2dKey, Material2dPlugin},
 };
 
-use crate::{render::svg2d::SVG_2D_SHADER_HANDLE, svg::Svg};
+use crate::{
+    render::{
+        svg2d::SVG_2D_SHADER_HANDLE,
+        vertex_buffer::{
+            SVG_2D_LOCATION_COLOR, SVG_ATTRIBUTE_COLOR, SVG_ATTRIBUTE_POSITION,
+            SVG_LOCATION_POSITION,
+        },
+    },
+    svg::{Svg, SvgBlendMode},
+};
 
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
 pub struct RenderPlugin;
@@ -23,4 +34,29 @@ impl Material2d for Svg {
     fn fragment_shader() -> ShaderRef {
         SVG_2D_SHADER_HANDLE.into()
     }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        match self.blend_mode {
+            // Translucent fills and anti-aliasing fringes need blending, just like sprites.
+            SvgBlendMode::Blend => AlphaMode2d::Blend,
+            // Coverage replaces blending, see `specialize`.
+            SvgBlendMode::AlphaToCoverage => AlphaMode2d::Opaque,
+        }
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // wgpu rejects alpha to coverage without multisampling.
+        descriptor.multisample.alpha_to_coverage_enabled =
+            key.bind_group_data == SvgBlendMode::AlphaToCoverage && key.mesh_key.msaa_samples() > 1;
+        let vertex_layout = layout.0.get_layout(&[
+            SVG_ATTRIBUTE_POSITION.at_shader_location(SVG_LOCATION_POSITION),
+            SVG_ATTRIBUTE_COLOR.at_shader_location(SVG_2D_LOCATION_COLOR),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        Ok(())
+    }
 }
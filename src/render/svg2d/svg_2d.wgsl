@@ -1,5 +1,4 @@
 #import bevy_sprite::{
-    mesh2d_types::Mesh2d,
     mesh2d_view_bindings::view,
     mesh2d_vertex_output::VertexOutput,
 }
@@ -9,13 +8,13 @@
 #endif
 
 
-@group(2) @binding(0)
-var<uniform> mesh: Mesh2d;
+// `Svg::tint`
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> tint: vec4<f32>;
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 #ifdef VERTEX_COLORS
-    var color = in.color;
+    var color = in.color * tint;
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
 #endif
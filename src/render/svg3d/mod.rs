@@ -3,36 +3,51 @@ use bevy::{
     prelude::*,
 };
 
+mod pbr;
 mod plugin;
 
 /// Handle to the custom shader with a unique random ID
 pub const SVG_3D_SHADER_HANDLE: Handle<Shader> =
     uuid_handle!("c7d158fe-e3de-11f0-87bd-33279dc14325");
 
+pub use pbr::SvgPbr;
 pub use plugin::RenderPlugin;
 
-use crate::{origin::Origin, svg::Svg};
+use crate::svg::Svg;
 
 use super::{svg_on_insert, SvgComponent};
 
+/// Draws an [`Svg`] in 3D.
+///
+/// Only the side the mesh faces is drawn, `+Z` unless configured otherwise with
+/// [`SvgFacing`](crate::prelude::SvgFacing) or the [`Facing`](crate::prelude::Facing) of the
+/// [`SvgSettings`](crate::prelude::SvgSettings).
 #[derive(Component, Default)]
-#[require(Mesh3d, Origin, Transform, Visibility)]
+#[require(Mesh3d, Transform, Visibility)]
 #[component(on_insert = svg_on_insert::<Svg3d>)]
 pub struct Svg3d(pub Handle<Svg>);
 
 impl SvgComponent for Svg3d {
     type MeshComponent = Mesh3d;
-    type MaterialComponent = MeshMaterial2d<Svg>;
+    type MaterialComponent = MeshMaterial3d<Svg>;
 
     fn get_handle(&self) -> &Handle<Svg> {
         &self.0
     }
 
+    fn get_handle_mut(&mut self) -> &mut Handle<Svg> {
+        &mut self.0
+    }
+
     fn new_material(svg: Handle<Svg>) -> Self::MaterialComponent {
-        MeshMaterial2d(svg)
+        MeshMaterial3d(svg)
     }
 
     fn get_mesh_mut(mesh: &mut Self::MeshComponent) -> &mut Handle<Mesh> {
         &mut mesh.0
     }
+
+    fn get_material_mut(material: &mut Self::MaterialComponent) -> &mut Handle<Svg> {
+        &mut material.0
+    }
 }
@@ -0,0 +1,92 @@
+use bevy::{
+    asset::Assets,
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::*,
+};
+
+use crate::{render::svg3d::Svg3d, svg::Svg};
+
+/// Draws a 3D SVG with physically based lighting instead of its flat colors, e.g. to make a
+/// logo look like gold.
+///
+/// The colors of the SVG become the base color of a [`StandardMaterial`] with these values,
+/// which is shared by all paths. The mesh gets normals along its
+/// [`SvgFacing`](crate::prelude::SvgFacing) and both sides are drawn.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgPbr {
+    /// See [`StandardMaterial::metallic`].
+    pub metallic: f32,
+    /// See [`StandardMaterial::perceptual_roughness`].
+    pub perceptual_roughness: f32,
+    /// See [`StandardMaterial::reflectance`].
+    pub reflectance: f32,
+}
+
+impl Default for SvgPbr {
+    fn default() -> Self {
+        let material = StandardMaterial::default();
+        Self {
+            metallic: material.metallic,
+            perceptual_roughness: material.perceptual_roughness,
+            reflectance: material.reflectance,
+        }
+    }
+}
+
+impl SvgPbr {
+    fn material(&self) -> StandardMaterial {
+        StandardMaterial {
+            metallic: self.metallic,
+            perceptual_roughness: self.perceptual_roughness,
+            reflectance: self.reflectance,
+            double_sided: true,
+            cull_mode: None,
+            ..Default::default()
+        }
+    }
+}
+
+/// Replaces the flat material of 3D SVGs with a [`SvgPbr`] by a lit one, and restores it when
+/// the component is removed. Their mesh variant with normals comes from
+/// [`apply_mesh_variants`](crate::variants::apply_mesh_variants).
+pub(crate) fn apply_pbr(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(
+        Entity,
+        Ref<SvgPbr>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Has<MeshMaterial3d<Svg>>,
+    )>,
+    mut removed: RemovedComponents<SvgPbr>,
+    restore: Query<&Svg3d, Without<SvgPbr>>,
+) {
+    for entity in removed.read() {
+        if let Ok(svg_component) = restore.get(entity) {
+            commands
+                .entity(entity)
+                .remove::<MeshMaterial3d<StandardMaterial>>()
+                .insert(MeshMaterial3d(svg_component.0.clone()));
+        }
+    }
+
+    for (entity, pbr, material, has_svg_material) in &query {
+        if has_svg_material {
+            commands.entity(entity).remove::<MeshMaterial3d<Svg>>();
+        }
+        match material {
+            Some(material) => {
+                if pbr.is_changed() {
+                    if let Some(material) = materials.get_mut(&material.0) {
+                        *material = pbr.material();
+                    }
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(MeshMaterial3d(materials.add(pbr.material())));
+            }
+        }
+    }
+}
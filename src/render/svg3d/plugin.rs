@@ -1,13 +1,25 @@
 use bevy::{
-    app::{App, Plugin},
+    app::{App, Plugin, PostUpdate},
     asset::{load_internal_asset, AssetApp as _},
-    pbr::{Material, MaterialPlugin},
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin},
+    prelude::AlphaMode,
+    prelude::IntoScheduleConfigs as _,
+    render::render_resource::{
+        Face, FrontFace, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    },
     shader::{Shader, ShaderRef},
 };
 
-use crate::svg::Svg;
+use crate::{
+    plugin::Set,
+    render::vertex_buffer::{
+        SVG_3D_LOCATION_COLOR, SVG_ATTRIBUTE_COLOR, SVG_ATTRIBUTE_POSITION, SVG_LOCATION_POSITION,
+    },
+    svg::{Svg, SvgBlendMode},
+};
 
-use super::SVG_3D_SHADER_HANDLE;
+use super::{pbr::apply_pbr, SVG_3D_SHADER_HANDLE};
 
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
 pub struct RenderPlugin;
@@ -17,7 +29,8 @@ impl Plugin for RenderPlugin {
         load_internal_asset!(app, SVG_3D_SHADER_HANDLE, "svg_3d.wgsl", Shader::from_wgsl);
 
         app.add_plugins(MaterialPlugin::<Svg>::default())
-            .register_asset_reflect::<Svg>();
+            .register_asset_reflect::<Svg>()
+            .add_systems(PostUpdate, apply_pbr.in_set(Set::SVG));
     }
 }
 
@@ -25,4 +38,29 @@ impl Material for Svg {
     fn fragment_shader() -> ShaderRef {
         SVG_3D_SHADER_HANDLE.into()
     }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        match self.blend_mode {
+            SvgBlendMode::Blend => AlphaMode::Opaque,
+            SvgBlendMode::AlphaToCoverage => AlphaMode::AlphaToCoverage,
+        }
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[
+            SVG_ATTRIBUTE_POSITION.at_shader_location(SVG_LOCATION_POSITION),
+            SVG_ATTRIBUTE_COLOR.at_shader_location(SVG_3D_LOCATION_COLOR),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        // Tessellation winds every triangle counter-clockwise as seen from its `Facing`, so
+        // only the back side is culled.
+        descriptor.primitive.front_face = FrontFace::Ccw;
+        descriptor.primitive.cull_mode = Some(Face::Back);
+        Ok(())
+    }
 }
@@ -1,11 +1,10 @@
 #import bevy_pbr::{
-    forward_io::{VertexOutput, FragmentOutput}
-    mesh_types::Mesh,
+    forward_io::{VertexOutput, FragmentOutput},
     mesh_view_bindings,
 }
 
-@group(2) @binding(0)
-var<uniform> mesh: Mesh;
+// `Svg::tint`
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> tint: vec4<f32>;
 
 @fragment
 fn fragment(
@@ -13,6 +12,6 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     var out: FragmentOutput;
-    out.color = in.color;
+    out.color = in.color * tint;
     return out;
 }
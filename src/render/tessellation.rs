@@ -1,70 +1,459 @@
+use core::{
+    hash::{Hash as _, Hasher as _},
+    ops::Range,
+};
+use std::hash::DefaultHasher;
+
 use bevy::{
-    log::{debug, error},
-    math::Vec3,
+    color::{Color, ColorToComponents as _},
+    log::debug,
+    math::{Vec3, Vec3Swizzles as _},
+    platform::collections::HashMap,
     transform::components::Transform,
 };
-use lyon_tessellation::{BuffersBuilder, FillOptions, FillTessellator, StrokeTessellator};
+use lyon_path::PathEvent;
+use lyon_tessellation::{
+    BuffersBuilder, FillTessellator, LineJoin, Side, StrokeOptions, StrokeTessellator,
+    TessellationError,
+};
+
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    render::vertex_buffer::{BufferExt as _, VertexBuffers, VertexConstructor},
+    render::vertex_buffer::{
+        BufferExt as _, FringeVertexConstructor, VertexBuffers, VertexConstructor,
+    },
     svg::{DrawType, Svg},
 };
 
+/// The side of the xy-plane the triangles of a mesh face, i.e. from where their vertices
+/// appear counter-clockwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Facing {
+    /// Triangles face `+Z`, towards a default camera.
+    #[default]
+    PositiveZ,
+    /// Triangles face `-Z`, e.g. for the inside of a deformed cylinder.
+    NegativeZ,
+}
+
+impl Facing {
+    /// The normal of triangles facing this side.
+    #[must_use]
+    pub const fn normal(self) -> [f32; 3] {
+        match self {
+            Self::PositiveZ => [0.0, 0.0, 1.0],
+            Self::NegativeZ => [0.0, 0.0, -1.0],
+        }
+    }
+}
+
+/// The color space the colors of mesh vertices are stored in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VertexColorSpace {
+    /// Linear RGB, which Bevy's 2D and 3D mesh pipelines expect. They convert to the color
+    /// space of the render target themselves.
+    #[default]
+    Linear,
+    /// Gamma-encoded sRGB, for custom shaders that expect it, e.g. because they write to a
+    /// render target without sRGB conversion.
+    Srgb,
+}
+
+impl VertexColorSpace {
+    /// The RGBA components of `color` in this color space.
+    #[must_use]
+    pub fn components(self, color: Color) -> [f32; 4] {
+        match self {
+            Self::Linear => color.to_linear().to_f32_array(),
+            Self::Srgb => color.to_srgba().to_f32_array(),
+        }
+    }
+}
+
+/// Options that control how an [`Svg`] is tessellated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TessellationOptions {
+    /// Minimum width of strokes in SVG document units. Thinner strokes are widened to it,
+    /// wider strokes are untouched.
+    pub min_stroke_width: f32,
+    /// Factor applied to the width of all strokes before [`min_stroke_width`](Self::min_stroke_width).
+    pub stroke_scale: f32,
+    /// Width of the anti-aliasing fringe around fills in SVG document units, `0.0` disables it.
+    ///
+    /// The fringe fades a fill out to transparent over this width, independent of MSAA.
+    /// `1.0` matches a single standard fringe at a scale of one pixel per unit, larger values
+    /// give softer edges. Off by default, because the fringe changes the shape of the mesh:
+    /// - It is centered on the outline, so fills grow by half its width and translucent fills
+    ///   get a darker rim where its inner half overlaps them.
+    /// - It is given in document units, so it widens with the scale of the entity.
+    /// - Opaque materials, like the default one of [`Svg3d`](crate::prelude::Svg3d), draw it
+    ///   as a solid band.
+    /// - Its outer side follows the winding of the whole path, so holes of even-odd fills
+    ///   that are wound like their outer contour fade out towards their inside.
+    pub edge_aa_width: f32,
+    /// Output a non-indexed mesh with per-triangle barycentric coordinates, so a shader can
+    /// draw triangle edges, e.g. for wireframes.
+    ///
+    /// This disables index-based vertex sharing, every triangle gets its own three vertices.
+    pub barycentric: bool,
+    /// Distance in SVG document units by which fills are expanded outward, `0.0` disables it.
+    ///
+    /// Fills that share an edge are tessellated independently, so their edges don't meet
+    /// exactly and the background shows through hairline cracks, e.g. in tile maps. A
+    /// sub-pixel expansion makes neighbors overlap instead. Translucent fills appear slightly
+    /// more opaque where they overlap.
+    pub seam_overdraw: f32,
+    /// The side all triangles face. Lyon winds triangles inconsistently, so they are rewound
+    /// to face this side, which matters when back faces are culled.
+    pub facing: Facing,
+    /// The color space vertex colors are stored in.
+    pub color_space: VertexColorSpace,
+    /// Divide the tolerances of paths by the scale of their transforms, so curves are
+    /// flattened to the tolerance in document units instead of path units. Paths that are
+    /// scaled up a lot, e.g. by a tiny view box, are flattened coarsely without it, see
+    /// [`Svg::view_box_scale`].
+    pub scale_tolerance: bool,
+}
+
+impl Default for TessellationOptions {
+    fn default() -> Self {
+        Self {
+            min_stroke_width: 0.0,
+            stroke_scale: 1.0,
+            edge_aa_width: 0.0,
+            barycentric: false,
+            seam_overdraw: 0.0,
+            facing: Facing::PositiveZ,
+            color_space: VertexColorSpace::Linear,
+            scale_tolerance: false,
+        }
+    }
+}
+
+/// Tessellates all paths of `svg` for which `visible` returns `true` into one buffer. Also
+/// returns the range of vertices that belongs to each path, which is empty for hidden paths,
+/// and the errors of the paths that couldn't be tessellated completely, by path index.
 pub fn generate_buffer(
     svg: &Svg,
     fill_tess: &mut FillTessellator,
     stroke_tess: &mut StrokeTessellator,
-) -> VertexBuffers {
+    options: &TessellationOptions,
+    visible: &dyn Fn(usize) -> bool,
+) -> (
+    VertexBuffers,
+    Vec<Range<u32>>,
+    Vec<(usize, TessellationError)>,
+) {
+    let mut buffers = VertexBuffers::new();
+    let (vertex_ranges, errors) =
+        generate_buffer_into(svg, fill_tess, stroke_tess, options, visible, &mut buffers);
+    (buffers, vertex_ranges, errors)
+}
+
+/// Like [`generate_buffer`], tessellating into `buffers` after clearing them, so their
+/// allocations are reused.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn generate_buffer_into(
+    svg: &Svg,
+    fill_tess: &mut FillTessellator,
+    stroke_tess: &mut StrokeTessellator,
+    options: &TessellationOptions,
+    visible: &dyn Fn(usize) -> bool,
+    buffers: &mut VertexBuffers,
+) -> (Vec<Range<u32>>, Vec<(usize, TessellationError)>) {
     debug!("Tessellating SVG: {}", svg.name);
 
-    let flip_y = Transform::from_scale(Vec3::new(1.0, -1.0, 1.0));
-    let mut buffers = VertexBuffers::new();
+    buffers.vertices.clear();
+    buffers.indices.clear();
+    let mut vertex_ranges = Vec::with_capacity(svg.paths.len());
+    let mut errors = Vec::new();
 
-    let mut color = None;
-    for path in &svg.paths {
-        let mut buffer = VertexBuffers::new();
+    for index in 0..svg.paths.len() {
+        if !visible(index) {
+            let end = buffers.vertices.len() as u32;
+            vertex_ranges.push(end..end);
+            continue;
+        }
+        let buffer = tessellate_path(svg, index, fill_tess, stroke_tess, options, &mut errors);
+        let start = buffers.vertices.len() as u32;
+        buffers.extend_one(buffer);
+        vertex_ranges.push(start..buffers.vertices.len() as u32);
+    }
+    rewind(buffers, options.facing);
+    debug!("Tessellating SVG: {} ... Done", svg.name);
+
+    (vertex_ranges, errors)
+}
+
+/// The tessellated paths of an [`Svg`], kept to tessellate edited versions of the document
+/// incrementally with [`Svg::tessellate_incremental`].
+#[derive(Clone, Debug)]
+pub struct SvgPathBuffers {
+    svg: Svg,
+    options: TessellationOptions,
+    paths: Vec<VertexBuffers>,
+    /// Errors of the paths that didn't tessellate completely, including reused ones.
+    errors: Vec<(usize, TessellationError)>,
+    reused: usize,
+}
+
+impl SvgPathBuffers {
+    /// Number of paths that were copied from the previous tessellation instead of being
+    /// tessellated again.
+    #[must_use]
+    pub const fn reused_count(&self) -> usize {
+        self.reused
+    }
 
-        if color.is_none() {
-            color = Some(path.color);
+    /// Errors of the paths that didn't tessellate completely, by index in the paths.
+    pub(crate) fn errors(&self) -> &[(usize, TessellationError)] {
+        &self.errors
+    }
+
+    /// Joins the paths into one buffer and returns the range of vertices of each path.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn join(&self) -> (VertexBuffers, Vec<Range<u32>>) {
+        let mut buffers = VertexBuffers::new();
+        let mut vertex_ranges = Vec::with_capacity(self.paths.len());
+        for path in &self.paths {
+            let offset = buffers.vertices.len() as u32;
+            buffers.vertices.extend_from_slice(&path.vertices);
+            buffers
+                .indices
+                .extend(path.indices.iter().map(|index| index + offset));
+            vertex_ranges.push(offset..buffers.vertices.len() as u32);
+        }
+        (buffers, vertex_ranges)
+    }
+}
+
+/// Tessellates every path of `svg` into a buffer of its own, rewound to the facing of
+/// `options`. Paths that are the same in `previous` are copied from it instead, if it was
+/// tessellated with the same options.
+pub(crate) fn generate_path_buffers(
+    svg: &Svg,
+    fill_tess: &mut FillTessellator,
+    stroke_tess: &mut StrokeTessellator,
+    options: &TessellationOptions,
+    previous: Option<&SvgPathBuffers>,
+) -> (SvgPathBuffers, Vec<(usize, TessellationError)>) {
+    let previous = previous.filter(|previous| previous.options == *options);
+    let mut by_content: HashMap<u64, Vec<usize>> = HashMap::new();
+    if let Some(previous) = previous {
+        for index in 0..previous.svg.paths.len() {
+            by_content
+                .entry(content_hash(&previous.svg, index))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut paths = Vec::with_capacity(svg.paths.len());
+    let mut errors = Vec::new();
+    let mut reused = 0;
+    for index in 0..svg.paths.len() {
+        // Most edits leave the other paths where they were, so try the same index first.
+        let same = previous.and_then(|previous| {
+            let candidates = by_content.get(&content_hash(svg, index))?;
+            core::iter::once(&index)
+                .chain(candidates)
+                .find(|&&candidate| {
+                    candidate < previous.paths.len()
+                        && same_path(svg, index, &previous.svg, candidate)
+                })
+                .map(|&candidate| (previous, candidate))
+        });
+        if let Some((previous, candidate)) = same {
+            paths.push(previous.paths[candidate].clone());
+            errors.extend(
+                previous
+                    .errors
+                    .iter()
+                    .filter(|(path, _)| *path == candidate)
+                    .map(|(_, error)| (index, error.clone())),
+            );
+            reused += 1;
+            continue;
         }
+        let mut buffer = tessellate_path(svg, index, fill_tess, stroke_tess, options, &mut errors);
+        rewind(&mut buffer, options.facing);
+        paths.push(buffer);
+    }
+    debug!(
+        "Tessellated SVG {} incrementally, reused {reused} of {} paths",
+        svg.name,
+        svg.paths.len()
+    );
+
+    let paths = SvgPathBuffers {
+        svg: svg.clone(),
+        options: *options,
+        paths,
+        errors: errors.clone(),
+        reused,
+    };
+    (paths, errors)
+}
+
+/// Hashes the end points of the segments of the path at `index`, to find paths that may be
+/// the same quickly.
+fn content_hash(svg: &Svg, index: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for event in svg.path_segments(&svg.paths[index]) {
+        let to = event.to();
+        [to.x, to.y].map(f32::to_bits).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Whether the path at `index` of `svg` tessellates like the one at `other_index` of `other`.
+#[allow(clippy::suspicious_operation_groupings)]
+fn same_path(svg: &Svg, index: usize, other: &Svg, other_index: usize) -> bool {
+    let (path, other_path) = (&svg.paths[index], &other.paths[other_index]);
+    path.abs_transform == other_path.abs_transform
+        && path.color == other_path.color
+        && path.draw_type == other_path.draw_type
+        && svg.path_segments(path) == other.path_segments(other_path)
+}
 
-        // Bevy has a different y-axis origin, so we need to flip that axis
-        let transform = flip_y * path.abs_transform;
-        match path.draw_type {
-            DrawType::Fill => {
-                if let Err(e) = fill_tess.tessellate(
-                    path.segments.clone(),
-                    &FillOptions::tolerance(0.001),
+/// Tessellates the path at `index` of `svg` into a buffer of its own, adding its errors to
+/// `errors`. The triangles aren't rewound to a facing yet.
+fn tessellate_path(
+    svg: &Svg,
+    index: usize,
+    fill_tess: &mut FillTessellator,
+    stroke_tess: &mut StrokeTessellator,
+    options: &TessellationOptions,
+    errors: &mut Vec<(usize, TessellationError)>,
+) -> VertexBuffers {
+    let flip_y = Transform::from_scale(Vec3::new(1.0, -1.0, 1.0));
+    let path = &svg.paths[index];
+    let mut buffer = VertexBuffers::new();
+
+    // Bevy has a different y-axis origin, so we need to flip that axis
+    let transform = flip_y * path.abs_transform;
+    let path_scale = path.abs_transform.scale.xy().abs().max_element();
+    let tolerance_scale = if options.scale_tolerance && path_scale > 0.0 {
+        path_scale
+    } else {
+        1.0
+    };
+    match path.draw_type {
+        DrawType::Fill(mut opts) => {
+            opts.tolerance /= tolerance_scale;
+            if let Err(e) = fill_tess.tessellate(
+                svg.path_segments(path).iter().copied(),
+                &opts,
+                &mut BuffersBuilder::new(
+                    &mut buffer,
+                    VertexConstructor {
+                        color: path.color,
+                        transform,
+                        color_space: options.color_space,
+                    },
+                ),
+            ) {
+                errors.push((index, e));
+            }
+
+            if options.seam_overdraw > 0.0 && path_scale > 0.0 {
+                // A stroke centered on the outline reaches half its width outward.
+                if let Err(e) = stroke_tess.tessellate(
+                    svg.path_segments(path).iter().copied(),
+                    &StrokeOptions::tolerance(0.01)
+                        .with_line_width(2.0 * options.seam_overdraw / path_scale)
+                        .with_line_join(LineJoin::Miter),
                     &mut BuffersBuilder::new(
                         &mut buffer,
                         VertexConstructor {
                             color: path.color,
                             transform,
+                            color_space: options.color_space,
                         },
                     ),
                 ) {
-                    error!("FillTessellator error: {:?}", e);
+                    errors.push((index, e));
                 }
             }
-            DrawType::Stroke(opts) => {
+            if options.edge_aa_width > 0.0 && path_scale > 0.0 {
+                // With a positive signed area the fill lies on the positive side of the
+                // outline, so the fringe has to fade out towards the negative side.
+                let outer_side = if signed_area(svg.path_segments(path)) > 0.0 {
+                    Side::Negative
+                } else {
+                    Side::Positive
+                };
                 if let Err(e) = stroke_tess.tessellate(
-                    path.segments.clone(),
-                    &opts,
+                    svg.path_segments(path).iter().copied(),
+                    &StrokeOptions::tolerance(0.01)
+                        .with_line_width(options.edge_aa_width / path_scale)
+                        .with_line_join(LineJoin::Round),
                     &mut BuffersBuilder::new(
                         &mut buffer,
-                        VertexConstructor {
+                        FringeVertexConstructor {
                             color: path.color,
                             transform,
+                            outer_side,
+                            color_space: options.color_space,
                         },
                     ),
                 ) {
-                    error!("StrokeTessellator error: {:?}", e);
+                    errors.push((index, e));
                 }
             }
         }
-        buffers.extend_one(buffer);
+        DrawType::Stroke(mut opts) => {
+            // The minimum width is given in document units, but the stroke is tessellated in
+            // path space, so it has to be scaled back.
+            opts.line_width *= options.stroke_scale;
+            opts.tolerance /= tolerance_scale;
+            if path_scale > 0.0 {
+                opts.line_width = opts.line_width.max(options.min_stroke_width / path_scale);
+            }
+            if let Err(e) = stroke_tess.tessellate(
+                svg.path_segments(path).iter().copied(),
+                &opts,
+                &mut BuffersBuilder::new(
+                    &mut buffer,
+                    VertexConstructor {
+                        color: path.color,
+                        transform,
+                        color_space: options.color_space,
+                    },
+                ),
+            ) {
+                errors.push((index, e));
+            }
+        }
     }
-    debug!("Tessellating SVG: {} ... Done", svg.name);
+    buffer
+}
+
+/// Reverses the triangles of `buffers` that don't face `facing`.
+fn rewind(buffers: &mut VertexBuffers, facing: Facing) {
+    for triangle in buffers.indices.chunks_exact_mut(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]]
+            .map(|index| buffers.vertices[index as usize].position());
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        let faces_positive_z = area > 0.0;
+        if faces_positive_z != (facing == Facing::PositiveZ) {
+            triangle.swap(1, 2);
+        }
+    }
+}
 
-    buffers
+/// Computes the signed area of the polygon spanned by the end points of `segments`.
+fn signed_area(segments: &[PathEvent]) -> f32 {
+    segments
+        .iter()
+        .map(|event| match *event {
+            PathEvent::Line { from, to }
+            | PathEvent::Quadratic { from, to, .. }
+            | PathEvent::Cubic { from, to, .. } => from.x * to.y - to.x * from.y,
+            PathEvent::End { last, first, .. } => last.x * first.y - first.x * last.y,
+            PathEvent::Begin { .. } => 0.0,
+        })
+        .sum::<f32>()
+        * 0.5
 }
@@ -1,12 +1,19 @@
+use core::ops::Range;
+
 use bevy::{
-    asset::RenderAssetUsages, color::{Color, ColorToComponents as _}, math::Vec3, mesh::{Indices, Mesh}, render::render_resource::PrimitiveTopology, transform::components::Transform
+    asset::RenderAssetUsages,
+    color::{Alpha as _, Color},
+    math::Vec3,
+    mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues},
+    render::render_resource::{PrimitiveTopology, VertexFormat},
+    transform::components::Transform,
 };
 use copyless::VecHelper as _;
 use lyon_tessellation::{
-    self, FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor,
+    self, FillVertex, FillVertexConstructor, Side, StrokeVertex, StrokeVertexConstructor,
 };
 
-use crate::Convert;
+use crate::{render::tessellation::VertexColorSpace, Convert};
 
 /// A vertex with all the necessary attributes to be inserted into a Bevy
 /// [`Mesh`](bevy::render::mesh::Mesh).
@@ -16,6 +23,13 @@ pub struct Vertex {
     color: [f32; 4],
 }
 
+impl Vertex {
+    /// Position of the vertex.
+    pub(crate) const fn position(&self) -> [f32; 3] {
+        self.position
+    }
+}
+
 /// The index type of a Bevy [`Mesh`](bevy::render::mesh::Mesh).
 pub type IndexType = u32;
 
@@ -32,19 +46,135 @@ impl Convert<Mesh> for VertexBuffers {
             colors.alloc().init(vert.color);
         }
 
-        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(SVG_ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(SVG_ATTRIBUTE_COLOR, colors);
         mesh.insert_indices(Indices::U32(self.indices));
 
         mesh
     }
 }
 
+/// Writes the buffers into `mesh`, reusing the allocations of its position, color and index
+/// buffers. Other attributes are removed, so the mesh has the same layout as a converted one.
+/// `mesh` has to be a [`PrimitiveTopology::TriangleList`]. The vertices of `buffers` are left
+/// empty, with their allocation, to be filled again.
+pub(crate) fn write_into_mesh(buffers: &mut VertexBuffers, mesh: &mut Mesh) {
+    let mut positions = match mesh.remove_attribute(SVG_ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => Vec::new(),
+    };
+    let mut colors = match mesh.remove_attribute(SVG_ATTRIBUTE_COLOR) {
+        Some(VertexAttributeValues::Float32x4(colors)) => colors,
+        _ => Vec::new(),
+    };
+    let others: Vec<_> = mesh
+        .attributes()
+        .map(|(attribute, _)| attribute.id)
+        .collect();
+    for id in others {
+        mesh.remove_attribute(id);
+    }
+
+    positions.clear();
+    colors.clear();
+    positions.reserve(buffers.vertices.len());
+    colors.reserve(buffers.vertices.len());
+    for vert in buffers.vertices.drain(..) {
+        positions.alloc().init(vert.position);
+        colors.alloc().init(vert.color);
+    }
+
+    mesh.insert_attribute(SVG_ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(SVG_ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(core::mem::take(&mut buffers.indices)));
+}
+
+// The layout of SVG meshes. The attributes, their ids, formats and shader locations only
+// change in breaking releases, so custom shaders can rely on them.
+
+/// Position of a vertex, `Float32x3`. Present in every SVG mesh.
+pub const SVG_ATTRIBUTE_POSITION: MeshVertexAttribute = Mesh::ATTRIBUTE_POSITION;
+
+/// RGBA color of a vertex, `Float32x4`. Present in every SVG mesh.
+pub const SVG_ATTRIBUTE_COLOR: MeshVertexAttribute = Mesh::ATTRIBUTE_COLOR;
+
+/// Barycentric coordinates of a vertex within its triangle, `Float32x3`. Only present in meshes
+/// tessellated with [`TessellationOptions::barycentric`](crate::prelude::TessellationOptions::barycentric).
+pub const SVG_ATTRIBUTE_BARYCENTRIC: MeshVertexAttribute = MeshVertexAttribute::new(
+    "Vertex_Barycentric",
+    0x5356_4742_6172_7963,
+    VertexFormat::Float32x3,
+);
+
+/// Shader location of [`SVG_ATTRIBUTE_POSITION`] in the 2D and 3D pipelines.
+pub const SVG_LOCATION_POSITION: u32 = 0;
+
+/// Shader location of [`SVG_ATTRIBUTE_COLOR`] in the 2D pipeline, as expected by Bevy's 2D mesh
+/// vertex shader.
+pub const SVG_2D_LOCATION_COLOR: u32 = 4;
+
+/// Shader location of [`SVG_ATTRIBUTE_COLOR`] in the 3D pipeline, as expected by Bevy's PBR mesh
+/// vertex shader.
+pub const SVG_3D_LOCATION_COLOR: u32 = 5;
+
+/// Shader location of [`SVG_ATTRIBUTE_BARYCENTRIC`], which is unused by Bevy's mesh vertex
+/// shaders. The built-in pipelines don't read it, custom materials have to add it to their
+/// vertex layout.
+pub const SVG_LOCATION_BARYCENTRIC: u32 = 8;
+
+/// Converts the buffers into a non-indexed mesh where every triangle has its own three
+/// vertices with [`SVG_ATTRIBUTE_BARYCENTRIC`] coordinates.
+///
+/// `vertex_ranges` are the per-path vertex ranges of the indexed buffers, the matching ranges
+/// of the new mesh are returned.
+#[allow(clippy::cast_possible_truncation)]
+pub fn into_barycentric_mesh(
+    buffers: VertexBuffers,
+    vertex_ranges: &[Range<u32>],
+) -> (Mesh, Vec<Range<u32>>) {
+    // The indices of every path form one contiguous block, in the order of the paths.
+    let mut new_ranges = Vec::with_capacity(vertex_ranges.len());
+    let mut index = 0;
+    for range in vertex_ranges {
+        let start = index;
+        while index < buffers.indices.len() && range.contains(&buffers.indices[index]) {
+            index += 1;
+        }
+        new_ranges.push(start as u32..index as u32);
+    }
+
+    let mut positions = Vec::with_capacity(buffers.indices.len());
+    let mut colors = Vec::with_capacity(buffers.indices.len());
+    let mut barycentrics = Vec::with_capacity(buffers.indices.len());
+    for (corner, idx) in buffers.indices.iter().enumerate() {
+        let vert = buffers.vertices[*idx as usize];
+        positions.alloc().init(vert.position);
+        colors.alloc().init(vert.color);
+        let mut barycentric = [0.0; 3];
+        barycentric[corner % 3] = 1.0;
+        barycentrics.alloc().init(barycentric);
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(SVG_ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(SVG_ATTRIBUTE_COLOR, colors);
+    mesh.insert_attribute(SVG_ATTRIBUTE_BARYCENTRIC, barycentrics);
+
+    (mesh, new_ranges)
+}
+
 /// Zero-sized type used to implement various vertex construction traits from Lyon.
 pub struct VertexConstructor {
     pub(crate) color: Color,
     pub(crate) transform: Transform,
+    pub(crate) color_space: VertexColorSpace,
 }
 
 /// Enables the construction of a [`Vertex`] when using a `FillTessellator`.
@@ -55,7 +185,7 @@ impl FillVertexConstructor<Vertex> for VertexConstructor {
 
         Vertex {
             position: [pos.x, pos.y, pos.z],
-            color: self.color.to_linear().to_f32_array(),
+            color: self.color_space.components(self.color),
         }
     }
 }
@@ -68,17 +198,43 @@ impl StrokeVertexConstructor<Vertex> for VertexConstructor {
 
         Vertex {
             position: [pos.x, pos.y, pos.z],
-            color: self.color.to_srgba().to_f32_array(),
+            color: self.color_space.components(self.color),
+        }
+    }
+}
+
+/// Constructs the vertices of an anti-aliasing fringe around a fill, which fades from the fill
+/// color to transparent towards `outer_side`.
+pub struct FringeVertexConstructor {
+    pub(crate) color: Color,
+    pub(crate) transform: Transform,
+    pub(crate) outer_side: Side,
+    pub(crate) color_space: VertexColorSpace,
+}
+
+impl StrokeVertexConstructor<Vertex> for FringeVertexConstructor {
+    fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
+        let color = if vertex.side() == self.outer_side {
+            self.color.with_alpha(0.0)
+        } else {
+            self.color
+        };
+        let vertex = vertex.position();
+        let pos = self.transform * Vec3::new(vertex.x, vertex.y, 0.0);
+
+        Vertex {
+            position: [pos.x, pos.y, pos.z],
+            color: self.color_space.components(color),
         }
     }
 }
 
 pub trait BufferExt<A> {
     fn extend_one(&mut self, item: A);
-    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T);
 }
 
 impl BufferExt<Self> for VertexBuffers {
+    #[allow(clippy::cast_possible_truncation)]
     fn extend_one(&mut self, item: Self) {
         let offset = self.vertices.len() as u32;
 
@@ -89,19 +245,4 @@ impl BufferExt<Self> for VertexBuffers {
             self.indices.alloc().init(idx + offset);
         }
     }
-
-    fn extend<T: IntoIterator<Item = Self>>(&mut self, iter: T) {
-        let mut offset = self.vertices.len() as u32;
-
-        for buf in iter {
-            let num_verts = buf.vertices.len() as u32;
-            for vert in buf.vertices {
-                self.vertices.alloc().init(vert);
-            }
-            for idx in buf.indices {
-                self.indices.alloc().init(idx + offset);
-            }
-            offset += num_verts;
-        }
-    }
 }
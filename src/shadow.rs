@@ -0,0 +1,186 @@
+use core::hash::{Hash as _, Hasher as _};
+use std::hash::DefaultHasher;
+
+use bevy::{
+    asset::{AssetId, Assets},
+    color::ColorToComponents as _,
+    mesh::Mesh,
+    prelude::*,
+};
+
+use crate::{
+    origin::Origin,
+    regen::{SvgRegenPriority, SvgRegenQueue},
+    render::{tessellation::TessellationOptions, SvgComponent},
+    svg::Svg,
+    variants::SvgMeshVariants,
+};
+
+/// Distance between an SVG and its shadow along the local z-axis.
+const SHADOW_DEPTH: f32 = 0.001;
+
+/// Draws a tinted silhouette of the SVG behind it, e.g. as drop shadow of an icon.
+///
+/// The shadow is a child entity drawing the SVG flattened to `color`, see
+/// [`Svg::flatten_to_color`]. The blur is approximated by a fringe around the fills that fades
+/// out over `blur`, so it softens the edges but doesn't spread the shadow like a true blur.
+/// The silhouette is tessellated in the background by the [`SvgRegenQueue`], so the shadow
+/// appears once it is ready.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgShadow {
+    /// Offset of the shadow in world units. It keeps its direction when the SVG is rotated, as
+    /// if the light came from a fixed side.
+    pub offset: Vec2,
+    /// Width of the soft edge in SVG units.
+    pub blur: f32,
+    /// Color of the shadow.
+    pub color: Color,
+}
+
+impl Default for SvgShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(4.0, -4.0),
+            blur: 4.0,
+            color: Color::BLACK.with_alpha(0.5),
+        }
+    }
+}
+
+impl SvgShadow {
+    /// Key of the silhouette of this shadow among the [`SvgMeshVariants`] of an SVG.
+    fn variant_key(&self, generation: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        "shadow".hash(&mut hasher);
+        generation.hash(&mut hasher);
+        self.color
+            .to_linear()
+            .to_f32_array()
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+        self.blur.to_bits().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Tessellates the silhouette of `svg`.
+    fn tessellate(&self, svg: &Svg) -> Mesh {
+        svg.flatten_to_color(self.color)
+            .tessellate_with_options(&TessellationOptions {
+                edge_aa_width: self.blur.max(0.0),
+                ..svg.tessellation_options
+            })
+    }
+}
+
+/// The child entity that draws the [`SvgShadow`] of an SVG entity.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct SvgShadowEntity {
+    /// The shadow entity.
+    pub entity: Entity,
+    svg: AssetId<Svg>,
+}
+
+/// Spawns and updates the shadow entities of SVGs with a [`SvgShadow`], and despawns them
+/// when it is removed.
+#[allow(clippy::type_complexity)]
+pub fn apply_shadows<C: SvgComponent>(
+    mut commands: Commands,
+    svgs: Res<Assets<Svg>>,
+    mut variants: ResMut<SvgMeshVariants>,
+    mut queue: ResMut<SvgRegenQueue>,
+    query: Query<(
+        Entity,
+        &C,
+        &SvgShadow,
+        &GlobalTransform,
+        Option<&Origin>,
+        Option<&SvgRegenPriority>,
+        Option<&SvgShadowEntity>,
+    )>,
+    mut shadows: Query<(&mut C::MeshComponent, &mut Transform), Without<C>>,
+    mut removed: RemovedComponents<SvgShadow>,
+    orphaned: Query<&SvgShadowEntity, Without<SvgShadow>>,
+) {
+    for entity in removed.read() {
+        if let Ok(shadow_entity) = orphaned.get(entity) {
+            commands.entity(shadow_entity.entity).despawn();
+            commands.entity(entity).remove::<SvgShadowEntity>();
+        }
+    }
+
+    for (entity, svg_component, shadow, global_transform, origin, priority, shadow_entity) in &query
+    {
+        let handle = svg_component.get_handle();
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+
+        // The origin offset is only added to the `GlobalTransform` of the SVG entity, so the
+        // shadow has to add it on its own. The offset is given in world space, so it is mapped
+        // back through the rotation and scale of the SVG entity.
+        let to_world = Mat3::from(global_transform.affine().matrix3);
+        let offset = if to_world.determinant() == 0.0 {
+            shadow.offset.extend(0.0)
+        } else {
+            to_world.inverse() * shadow.offset.extend(0.0)
+        };
+        let translation = origin.map_or(Vec3::ZERO, |origin| origin.compute_translation(svg.size))
+            + offset.with_z(-SHADOW_DEPTH);
+
+        let shadow_id = match shadow_entity {
+            Some(shadow_entity) if shadows.contains(shadow_entity.entity) => {
+                if shadow_entity.svg != handle.id() {
+                    commands
+                        .entity(shadow_entity.entity)
+                        .insert(C::new_material(handle.clone()));
+                    commands.entity(entity).insert(SvgShadowEntity {
+                        svg: handle.id(),
+                        ..*shadow_entity
+                    });
+                }
+                shadow_entity.entity
+            }
+            _ => {
+                let shadow_id = commands
+                    .spawn((
+                        C::MeshComponent::default(),
+                        C::new_material(handle.clone()),
+                        Transform::from_translation(translation),
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands.entity(entity).insert(SvgShadowEntity {
+                    entity: shadow_id,
+                    svg: handle.id(),
+                });
+                shadow_id
+            }
+        };
+
+        let id = handle.id();
+        let key = shadow.variant_key(variants.generation(id));
+        let Some(target) = variants.get(id, key) else {
+            // The shadow entity waits for the silhouette, so the SVG entity can wait for its
+            // own mesh variant at the same time.
+            let priority = priority.map_or(0, |priority| priority.0);
+            if !queue.is_requested(shadow_id, id, key) && !queue.join(shadow_id, id, key, priority)
+            {
+                let (svg, shadow) = (svg.clone(), *shadow);
+                queue.request(shadow_id, id, key, priority, move || {
+                    shadow.tessellate(&svg)
+                });
+            }
+            continue;
+        };
+        queue.cancel(shadow_id);
+
+        if let Ok((mut mesh, mut transform)) = shadows.get_mut(shadow_id) {
+            if *C::get_mesh_mut(mesh.bypass_change_detection()) != target {
+                *C::get_mesh_mut(&mut mesh) = target;
+            }
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::svg::Svg;
+
+/// Keeps the strokes of an SVG at least this many physical pixels wide, so thin lines don't
+/// vanish when the camera zooms out.
+///
+/// The pixel size is measured with the [`SvgCamera`](crate::prelude::SvgCamera), or the first
+/// active orthographic camera, which already accounts for the window scale factor. Affected
+/// SVGs are re-tessellated with widened strokes in the background, strokes that are already
+/// wider than the minimum are untouched.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgMinStrokePixels(pub f32);
+
+/// Number of variants per doubling of the minimum stroke width. The width is quantized to
+/// these steps, so zooming only re-tessellates every now and then.
+const STEPS_PER_OCTAVE: f32 = 4.0;
+
+impl SvgMinStrokePixels {
+    /// Returns the quantized minimum stroke width, see [`step_value`], for an entity drawn at
+    /// `scale` with strokes scaled by `stroke_factor`, or `None` if the strokes of `svg` are
+    /// wide enough.
+    pub(crate) fn step(
+        self,
+        svg: &Svg,
+        world_per_pixel: f32,
+        scale: f32,
+        stroke_factor: f32,
+    ) -> Option<i32> {
+        let thinnest = svg.min_stroke_width()? * stroke_factor;
+        if scale <= 0.0 {
+            return None;
+        }
+        let min_width = self.0 * world_per_pixel / scale;
+        #[allow(clippy::cast_possible_truncation)]
+        (min_width > thinnest).then(|| (min_width.log2() * STEPS_PER_OCTAVE).ceil() as i32)
+    }
+}
+
+/// Scales the strokes of an SVG independently of the entity's scale.
+///
+/// The strokes are drawn this many times as wide as authored, at any scale of the entity. E.g. `SvgStrokeScale(1.0)`
+/// keeps a 2 unit outline 2 units wide while the icon is scaled up tenfold.
+///
+/// Affected SVGs are re-tessellated with adjusted strokes in the background, with the stroke
+/// factor quantized to a few steps per doubling, so smooth scaling only re-tessellates every
+/// now and then.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgStrokeScale(pub f32);
+
+impl Default for SvgStrokeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl SvgStrokeScale {
+    /// Returns the quantized stroke factor, see [`step_value`], for an entity drawn at
+    /// `scale`, or `None` if its strokes are drawn as authored.
+    pub(crate) fn step(self, scale: f32) -> Option<i32> {
+        if scale <= 0.0 || self.0 <= 0.0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let step = ((self.0 / scale).log2() * STEPS_PER_OCTAVE).round() as i32;
+        (step != 0).then_some(step)
+    }
+}
+
+/// The stroke factor or width of a quantized `step`.
+pub(crate) fn step_value(step: i32) -> f32 {
+    #[allow(clippy::cast_precision_loss)]
+    (step as f32 / STEPS_PER_OCTAVE).exp2()
+}
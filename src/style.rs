@@ -0,0 +1,351 @@
+use core::hash::{Hash as _, Hasher as _};
+use std::{hash::DefaultHasher, sync::Arc};
+
+use bevy::{
+    asset::AssetId,
+    color::{Alpha as _, ColorToComponents as _},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use lyon_path::{iterator::PathIterator as _, math::Point, PathEvent};
+use lyon_tessellation::{LineCap, LineJoin};
+
+use crate::{
+    render::SvgComponent,
+    svg::{DrawType, Svg, SvgBlendMode},
+};
+
+/// Overrides the stroke style of an entity's SVG without editing the shared asset.
+///
+/// The entity gets its own variant of the mesh, which is shared with all entities using the
+/// same asset and override. Removing the component returns the entity to the asset's mesh.
+#[derive(Clone, Component, Debug, PartialEq)]
+pub struct SvgStrokeStyleOverride {
+    /// Dash pattern as alternating dash and gap lengths, in the stroke's units. An odd number
+    /// of lengths is repeated once, like `stroke-dasharray`. `None` keeps the strokes solid.
+    pub dash_array: Option<Vec<f32>>,
+    /// Distance into the dash pattern at which each subpath starts.
+    pub dash_offset: f32,
+    /// Cap at the ends of strokes and dashes.
+    pub line_cap: Option<LineCap>,
+    /// Join between stroke segments.
+    pub line_join: Option<LineJoin>,
+    /// Factor applied to all stroke widths.
+    pub width_multiplier: f32,
+    /// Only override the strokes of the elements with these ids or labels, see [`Svg::node`],
+    /// and their children. `None` overrides all strokes.
+    pub ids: Option<Vec<String>>,
+}
+
+impl Default for SvgStrokeStyleOverride {
+    fn default() -> Self {
+        Self {
+            dash_array: None,
+            dash_offset: 0.0,
+            line_cap: None,
+            line_join: None,
+            width_multiplier: 1.0,
+            ids: None,
+        }
+    }
+}
+
+impl SvgStrokeStyleOverride {
+    /// Returns a copy of `svg` with this override applied to its strokes.
+    #[must_use]
+    pub fn apply(&self, svg: &Svg) -> Svg {
+        let mut styled = svg.clone();
+        let selected: Option<Vec<usize>> = self.ids.as_ref().map(|ids| {
+            ids.iter()
+                .filter_map(|id| svg.node(id))
+                .flat_map(|node| node.paths.clone())
+                .collect()
+        });
+
+        for (index, path) in styled.paths.iter_mut().enumerate() {
+            let DrawType::Stroke(opts) = &mut path.draw_type else {
+                continue;
+            };
+            if selected
+                .as_ref()
+                .is_some_and(|selected| !selected.contains(&index))
+            {
+                continue;
+            }
+
+            opts.line_width *= self.width_multiplier;
+            if let Some(cap) = self.line_cap {
+                *opts = opts.with_line_cap(cap);
+            }
+            if let Some(join) = self.line_join {
+                *opts = opts.with_line_join(join);
+            }
+            if let Some(dash_array) = &self.dash_array {
+                let dashed = dash(
+                    &svg.segments[path.segments.clone()],
+                    dash_array,
+                    self.dash_offset,
+                    opts.tolerance,
+                );
+                let start = styled.segments.len();
+                styled.segments.extend(dashed);
+                path.segments = start..styled.segments.len();
+            }
+        }
+        styled
+    }
+
+    pub(crate) fn variant_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        "stroke_style".hash(&mut hasher);
+        self.dash_array
+            .as_ref()
+            .map(|dashes| dashes.iter().map(|dash| dash.to_bits()).collect::<Vec<_>>())
+            .hash(&mut hasher);
+        self.dash_offset.to_bits().hash(&mut hasher);
+        self.line_cap
+            .as_ref()
+            .map(core::mem::discriminant)
+            .hash(&mut hasher);
+        self.line_join
+            .as_ref()
+            .map(core::mem::discriminant)
+            .hash(&mut hasher);
+        self.width_multiplier.to_bits().hash(&mut hasher);
+        self.ids.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Replaces the fill colors of an entity's SVG without editing the shared asset, e.g. for
+/// emblems in the color of each player.
+///
+/// Every fill takes on the override color, keeping its own alpha, while strokes keep their
+/// colors. The entity gets its own variant of the mesh, which is shared with all entities using
+/// the same asset and color. Removing the component returns the entity to the asset's mesh.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgFillOverride(pub Color);
+
+impl SvgFillOverride {
+    /// Returns a copy of `svg` with its fills in the override color.
+    #[must_use]
+    pub fn apply(&self, svg: &Svg) -> Svg {
+        let mut recolored = svg.clone();
+        self.recolor(&mut recolored);
+        recolored
+    }
+
+    /// Sets the fills of `svg` to the override color.
+    pub(crate) fn recolor(&self, svg: &mut Svg) {
+        let color = self.0.to_srgba();
+        for path in &mut svg.paths {
+            if matches!(path.draw_type, DrawType::Fill(_)) {
+                path.color = Color::Srgba(color.with_alpha(color.alpha * path.color.alpha()));
+            }
+        }
+    }
+
+    pub(crate) fn variant_key(self) -> [u32; 4] {
+        self.0.to_linear().to_f32_array().map(f32::to_bits)
+    }
+}
+
+/// Copies of SVG materials with the [`SvgBlendMode`] of an entity, keyed by the asset and the
+/// blend mode.
+#[derive(Default, Resource)]
+pub(crate) struct SvgMaterialOverrides {
+    materials: HashMap<(AssetId<Svg>, SvgBlendMode), Handle<Svg>>,
+}
+
+impl SvgMaterialOverrides {
+    /// Returns the copy of the material of `svg` with `blend_mode`, adding it if it doesn't
+    /// exist yet.
+    fn get_or_insert(
+        &mut self,
+        handle: &Handle<Svg>,
+        blend_mode: SvgBlendMode,
+        svgs: &mut Assets<Svg>,
+    ) -> Option<Handle<Svg>> {
+        let key = (handle.id(), blend_mode);
+        if let Some(material) = self.materials.get(&key) {
+            return Some(material.clone());
+        }
+        let svg = svgs.get(handle)?;
+        // The material is an asset of its own, so it is a full copy: code going through all
+        // SVG assets sees the same document twice, but never an empty one.
+        let material = Svg {
+            blend_mode,
+            dirty: false,
+            ..svg.clone()
+        };
+        let material = svgs.add(material);
+        self.materials.insert(key, material.clone());
+        Some(material)
+    }
+
+    /// Drops the copies that are no longer used by any entity.
+    fn release_unused(&mut self) {
+        self.materials.retain(|_, material| match material {
+            Handle::Strong(handle) => Arc::strong_count(handle) > 1,
+            Handle::Uuid(..) => true,
+        });
+    }
+}
+
+/// Drops the material copies of modified or removed SVGs, so they are copied again from the
+/// new data.
+pub(crate) fn invalidate_material_overrides(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    mut overrides: ResMut<SvgMaterialOverrides>,
+) {
+    for event in svg_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            overrides.materials.retain(|(svg, ..), _| svg != id);
+        }
+    }
+}
+
+/// Gives entities with an [`SvgBlendMode`] the matching copy of their material, and returns
+/// entities whose blend mode was removed to the asset's material.
+///
+/// Runs after the material is reset to the asset's on handle changes, so no frame is drawn
+/// without the override.
+pub(crate) fn apply_material_overrides<C: SvgComponent>(
+    mut removed: RemovedComponents<SvgBlendMode>,
+    mut svgs: ResMut<Assets<Svg>>,
+    mut overrides: ResMut<SvgMaterialOverrides>,
+    mut query: Query<(&C, Option<&SvgBlendMode>, &mut C::MaterialComponent)>,
+) {
+    for entity in removed.read() {
+        if let Ok((svg_component, None, mut material)) = query.get_mut(entity) {
+            set_material::<C>(&mut material, svg_component.get_handle());
+        }
+    }
+    for (svg_component, blend_mode, mut material) in &mut query {
+        let Some(blend_mode) = blend_mode else {
+            continue;
+        };
+        let handle = svg_component.get_handle();
+        if let Some(target) = overrides.get_or_insert(handle, *blend_mode, &mut svgs) {
+            set_material::<C>(&mut material, &target);
+        }
+    }
+    overrides.release_unused();
+}
+
+/// Sets the material handle, only marking the component as changed if it differs.
+fn set_material<C: SvgComponent>(material: &mut Mut<C::MaterialComponent>, target: &Handle<Svg>) {
+    if C::get_material_mut(material.bypass_change_detection()) != target {
+        *C::get_material_mut(material) = target.clone();
+    }
+}
+
+/// Splits the flattened `events` into dashes following `pattern`, starting `offset` into it.
+fn dash(events: &[PathEvent], pattern: &[f32], offset: f32, tolerance: f32) -> Vec<PathEvent> {
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let total: f32 = pattern.iter().sum();
+    if pattern.iter().any(|length| *length < 0.0) || total <= 0.0 {
+        return events.to_vec();
+    }
+
+    // Position in the pattern at the start of each subpath.
+    let mut start_index = 0;
+    let mut start_remaining = pattern[0];
+    let mut skip = offset.rem_euclid(total);
+    // `skip` is less than `total`, so this stops within one repetition of the pattern.
+    #[allow(clippy::while_float)]
+    while skip >= start_remaining {
+        skip -= start_remaining;
+        start_index = (start_index + 1) % pattern.len();
+        start_remaining = pattern[start_index];
+    }
+    start_remaining -= skip;
+
+    let mut dasher = Dasher {
+        pattern: &pattern,
+        index: start_index,
+        remaining: start_remaining,
+        dash_start: Point::zero(),
+        pen: Point::zero(),
+        dashed: Vec::new(),
+    };
+    for event in events.iter().copied().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => {
+                dasher.index = start_index;
+                dasher.remaining = start_remaining;
+                dasher.pen = at;
+                if dasher.drawing() {
+                    dasher.dashed.push(PathEvent::Begin { at });
+                    dasher.dash_start = at;
+                }
+            }
+            PathEvent::Line { from, to } => dasher.line(from, to),
+            PathEvent::End { last, first, close } => {
+                if close {
+                    dasher.line(last, first);
+                }
+                if dasher.drawing() {
+                    dasher.dashed.push(PathEvent::End {
+                        last: dasher.pen,
+                        first: dasher.dash_start,
+                        close: false,
+                    });
+                }
+            }
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
+        }
+    }
+    dasher.dashed
+}
+
+/// State of walking along a flattened path while splitting it into dashes.
+struct Dasher<'a> {
+    pattern: &'a [f32],
+    /// Current entry of the pattern, even entries are dashes and odd ones gaps.
+    index: usize,
+    /// Length left in the current entry.
+    remaining: f32,
+    dash_start: Point,
+    pen: Point,
+    dashed: Vec<PathEvent>,
+}
+
+impl Dasher<'_> {
+    const fn drawing(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    fn line(&mut self, mut from: Point, to: Point) {
+        let mut length = (to - from).length();
+        #[allow(clippy::while_float)]
+        while length > 0.0 {
+            let step = self.remaining.min(length);
+            let point = from.lerp(to, step / length);
+            if self.drawing() {
+                self.dashed.push(PathEvent::Line { from, to: point });
+            }
+            length -= step;
+            self.remaining -= step;
+            from = point;
+            if self.remaining <= 0.0 {
+                if self.drawing() {
+                    self.dashed.push(PathEvent::End {
+                        last: point,
+                        first: self.dash_start,
+                        close: false,
+                    });
+                } else {
+                    self.dashed.push(PathEvent::Begin { at: point });
+                    self.dash_start = point;
+                }
+                self.index = (self.index + 1) % self.pattern.len();
+                self.remaining = self.pattern[self.index];
+            }
+        }
+        self.pen = from;
+    }
+}
@@ -1,28 +1,84 @@
-use std::path::PathBuf;
+use std::{cell::RefCell, ops::Range, path::PathBuf, sync::Arc};
 
 use bevy::{
     asset::{Asset, Handle},
-    color::Color,
-    math::{Mat4, Vec2},
-    mesh::Mesh,
+    color::{Alpha as _, Color, ColorToComponents as _, ColorToPacked as _, LinearRgba},
+    ecs::component::Component,
+    log::warn,
+    math::{Mat4, Rect, UVec2, Vec2, Vec3, Vec3Swizzles as _, Vec4},
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    platform::collections::HashMap,
     reflect::{std_traits::ReflectDefault, Reflect},
-    render::render_resource::AsBindGroup,
+    render::render_resource::{AsBindGroup, PrimitiveTopology},
     transform::components::Transform,
 };
 use lyon_geom::euclid::{default::Transform2D, Point2D, UnknownUnit};
-use lyon_path::PathEvent;
-use lyon_tessellation::{math::Point, FillTessellator, StrokeTessellator};
+use lyon_path::{iterator::PathIterator as _, PathEvent};
+use lyon_tessellation::{
+    math::Point, FillOptions, FillRule, FillTessellator, StrokeTessellator, TessellationError,
+};
+use serde::{Deserialize, Serialize};
 use svgtypes::ViewBox;
 use usvg::{
     tiny_skia_path::{PathSegment, PathSegmentsIter},
     Node,
 };
 
-use crate::{loader::FileSvgError, render::tessellation, Convert};
+use crate::{
+    animation::SvgAnimation,
+    arc,
+    capability::{self, SvgCapability, PLACEHOLDER_COLOR},
+    loader::FileSvgError,
+    raster,
+    render::{
+        tessellation::{self, SvgPathBuffers, TessellationOptions},
+        vertex_buffer::{self, Vertex, VertexBuffers, SVG_ATTRIBUTE_COLOR},
+    },
+    xml, Convert,
+};
+
+/// Fill options paths are tessellated with, unless configured with
+/// [`SvgPlugin::with_options`](crate::prelude::SvgPlugin::with_options).
+pub(crate) const DEFAULT_FILL_OPTIONS: FillOptions = {
+    let mut options = FillOptions::DEFAULT;
+    options.tolerance = 0.001;
+    options
+};
+
+/// Stroke options strokes are tessellated with, unless configured with
+/// [`SvgPlugin::with_options`](crate::prelude::SvgPlugin::with_options).
+pub(crate) const DEFAULT_STROKE_OPTIONS: lyon_tessellation::StrokeOptions = {
+    let mut options = lyon_tessellation::StrokeOptions::DEFAULT;
+    options.tolerance = 0.01;
+    options
+};
+
+/// [`Svg::view_box_scale`] above which the loader warns that curves are flattened coarsely.
+pub const PRECISION_WARNING_SCALE: f32 = 100.0;
+
+/// Deviation of curves in pixels that [`Svg::fit_tolerance`] allows.
+const DISPLAY_TOLERANCE: f32 = 0.25;
+
+/// Millimeters per inch, to convert pixels at a DPI to millimeters.
+const MM_PER_INCH: f32 = 25.4;
+
+/// Buffers [`Svg::tessellate_into`] keeps between calls on the same thread, so refilling a mesh
+/// doesn't allocate once they have grown to the size of the document.
+#[derive(Default)]
+struct Scratch {
+    vertices: Vec<Vertex>,
+    fill: FillTessellator,
+    stroke: StrokeTessellator,
+}
+
+thread_local! {
+    static SCRATCH: RefCell<Scratch> = RefCell::new(Scratch::default());
+}
 
 /// A loaded and deserialized SVG file.
 #[derive(AsBindGroup, Reflect, Debug, Clone, Asset)]
 #[reflect(Default, Debug)]
+#[bind_group_data(SvgBlendMode)]
 pub struct Svg {
     /// The name of the file.
     pub name: String,
@@ -32,142 +88,1395 @@ pub struct Svg {
     /// `ViewBox` of the SVG.
     pub view_box: ViewBox,
     #[reflect(ignore)]
-    /// All paths that make up the SVG.
+    /// All paths that make up the SVG. After changing them, call
+    /// [`mark_dirty`](Self::mark_dirty) to update the [`mesh`](Self::mesh).
     pub paths: Vec<PathDescriptor>,
+    #[reflect(ignore)]
+    /// Segments of all paths, stored in one buffer. After changing them, call
+    /// [`mark_dirty`](Self::mark_dirty) to update the [`mesh`](Self::mesh).
+    pub segments: Vec<PathEvent>,
+    #[reflect(ignore)]
+    /// Elements with an `id`, keyed by it.
+    pub nodes: HashMap<String, SvgNode>,
     /// The fully tessellated paths as [`Mesh`].
     pub mesh: Handle<Mesh>,
+    #[reflect(ignore)]
+    /// SMIL animations of the SVG, only parsed when enabled in the [`SvgSettings`](crate::prelude::SvgSettings).
+    pub animations: Vec<SvgAnimation>,
+    #[reflect(ignore)]
+    /// Options used to tessellate [`mesh`](Self::mesh).
+    pub tessellation_options: TessellationOptions,
+    #[reflect(ignore)]
+    /// How the SVG is blended with what is behind it. Entities with their own [`SvgBlendMode`]
+    /// draw with a copy of the material that has it.
+    pub blend_mode: SvgBlendMode,
+    /// Color the vertex colors are multiplied with when the SVG is drawn, white by default.
+    #[uniform(0)]
+    pub tint: LinearRgba,
+    /// Sizes in pixels of all embedded images.
+    pub(crate) image_sizes: Vec<UVec2>,
+    /// Size in millimeters, if the document is sized in physical units.
+    pub(crate) physical_size: Option<Vec2>,
+    /// Vertices of each path in [`mesh`](Self::mesh), empty until it is tessellated.
+    pub(crate) vertex_ranges: Vec<Range<u32>>,
+    #[reflect(ignore)]
+    /// Errors of the paths that didn't tessellate completely into [`mesh`](Self::mesh).
+    pub(crate) tessellation_errors: Vec<(usize, TessellationError)>,
+    /// Whether [`mesh`](Self::mesh) has to be tessellated again.
+    pub(crate) dirty: bool,
+    /// Paths recolored since [`mesh`](Self::mesh) was tessellated, with the alpha their
+    /// vertices were tessellated with, see [`set_gradient_stops`](Self::set_gradient_stops).
+    pub(crate) recolored: Vec<(usize, f32)>,
+    #[reflect(ignore)]
+    /// Unsupported features the document uses, sorted.
+    pub(crate) missing_capabilities: Vec<SvgCapability>,
 }
 
 impl Default for Svg {
     fn default() -> Self {
         Self {
-            name: Default::default(),
-            size: Default::default(),
+            name: String::new(),
+            size: Vec2::ZERO,
             view_box: ViewBox {
                 x: 0.,
                 y: 0.,
                 w: 0.,
                 h: 0.,
             },
-            paths: Default::default(),
-            mesh: Default::default(),
+            paths: Vec::new(),
+            segments: Vec::new(),
+            nodes: HashMap::default(),
+            mesh: Handle::default(),
+            animations: Vec::new(),
+            tessellation_options: TessellationOptions::default(),
+            blend_mode: SvgBlendMode::default(),
+            tint: LinearRgba::WHITE,
+            image_sizes: Vec::new(),
+            physical_size: None,
+            vertex_ranges: Vec::new(),
+            tessellation_errors: Vec::new(),
+            dirty: false,
+            recolored: Vec::new(),
+            missing_capabilities: Vec::new(),
         }
     }
 }
 
 impl Svg {
     /// Loads an SVG from bytes
+    ///
+    /// Text is rendered with the system fonts and the fonts in `fonts`, which defaults to `./assets`.
+    ///
+    /// # Errors
+    /// If `bytes` isn't a valid SVG document.
     pub fn from_bytes(
         bytes: &[u8],
         path: impl Into<PathBuf>,
         fonts: Option<impl Into<PathBuf>>,
     ) -> Result<Self, FileSvgError> {
-        let mut opts = usvg::Options::default();
-        let fontdb = opts.fontdb_mut();
+        let mut fontdb = usvg::fontdb::Database::new();
         fontdb.load_system_fonts();
-        fontdb.load_fonts_dir(fonts.map_or("./assets".into(), |p| p.into()));
+        fontdb.load_fonts_dir(fonts.map_or_else(|| "./assets".into(), Into::into));
 
         let pathbuf: PathBuf = path.into();
+        Self::from_bytes_with_fontdb(bytes, &pathbuf.display().to_string(), Arc::new(fontdb))
+    }
+
+    /// Loads an SVG from bytes, rendering text with the fonts in `fontdb`.
+    ///
+    /// `path` is only used for error messages, so it can be any asset path, including ones
+    /// from non-filesystem asset sources. The filesystem is never accessed for fonts.
+    ///
+    /// # Errors
+    /// If `bytes` isn't a valid SVG document.
+    pub fn from_bytes_with_fontdb(
+        bytes: &[u8],
+        path: &str,
+        fontdb: Arc<usvg::fontdb::Database>,
+    ) -> Result<Self, FileSvgError> {
+        Self::from_bytes_with_options(bytes, path, fontdb, &SvgConvertOptions::default())
+    }
+
+    /// Like [`from_bytes_with_fontdb`](Self::from_bytes_with_fontdb), converting the parsed
+    /// document with `options`.
+    pub(crate) fn from_bytes_with_options(
+        bytes: &[u8],
+        path: &str,
+        fontdb: Arc<usvg::fontdb::Database>,
+        options: &SvgConvertOptions,
+    ) -> Result<Self, FileSvgError> {
+        let opts = usvg::Options {
+            fontdb,
+            ..Default::default()
+        };
+
         let svg_tree = usvg::Tree::from_data(bytes, &opts).map_err(|err| FileSvgError {
             error: err.into(),
-            path: pathbuf.display().to_string(),
+            path: path.to_owned(),
         })?;
 
-        return Ok(Self::from_tree(svg_tree))
+        let mut svg = Self::from_usvg_tree(&svg_tree, options);
+        svg.read_document(bytes, opts.dpi);
+        Ok(svg)
+    }
+
+    /// Converts a document that was already parsed with `usvg`, e.g. for validation, without
+    /// parsing it again.
+    ///
+    /// The tree has to come from the same `usvg` version as this crate's, which is re-exported
+    /// as [`bevy_svg::usvg`](crate::usvg); trees of other versions are different types. Labels of
+    /// authoring tools, SMIL animations and other data `usvg` drops aren't available.
+    ///
+    /// ```
+    /// use bevy_svg::{prelude::*, usvg};
+    ///
+    /// let text = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+    ///     <rect width="10" height="10" fill="red" stroke="blue"/>
+    ///     <circle cx="5" cy="5" r="2" fill="green"/>
+    /// </svg>"#;
+    /// let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    /// let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    /// // The fill and the stroke of the rect, and the fill of the circle.
+    /// assert_eq!(svg.paths.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn from_usvg_tree(tree: &usvg::Tree, options: &SvgConvertOptions) -> Self {
+        let mut svg = Self::from_tree(tree, options);
+        svg.set_default_options(&options.fill, &options.stroke);
+        if !options.color_map.is_empty() {
+            for path in &mut svg.paths {
+                path.color = options.map_color(path.color);
+            }
+        }
+        if let Some(epsilon) = options.arc_tolerance {
+            svg.refit_arcs(epsilon);
+        }
+        if options.merge_fills {
+            svg.merge_same_color_fills();
+        }
+        if options.flip_y {
+            svg.flip_vertically();
+        }
+        svg
+    }
+
+    /// Adds what `usvg` drops: the labels authoring tools store in vendor attributes to the
+    /// [`nodes`](Self::nodes), and the physical size, from the [`size`](Self::size) in pixels
+    /// at `dpi`.
+    fn read_document(&mut self, bytes: &[u8], dpi: f32) {
+        let Some(text) = xml::document_text(bytes) else {
+            return;
+        };
+        let Ok(document) = xml::parse_document(&text) else {
+            return;
+        };
+        if xml::has_physical_size(&document) {
+            self.physical_size = Some(self.size * MM_PER_INCH / dpi);
+        }
+        for (id, label) in xml::element_labels(&document) {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.label = label.label;
+                node.layer = label.layer;
+            }
+        }
+    }
+
+    /// Size of the SVG in millimeters, if its `width` and `height` are given in physical units
+    /// like `mm`, `cm` or `in`. `None` for documents sized in pixels or without units, and for
+    /// documents converted with [`from_usvg_tree`](Self::from_usvg_tree).
+    ///
+    /// [`size`](Self::size) is the same size in pixels, at the 96 DPI of CSS.
+    #[must_use]
+    pub const fn physical_size(&self) -> Option<Vec2> {
+        self.physical_size
+    }
+
+    /// Length of the [`animations`](Self::animations) in seconds, until the last one ends.
+    /// [`f32::INFINITY`] if one repeats indefinitely, `0.0` without animations.
+    #[must_use]
+    pub fn animation_duration(&self) -> f32 {
+        self.animations
+            .iter()
+            .map(SvgAnimation::end)
+            .fold(0.0, f32::max)
+    }
+
+    /// Marks the SVG to be tessellated again, after its [`paths`](Self::paths) or
+    /// [`segments`](Self::segments) were changed, directly or with methods like
+    /// [`refit_arcs`](Self::refit_arcs). Changing them doesn't update [`mesh`](Self::mesh)
+    /// on its own.
+    ///
+    /// SVGs in `Assets<Svg>` are tessellated again in the next [`PostUpdate`](bevy::app::PostUpdate).
+    /// The mesh is replaced in place, so entities showing it update without a new handle.
+    pub const fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether the SVG is waiting to be tessellated again, see [`mark_dirty`](Self::mark_dirty).
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Document units per view box unit, the scale the view box maps its content with. Above
+    /// [`PRECISION_WARNING_SCALE`], e.g. for a `0 0 1 1` view box drawn 1000 units wide,
+    /// curves are flattened coarsely unless
+    /// [`TessellationOptions::scale_tolerance`] is set.
+    #[must_use]
+    pub fn view_box_scale(&self) -> f32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let view_box = Vec2::new(self.view_box.w as f32, self.view_box.h as f32);
+        if view_box.min_element() > 0.0 {
+            (self.size / view_box).max_element()
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the transform mapping mesh positions back to view box coordinates, e.g. to
+    /// place overlays at the coordinates of an element.
+    ///
+    /// Tessellation maps each path through its `abs_transform`, which includes the scale of
+    /// the view box onto the document [`size`](Self::size), and flips the y-axis. This undoes
+    /// both: the top left of the view box is at the mesh origin and the y-axis points down
+    /// again. A view box with a zero size is treated as the document rect.
+    #[must_use]
+    pub fn mesh_to_viewbox_transform(&self) -> Transform {
+        #[allow(clippy::cast_possible_truncation)]
+        let (min, view_box) = (
+            Vec2::new(self.view_box.x as f32, self.view_box.y as f32),
+            Vec2::new(self.view_box.w as f32, self.view_box.h as f32),
+        );
+        let scale = if view_box.min_element() > 0.0 && self.size.min_element() > 0.0 {
+            view_box / self.size
+        } else {
+            Vec2::ONE
+        };
+        Transform::from_translation(min.extend(0.0)).with_scale(Vec3::new(scale.x, -scale.y, 1.0))
+    }
+
+    /// Returns `true` if the SVG has nothing to draw, e.g. because all its elements are
+    /// `display="none"` or it only contains metadata. Its mesh is empty, but it loaded fine.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Returns the unsupported features the document uses, which are drawn with their
+    /// [fallbacks](SvgCapability::fallback) instead. Empty if the SVG is drawn as intended.
+    #[must_use]
+    pub fn missing_capabilities(&self) -> &[SvgCapability] {
+        &self.missing_capabilities
     }
 
-    /// Creates a bevy mesh from the SVG data.
-    #[must_use] 
+    fn require(&mut self, capability: SvgCapability) {
+        if let Err(index) = self.missing_capabilities.binary_search(&capability) {
+            self.missing_capabilities.insert(index, capability);
+        }
+    }
+
+    /// Returns the element whose `id` is `name`, or else the first one labelled `name` by an
+    /// authoring tool, see [`SvgNode::label`].
+    #[must_use]
+    pub fn node(&self, name: &str) -> Option<&SvgNode> {
+        self.nodes.get(name).or_else(|| {
+            self.nodes
+                .values()
+                .filter(|node| node.label.as_deref() == Some(name))
+                .min_by_key(|node| (node.paths.start, core::cmp::Reverse(node.paths.end)))
+        })
+    }
+
+    /// Returns the layers of the SVG in document order: Inkscape layers, and the labelled
+    /// top-level groups Figma exports its layers as.
+    #[must_use]
+    pub fn layers(&self) -> Vec<SvgLayerInfo> {
+        let mut layers: Vec<SvgLayerInfo> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.layer)
+            .map(|(id, node)| SvgLayerInfo {
+                label: node.label.clone().unwrap_or_else(|| id.clone()),
+                id: id.clone(),
+                path_indices: node.paths.clone(),
+            })
+            .collect();
+        // Outer layers first if nested layers start at the same path.
+        layers.sort_by(|a, b| {
+            a.path_indices
+                .start
+                .cmp(&b.path_indices.start)
+                .then(b.path_indices.end.cmp(&a.path_indices.end))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        layers
+    }
+
+    /// Creates a bevy mesh from the SVG data, using the SVG's [`tessellation_options`](Self::tessellation_options).
+    #[must_use]
     pub fn tessellate(&self) -> Mesh {
-        let buffer = tessellation::generate_buffer(
+        self.tessellate_with_options(&self.tessellation_options)
+    }
+
+    /// Creates a bevy mesh from the SVG data, using the given [`TessellationOptions`].
+    #[must_use]
+    pub fn tessellate_with_options(&self, options: &TessellationOptions) -> Mesh {
+        self.tessellate_with_ranges(options).0
+    }
+
+    /// Creates a bevy mesh from the SVG data, and returns the range of vertices in it that
+    /// belongs to each of the [`paths`](Self::paths).
+    #[must_use]
+    pub fn tessellate_with_ranges(&self, options: &TessellationOptions) -> (Mesh, Vec<Range<u32>>) {
+        self.tessellate_visible(options, |_| true)
+    }
+
+    /// Creates a bevy mesh from only those [`paths`](Self::paths) for which `visible` returns
+    /// `true`, e.g. to hide layers. Like [`Svg::tessellate_with_ranges`], the vertex ranges are
+    /// returned, hidden paths have empty ranges.
+    #[must_use]
+    pub fn tessellate_visible(
+        &self,
+        options: &TessellationOptions,
+        visible: impl Fn(usize) -> bool,
+    ) -> (Mesh, Vec<Range<u32>>) {
+        let (mesh, vertex_ranges, errors) = self.tessellate_checked(options, &visible);
+        for (index, error) in errors {
+            warn!(
+                "Path {index} of SVG {} didn't tessellate completely: {error:?}",
+                self.name
+            );
+        }
+        (mesh, vertex_ranges)
+    }
+
+    /// Tessellates the SVG with its [`tessellation_options`](Self::tessellation_options) into
+    /// `mesh`, replacing its contents, e.g. to update a mesh while a document is edited live.
+    ///
+    /// The vertex and index buffers of `mesh` are cleared and refilled, so their allocations
+    /// are reused instead of allocating a new mesh as [`tessellate`](Self::tessellate) does.
+    /// Attributes an SVG mesh doesn't have are removed. Barycentric meshes and meshes that
+    /// aren't triangle lists are replaced with a newly allocated mesh.
+    pub fn tessellate_into(&self, mesh: &mut Mesh) {
+        let options = &self.tessellation_options;
+        if options.barycentric || mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            *mesh = self.tessellate();
+            return;
+        }
+
+        let indices = match mesh.remove_indices() {
+            Some(Indices::U32(indices)) => indices,
+            _ => Vec::new(),
+        };
+        SCRATCH.with_borrow_mut(|scratch| {
+            let mut buffers = VertexBuffers {
+                vertices: core::mem::take(&mut scratch.vertices),
+                indices,
+            };
+            let (_, errors) = tessellation::generate_buffer_into(
+                self,
+                &mut scratch.fill,
+                &mut scratch.stroke,
+                options,
+                &|_| true,
+                &mut buffers,
+            );
+            for (index, error) in errors {
+                warn!(
+                    "Path {index} of SVG {} didn't tessellate completely: {error:?}",
+                    self.name
+                );
+            }
+            vertex_buffer::write_into_mesh(&mut buffers, mesh);
+            scratch.vertices = buffers.vertices;
+        });
+    }
+
+    /// Tessellates the SVG with its [`tessellation_options`](Self::tessellation_options) like
+    /// [`tessellate_with_ranges`](Self::tessellate_with_ranges), but only the paths that
+    /// changed since `previous`, the paths of an earlier version of the document, e.g. while
+    /// it is edited live. The other paths are copied from `previous`. Paths are matched by
+    /// index first, then by their segments, transform, color and draw type, so paths that
+    /// moved within the document are reused too.
+    ///
+    /// Also returns the paths to pass along with the next version. Without `previous`, or if
+    /// it was tessellated with other options, every path is tessellated. The paths take about
+    /// as much memory as the mesh.
+    #[must_use]
+    pub fn tessellate_incremental(
+        &self,
+        previous: Option<&SvgPathBuffers>,
+    ) -> (Mesh, Vec<Range<u32>>, SvgPathBuffers) {
+        let options = &self.tessellation_options;
+        let (paths, errors) = tessellation::generate_path_buffers(
+            self,
+            &mut FillTessellator::new(),
+            &mut StrokeTessellator::new(),
+            options,
+            previous,
+        );
+        for (index, error) in errors {
+            warn!(
+                "Path {index} of SVG {} didn't tessellate completely: {error:?}",
+                self.name
+            );
+        }
+        let (buffer, vertex_ranges) = paths.join();
+        let (mesh, vertex_ranges) = if options.barycentric {
+            vertex_buffer::into_barycentric_mesh(buffer, &vertex_ranges)
+        } else {
+            (buffer.convert(), vertex_ranges)
+        };
+        (mesh, vertex_ranges, paths)
+    }
+
+    /// Returns the errors of the paths that don't tessellate completely with the SVG's
+    /// [`tessellation_options`](Self::tessellation_options), by index in
+    /// [`paths`](Self::paths). Such paths are missing from the mesh or only partly in it.
+    ///
+    /// The errors are recorded when [`mesh`](Self::mesh) is tessellated, which otherwise only
+    /// logs them as warnings. SVGs that weren't loaded as assets are tessellated to find them.
+    #[must_use]
+    pub fn tessellation_diagnostics(&self) -> Vec<(usize, TessellationError)> {
+        if self.vertex_ranges.len() == self.paths.len() {
+            self.tessellation_errors.clone()
+        } else {
+            self.tessellate_checked(&self.tessellation_options, &|_| true)
+                .2
+        }
+    }
+
+    /// Tessellates the mesh of the asset with its
+    /// [`tessellation_options`](Self::tessellation_options), recording the vertex ranges and
+    /// errors of its paths.
+    pub(crate) fn tessellate_asset(&mut self) -> Mesh {
+        let (mesh, vertex_ranges, errors) =
+            self.tessellate_checked(&self.tessellation_options, &|_| true);
+        for (index, error) in &errors {
+            warn!(
+                "Path {index} of SVG {} didn't tessellate completely: {error:?}",
+                self.name
+            );
+        }
+        self.vertex_ranges = vertex_ranges;
+        self.tessellation_errors = errors;
+        self.recolored.clear();
+        mesh
+    }
+
+    fn tessellate_checked(
+        &self,
+        options: &TessellationOptions,
+        visible: &dyn Fn(usize) -> bool,
+    ) -> (Mesh, Vec<Range<u32>>, Vec<(usize, TessellationError)>) {
+        let (buffer, vertex_ranges, errors) = tessellation::generate_buffer(
             self,
             &mut FillTessellator::new(),
             &mut StrokeTessellator::new(),
+            options,
+            visible,
         );
-        buffer.convert()
+        let (mesh, vertex_ranges) = if options.barycentric {
+            vertex_buffer::into_barycentric_mesh(buffer, &vertex_ranges)
+        } else {
+            (buffer.convert(), vertex_ranges)
+        };
+        (mesh, vertex_ranges, errors)
+    }
+
+    /// Tessellates only the stroke of the descriptor at `index` in [`paths`](Self::paths), e.g. to
+    /// draw outlines with their own material. Returns `None` if the descriptor isn't a stroke.
+    ///
+    /// The mesh is always indexed, even if [`tessellation_options`](Self::tessellation_options)
+    /// request barycentric coordinates.
+    #[must_use]
+    pub fn stroke_geometry(&self, index: usize) -> Option<Mesh> {
+        let path = self.paths.get(index)?;
+        if !matches!(path.draw_type, DrawType::Stroke(_)) {
+            return None;
+        }
+        let options = TessellationOptions {
+            barycentric: false,
+            ..self.tessellation_options
+        };
+        Some(
+            self.tessellate_visible(&options, |visible| visible == index)
+                .0,
+        )
+    }
+
+    /// Returns the width of the thinnest stroke in SVG document units, if there is any stroke.
+    #[must_use]
+    pub fn min_stroke_width(&self) -> Option<f32> {
+        self.paths
+            .iter()
+            .filter_map(|path| match &path.draw_type {
+                DrawType::Stroke(opts) => {
+                    Some(opts.line_width * path.abs_transform.scale.xy().abs().max_element())
+                }
+                DrawType::Fill(_) => None,
+            })
+            .reduce(f32::min)
+    }
+
+    /// Returns the indices of all descriptors that were created from the source path with
+    /// index `source_index`, e.g. its fill and its stroke.
+    #[must_use]
+    pub fn path_group(&self, source_index: usize) -> Vec<usize> {
+        self.paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| path.source_path_index == source_index)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Computes the tight bounds of all paths in SVG document space, i.e. with the y-axis
+    /// pointing down. Control points are included, so curves may be slightly overestimated.
+    #[must_use]
+    pub fn content_bounds(&self) -> Option<Rect> {
+        self.paths
+            .iter()
+            .flat_map(|path| {
+                self.path_segments(path).iter().flat_map(move |event| {
+                    let points = match *event {
+                        PathEvent::Begin { at } => vec![at],
+                        PathEvent::Line { from, to }
+                        | PathEvent::End {
+                            last: from,
+                            first: to,
+                            ..
+                        } => {
+                            vec![from, to]
+                        }
+                        PathEvent::Quadratic { from, ctrl, to } => vec![from, ctrl, to],
+                        PathEvent::Cubic {
+                            from,
+                            ctrl1,
+                            ctrl2,
+                            to,
+                        } => vec![from, ctrl1, ctrl2, to],
+                    };
+                    points.into_iter().map(move |point| {
+                        path.abs_transform
+                            .transform_point(Vec3::new(point.x, point.y, 0.0))
+                            .xy()
+                    })
+                })
+            })
+            .fold(None, |bounds: Option<Rect>, point| {
+                Some(bounds.map_or_else(
+                    || Rect::from_corners(point, point),
+                    |bounds| bounds.union_point(point),
+                ))
+            })
+    }
+
+    /// Sets the stops of the gradient that the path at `index` is painted with, e.g. to shift
+    /// the hues of a gradient every frame. Returns `false`, changing nothing, if there is no
+    /// path `index` or it isn't painted with a gradient.
+    ///
+    /// Gradients are drawn in a single color (see [`SvgCapability::Gradient`]), the mean of
+    /// the colors along the gradient, weighted by the distances between the offsets of the
+    /// stops. The colors of `stops` include the opacity of the paint. The path is recolored
+    /// to it, and the vertices of the path in the [`mesh`](Self::mesh) of SVGs in
+    /// `Assets<Svg>` are recolored in the next [`PostUpdate`](bevy::app::PostUpdate) without
+    /// tessellating again. A path that was fully transparent has no coverage of its
+    /// anti-aliasing fringe left to keep, so the SVG is tessellated again instead.
+    pub fn set_gradient_stops(&mut self, index: usize, stops: &[SvgGradientStop]) -> bool {
+        let Some(path) = self.paths.get_mut(index).filter(|path| path.gradient) else {
+            return false;
+        };
+        let previous = path.color.alpha();
+        path.color = SvgGradientStop::mean(stops);
+        if previous <= 0.0 {
+            self.mark_dirty();
+        } else if !self
+            .recolored
+            .iter()
+            .any(|(recolored, _)| *recolored == index)
+        {
+            self.recolored.push((index, previous));
+        }
+        true
+    }
+
+    /// Recolors the vertices of the paths in `mesh`, the [`mesh`](Self::mesh) of this SVG,
+    /// that were recolored with [`set_gradient_stops`](Self::set_gradient_stops). Returns
+    /// `false`, leaving `mesh` as it is, if `mesh` wasn't tessellated from the paths of this
+    /// SVG.
+    pub(crate) fn recolor_mesh(&mut self, mesh: &mut Mesh) -> bool {
+        let recolored = core::mem::take(&mut self.recolored);
+        #[allow(clippy::cast_possible_truncation)]
+        let vertex_count = mesh.count_vertices() as u32;
+        let matches = self.vertex_ranges.len() == self.paths.len()
+            && self.vertex_ranges.last().map_or(0, |range| range.end) == vertex_count;
+        let Some(VertexAttributeValues::Float32x4(colors)) =
+            mesh.attribute_mut(SVG_ATTRIBUTE_COLOR).filter(|_| matches)
+        else {
+            return false;
+        };
+        for (index, previous) in recolored {
+            let [r, g, b, a] = self
+                .tessellation_options
+                .color_space
+                .components(self.paths[index].color);
+            let range = &self.vertex_ranges[index];
+            for vertex in &mut colors[range.start as usize..range.end as usize] {
+                // Keep the coverage of anti-aliasing fringes, which fade out to transparent.
+                *vertex = [r, g, b, a * vertex[3] / previous];
+            }
+        }
+        true
+    }
+
+    /// Counts the paths and the vertices of the SVG, and how many of them outline text.
+    ///
+    /// The vertices are those of [`mesh`](Self::mesh). SVGs that weren't loaded as assets are
+    /// tessellated to count them.
+    #[must_use]
+    pub fn stats(&self) -> SvgStats {
+        let tessellated;
+        let vertex_ranges = if self.vertex_ranges.len() == self.paths.len() {
+            &self.vertex_ranges
+        } else {
+            tessellated = self.tessellate_with_ranges(&self.tessellation_options).1;
+            &tessellated
+        };
+        let mut stats = SvgStats {
+            path_count: self.paths.len(),
+            ..Default::default()
+        };
+        for (path, range) in self.paths.iter().zip(vertex_ranges) {
+            let vertex_count = range.len();
+            stats.vertex_count += vertex_count;
+            if path.text {
+                stats.text_path_count += 1;
+                stats.text_vertex_count += vertex_count;
+            }
+        }
+        stats
+    }
+
+    /// Replaces the fills of text with rectangles of their bounds in the same colors, like the
+    /// "greeked" text of layout previews, and drops the strokes of text. Outlined text takes
+    /// many vertices per glyph, a rectangle four. [`mesh`](Self::mesh) isn't updated, see
+    /// [`mark_dirty`](Self::mark_dirty).
+    pub fn simplify_text(&mut self) {
+        for index in 0..self.paths.len() {
+            if !self.paths[index].text {
+                continue;
+            }
+            let mut bounds = Rect {
+                min: Vec2::INFINITY,
+                max: Vec2::NEG_INFINITY,
+            };
+            for event in self.path_segments(&self.paths[index]) {
+                // Control points bound the curves.
+                let points = match *event {
+                    PathEvent::Begin { at } => [at; 3],
+                    PathEvent::Line { to, .. } | PathEvent::End { last: to, .. } => [to; 3],
+                    PathEvent::Quadratic { ctrl, to, .. } => [ctrl, to, to],
+                    PathEvent::Cubic {
+                        ctrl1, ctrl2, to, ..
+                    } => [ctrl1, ctrl2, to],
+                };
+                for point in points {
+                    bounds = bounds.union_point(Vec2::new(point.x, point.y));
+                }
+            }
+            let path = &self.paths[index];
+            let dropped = path.segments.start..path.segments.start;
+            let segments = if matches!(path.draw_type, DrawType::Fill(_)) && !bounds.is_empty() {
+                self.push_rect(bounds)
+            } else {
+                dropped
+            };
+            self.paths[index].segments = segments;
+        }
+        self.vertex_ranges.clear();
+    }
+
+    /// Mirrors the content vertically within the document, so its top and bottom swap.
+    ///
+    /// SVG documents have a y-axis pointing down and are flipped into Bevy's y-up space when
+    /// tessellated, so the top left of the document lands at the mesh origin. This is for
+    /// documents whose coordinates were written with the y-axis pointing up, e.g. plots
+    /// exported from tools with mathematical coordinates, which appear upside-down otherwise.
+    ///
+    /// Content that is mirrored by its own transforms, like a `scale(1, -1)` around y-up
+    /// coordinates, is already drawn the right way up and mustn't be flipped again.
+    pub fn flip_vertically(&mut self) {
+        let flip = Mat4::from_translation(Vec3::new(0.0, self.size.y, 0.0))
+            * Mat4::from_scale(Vec3::new(1.0, -1.0, 1.0));
+        let flipped = |transform: &Transform| Transform::from_matrix(flip * transform.to_matrix());
+        for path in &mut self.paths {
+            path.abs_transform = flipped(&path.abs_transform);
+        }
+        for node in self.nodes.values_mut() {
+            node.abs_transform = flipped(&node.abs_transform);
+        }
+    }
+
+    /// Replaces the tessellation options of all paths with `fill` and `stroke`. The fill rule,
+    /// stroke width, caps, joins and miter limit are kept, because they come from the SVG.
+    pub fn set_default_options(
+        &mut self,
+        fill: &FillOptions,
+        stroke: &lyon_tessellation::StrokeOptions,
+    ) {
+        self.vertex_ranges.clear();
+        for path in &mut self.paths {
+            match &mut path.draw_type {
+                DrawType::Fill(options) => *options = fill.with_fill_rule(options.fill_rule),
+                DrawType::Stroke(options) => {
+                    *options = stroke
+                        .with_line_width(options.line_width)
+                        .with_start_cap(options.start_cap)
+                        .with_end_cap(options.end_cap)
+                        .with_line_join(options.line_join)
+                        .with_miter_limit(options.miter_limit);
+                }
+            }
+        }
     }
 
-    fn parse_tree(node: &Node, descriptors: &mut Vec<PathDescriptor>) {
+    /// Sets the tolerances of all paths so curves deviate by at most a quarter pixel when the
+    /// SVG is drawn `display_size` pixels wide along its larger side. Small icons get coarser
+    /// tessellations with fewer vertices, large ones finer ones.
+    pub fn fit_tolerance(&mut self, display_size: f32) {
+        let extent = self.size.max_element();
+        if display_size <= 0.0 || extent <= 0.0 {
+            return;
+        }
+        let tolerance = DISPLAY_TOLERANCE * extent / display_size;
+        self.vertex_ranges.clear();
+        for path in &mut self.paths {
+            // Tolerances are in path units, unless they are scaled during tessellation.
+            let scale = path.abs_transform.scale.xy().abs().max_element();
+            let path_tolerance = if self.tessellation_options.scale_tolerance || scale <= 0.0 {
+                tolerance
+            } else {
+                tolerance / scale
+            };
+            match &mut path.draw_type {
+                DrawType::Fill(options) => options.tolerance = path_tolerance,
+                DrawType::Stroke(options) => options.tolerance = path_tolerance,
+            }
+        }
+    }
+
+    /// Replaces cubics that approximate circular arcs with finer approximations, so that they
+    /// deviate from the true circle by less than `epsilon` SVG units.
+    pub fn refit_arcs(&mut self, epsilon: f32) {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut ranges = Vec::with_capacity(self.paths.len());
+        for path in &self.paths {
+            // Fill and stroke of a path share their segments, so keep them shared.
+            if let Some((previous, range)) = ranges
+                .iter()
+                .find(|(previous, _): &&(Range<usize>, Range<usize>)| *previous == path.segments)
+            {
+                ranges.push((previous.clone(), range.clone()));
+                continue;
+            }
+            let start = segments.len();
+            for event in &self.segments[path.segments.clone()] {
+                match *event {
+                    PathEvent::Cubic {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    } => match arc::refit_arc(from, ctrl1, ctrl2, to, epsilon) {
+                        Some(refitted) => segments.extend(refitted),
+                        None => segments.push(*event),
+                    },
+                    _ => segments.push(*event),
+                }
+            }
+            ranges.push((path.segments.clone(), start..segments.len()));
+        }
+        for (path, (_, range)) in self.paths.iter_mut().zip(ranges) {
+            path.segments = range;
+        }
+        self.segments = segments;
+        self.vertex_ranges.clear();
+    }
+
+    /// Returns the segments of `path`, which has to be one of the [`paths`](Self::paths) of this SVG.
+    #[must_use]
+    pub fn path_segments(&self, path: &PathDescriptor) -> &[PathEvent] {
+        &self.segments[path.segments.clone()]
+    }
+
+    /// Returns the segments of the descriptor at `index` in [`paths`](Self::paths), mapped
+    /// through its `abs_transform` into SVG document space, i.e. with the y-axis pointing down
+    /// like [`content_bounds`](Self::content_bounds). Yields nothing for an invalid `index`.
+    pub fn transformed_segments(&self, index: usize) -> impl Iterator<Item = PathEvent> + '_ {
+        let path = self.paths.get(index);
+        let transform = path.map_or_else(Transform2D::identity, |path| {
+            let matrix = path.abs_transform.to_matrix();
+            Transform2D::new(
+                matrix.x_axis.x,
+                matrix.x_axis.y,
+                matrix.y_axis.x,
+                matrix.y_axis.y,
+                matrix.w_axis.x,
+                matrix.w_axis.y,
+            )
+        });
+        path.map(|path| self.path_segments(path))
+            .unwrap_or_default()
+            .iter()
+            .map(move |event| event.transformed(&transform))
+    }
+
+    /// Flattens the descriptor at `index` in [`paths`](Self::paths) into polylines in SVG
+    /// document space, see [`transformed_segments`](Self::transformed_segments), deviating at
+    /// most `tolerance` from the curves. Closed subpaths end with their first point.
+    #[must_use]
+    pub fn flattened_outline(&self, index: usize, tolerance: f32) -> Vec<Vec<Point>> {
+        let mut polylines: Vec<Vec<Point>> = Vec::new();
+        for event in self.transformed_segments(index).flattened(tolerance) {
+            match event {
+                PathEvent::Begin { at } => polylines.push(vec![at]),
+                PathEvent::Line { to, .. } => {
+                    if let Some(polyline) = polylines.last_mut() {
+                        polyline.push(to);
+                    }
+                }
+                PathEvent::End {
+                    first, close: true, ..
+                } => {
+                    if let Some(polyline) = polylines.last_mut() {
+                        if polyline.last() != Some(&first) {
+                            polyline.push(first);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        polylines
+    }
+
+    /// Like [`flattened_outline`](Self::flattened_outline), with each polyline simplified by
+    /// the Douglas–Peucker algorithm, e.g. for cheaper colliders or hit tests.
+    ///
+    /// The simplified polylines keep the end points and deviate at most `epsilon` from the
+    /// flattened ones, larger values drop more points. The curves are flattened with a tenth
+    /// of `epsilon` first.
+    #[must_use]
+    pub fn simplified_outline(&self, index: usize, epsilon: f32) -> Vec<Vec<Vec2>> {
+        self.flattened_outline(index, epsilon * 0.1)
+            .into_iter()
+            .map(|polyline| {
+                let points: Vec<Vec2> = polyline
+                    .into_iter()
+                    .map(|point| Vec2::new(point.x, point.y))
+                    .collect();
+                douglas_peucker(&points, epsilon)
+            })
+            .collect()
+    }
+
+    /// Exports the outlines of all [`paths`](Self::paths) as JSON, for tools that consume plain
+    /// geometry. Each path becomes an object with its index, whether it is a `"fill"` or a
+    /// `"stroke"`, its sRGBA color and its [flattened](Self::flattened_outline) polylines,
+    /// flattened with the tolerance the path is tessellated with:
+    ///
+    /// ```json
+    /// [{"index":0,"kind":"fill","color":[1,0,0,1],"polylines":[[[0,0],[10,0],[10,10],[0,0]]]}]
+    /// ```
+    #[must_use]
+    pub fn outlines_to_json(&self) -> String {
+        use core::fmt::Write as _;
+
+        // JSON has no representation of non-finite numbers.
+        let number = |value: f32| {
+            if value.is_finite() {
+                value.to_string()
+            } else {
+                "null".to_owned()
+            }
+        };
+
+        let mut json = String::from("[");
+        for (index, path) in self.paths.iter().enumerate() {
+            let (kind, tolerance) = match &path.draw_type {
+                DrawType::Fill(options) => ("fill", options.tolerance),
+                DrawType::Stroke(options) => ("stroke", options.tolerance),
+            };
+            let [r, g, b, a] = path.color.to_srgba().to_f32_array();
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                r#"{{"index":{index},"kind":"{kind}","color":[{},{},{},{}],"polylines":["#,
+                number(r),
+                number(g),
+                number(b),
+                number(a),
+            );
+            for (i, polyline) in self.flattened_outline(index, tolerance).iter().enumerate() {
+                json.push_str(if i > 0 { ",[" } else { "[" });
+                for (j, point) in polyline.iter().enumerate() {
+                    let separator = if j > 0 { "," } else { "" };
+                    let _ = write!(json, "{separator}[{},{}]", number(point.x), number(point.y));
+                }
+                json.push(']');
+            }
+            json.push_str("]}");
+        }
+        json.push(']');
+        json
+    }
+
+    /// Adds the paths of `node` and its children. `opacity` is the product of the opacities of
+    /// the groups containing `node`, including that of the root `<svg>` element, and fades the
+    /// colors of the paths. `depth` is the number of elements containing `node`.
+    fn parse_tree(&mut self, node: &Node, options: &SvgConvertOptions, opacity: f32, depth: usize) {
+        let first_path = self.paths.len();
         match node {
             Node::Group(group) => {
-                for node in group.children() {
-                    Self::parse_tree(node, descriptors);
+                if group.clip_path().is_some() {
+                    self.require(SvgCapability::ClipPath);
+                }
+                if !group.filters().is_empty() {
+                    self.require(SvgCapability::Filter);
+                }
+                if group.mask().is_some() {
+                    self.require(SvgCapability::Mask);
+                } else {
+                    // Children of translucent groups are faded one by one, so where they
+                    // overlap they are blended twice instead of once.
+                    let opacity = opacity * group.opacity().get();
+                    for node in group.children() {
+                        self.parse_tree(node, options, opacity, depth + 1);
+                    }
+                }
+            }
+            Node::Image(image) => {
+                // The size in user units is how large it's drawn, the texture has the size of
+                // the image data.
+                let size = Vec2::new(image.size().width(), image.size().height());
+                if let Some((width, height)) = raster::pixel_size(image.kind()) {
+                    self.image_sizes.push(UVec2::new(width, height));
+                }
+                self.require(SvgCapability::Image);
+
+                let segments = self.push_rect(Rect::from_corners(Vec2::ZERO, size));
+                self.paths.push(PathDescriptor {
+                    segments,
+                    abs_transform: node.abs_transform().convert(),
+                    color: PLACEHOLDER_COLOR.with_alpha(PLACEHOLDER_COLOR.alpha() * opacity),
+                    draw_type: DrawType::Fill(DEFAULT_FILL_OPTIONS),
+                    source_path_index: self
+                        .paths
+                        .last()
+                        .map_or(0, |last| last.source_path_index + 1),
+                    text: false,
+                    gradient: false,
+                });
+            }
+            Node::Text(text) => {
+                for node in text.flattened().children() {
+                    self.parse_tree(node, options, opacity, depth + 1);
+                }
+                for path in &mut self.paths[first_path..] {
+                    path.text = true;
                 }
             }
-            Node::Path(path) => {
-                let t = node.abs_transform();
-                let abs_t = Transform::from_matrix(Mat4::from_cols(
-                    [t.sx, t.ky, 0.0, 0.0].into(),
-                    [t.kx, t.sy, 0.0, 0.0].into(),
-                    [0.0, 0.0, 1.0, 0.0].into(),
-                    [t.tx, t.ty, 0.0, 1.0].into(),
-                ));
+            Node::Path(path) if options.include_hidden || path.is_visible() => {
+                let transform = node.abs_transform();
+                // A `Transform` can't hold a skew, so skewed paths are moved into document
+                // space instead, with their strokes scaled by the mean scale of the transform.
+                let skewed = skews(transform);
+                let abs_t = if skewed {
+                    Transform::IDENTITY
+                } else {
+                    transform.convert()
+                };
+                let source_path_index = self
+                    .paths
+                    .last()
+                    .map_or(0, |last| last.source_path_index + 1);
+
+                // Fill and stroke of a path share the same segments.
+                let start = self.segments.len();
+                if skewed {
+                    let matrix = Transform2D::new(
+                        transform.sx,
+                        transform.ky,
+                        transform.kx,
+                        transform.sy,
+                        transform.tx,
+                        transform.ty,
+                    );
+                    let events: PathConvIter<'_> = path.convert();
+                    self.segments
+                        .extend(events.map(|event| event.transformed(&matrix)));
+                } else {
+                    self.segments.extend(path.convert());
+                }
+                let segments = start..self.segments.len();
 
                 if let Some(fill) = &path.fill() {
-                    let color = match fill.paint() {
-                        usvg::Paint::Color(c) => {
-                            Color::srgba_u8(c.red, c.green, c.blue, fill.opacity().to_u8())
-                        }
-                        _ => Color::default(),
+                    if let Some(capability) = SvgCapability::of_paint(fill.paint()) {
+                        self.require(capability);
+                    }
+                    let color = capability::paint_color(fill.paint(), fill.opacity());
+                    let color = color.with_alpha(color.alpha() * opacity);
+
+                    let fill_rule = match fill.rule() {
+                        usvg::FillRule::NonZero => FillRule::NonZero,
+                        usvg::FillRule::EvenOdd => FillRule::EvenOdd,
                     };
 
-                    descriptors.push(PathDescriptor {
-                        segments: path.convert().collect(),
+                    self.paths.push(PathDescriptor {
+                        segments: segments.clone(),
                         abs_transform: abs_t,
                         color,
-                        draw_type: DrawType::Fill,
+                        draw_type: DrawType::Fill(DEFAULT_FILL_OPTIONS.with_fill_rule(fill_rule)),
+                        source_path_index,
+                        text: false,
+                        gradient: SvgCapability::of_paint(fill.paint())
+                            == Some(SvgCapability::Gradient),
                     });
                 }
 
                 if let Some(stroke) = &path.stroke() {
-                    let (color, draw_type) = stroke.convert();
+                    if let Some(capability) = SvgCapability::of_paint(stroke.paint()) {
+                        self.require(capability);
+                    }
+                    let (color, mut draw_type) = stroke.convert();
+                    let color = color.with_alpha(color.alpha() * opacity);
+                    if let (true, DrawType::Stroke(opts)) = (skewed, &mut draw_type) {
+                        let determinant = transform.sx * transform.sy - transform.kx * transform.ky;
+                        opts.line_width *= determinant.abs().sqrt();
+                    }
 
-                    descriptors.push(PathDescriptor {
-                        segments: path.convert().collect(),
+                    self.paths.push(PathDescriptor {
+                        segments,
                         abs_transform: abs_t,
                         color,
                         draw_type,
+                        source_path_index,
+                        text: false,
+                        gradient: SvgCapability::of_paint(stroke.paint())
+                            == Some(SvgCapability::Gradient),
                     });
                 }
             }
-            _ => {}
+            Node::Path(_) => {}
+        }
+
+        if !node.id().is_empty() {
+            let (transform, opacity) = match node {
+                Node::Group(group) => (group.transform().convert(), group.opacity().get()),
+                _ => (Transform::IDENTITY, 1.0),
+            };
+            self.nodes.insert(
+                node.id().to_owned(),
+                SvgNode {
+                    abs_transform: node.abs_transform().convert(),
+                    transform,
+                    opacity,
+                    depth,
+                    paths: first_path..self.paths.len(),
+                    label: None,
+                    layer: false,
+                },
+            );
         }
     }
 
-    pub(crate) fn from_tree(tree: usvg::Tree) -> Self {
-        let transform = tree.root().transform();
-        let size = tree.size();
-        let mut descriptors = vec![];
-        for node in tree.root().children() {
-            Self::parse_tree(node, &mut descriptors);
+    /// Appends the outline of `rect` to the segments and returns their range.
+    fn push_rect(&mut self, rect: Rect) -> Range<usize> {
+        let start = self.segments.len();
+        let corners = [
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y),
+            rect.max,
+            Vec2::new(rect.min.x, rect.max.y),
+        ]
+        .map(|corner| Point::new(corner.x, corner.y));
+        self.segments.push(PathEvent::Begin { at: corners[0] });
+        for (from, to) in corners.iter().zip(corners.iter().skip(1)) {
+            self.segments.push(PathEvent::Line {
+                from: *from,
+                to: *to,
+            });
         }
+        self.segments.push(PathEvent::End {
+            last: corners[3],
+            first: corners[0],
+            close: true,
+        });
+        start..self.segments.len()
+    }
 
-        Self {
-            name: Default::default(),
+    fn from_tree(tree: &usvg::Tree, options: &SvgConvertOptions) -> Self {
+        let size = tree.size();
+
+        // `usvg` maps the view box onto the document size with an unnamed group around the
+        // whole content, which it only adds if that mapping isn't the identity. The view box is
+        // the document rect mapped back. Normalizing the corners keeps it well-formed if the
+        // mapping flips an axis; the flip itself stays in the path transforms.
+        let transform = match tree.root().children() {
+            [Node::Group(group)] if group.id().is_empty() => group.transform(),
+            _ => tree.root().transform(),
+        };
+        let view_box = transform.invert().map_or_else(
+            || Rect::from_corners(Vec2::ZERO, Vec2::new(size.width(), size.height())),
+            |inverse| {
+                let mut corners = [
+                    usvg::tiny_skia_path::Point::from_xy(0.0, 0.0),
+                    usvg::tiny_skia_path::Point::from_xy(size.width(), size.height()),
+                ];
+                inverse.map_points(&mut corners);
+                Rect::from_corners(
+                    Vec2::new(corners[0].x, corners[0].y),
+                    Vec2::new(corners[1].x, corners[1].y),
+                )
+            },
+        );
+        let mut svg = Self {
             size: Vec2::new(size.width(), size.height()),
             view_box: ViewBox {
-                x: f64::from(-transform.tx),
-                y: f64::from(-transform.ty),
-                w: f64::from(size.width() * transform.sx),
-                h: f64::from(size.height() * transform.sy),
+                x: f64::from(view_box.min.x),
+                y: f64::from(view_box.min.y),
+                w: f64::from(view_box.width()),
+                h: f64::from(view_box.height()),
             },
-            paths: descriptors,
-            mesh: Default::default(),
+            ..Default::default()
+        };
+        for node in tree.root().children() {
+            svg.parse_tree(node, options, tree.root().opacity().get(), 0);
+        }
+        svg
+    }
+}
+
+/// Simplifies `points` with the Douglas–Peucker algorithm, keeping the points that deviate
+/// more than `epsilon` from the simplified polyline.
+fn douglas_peucker(points: &[Vec2], epsilon: f32) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let (a, b) = (points[start], points[end]);
+        let distance = |point: Vec2| {
+            let segment = b - a;
+            let length_squared = segment.length_squared();
+            if length_squared == 0.0 {
+                return point.distance(a);
+            }
+            let t = ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0);
+            point.distance(a + segment * t)
+        };
+        let farthest = (start + 1..end)
+            .map(|index| (index, distance(points[index])))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((index, distance)) = farthest {
+            if distance > epsilon {
+                keep[index] = true;
+                ranges.push((start, index));
+                ranges.push((index, end));
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+/// How [`Svg::from_usvg_tree`] converts a `usvg` tree. The asset loader fills these from the
+/// [`SvgSettings`](crate::prelude::SvgSettings) and the
+/// [`SvgPlugin`](crate::prelude::SvgPlugin).
+#[derive(Clone, Debug)]
+pub struct SvgConvertOptions {
+    /// Also draw paths with `visibility="hidden"`. Elements with `display="none"` are always
+    /// removed by `usvg`.
+    pub include_hidden: bool,
+    /// Colors to replace, compared without their alpha. The alpha of the replacement is
+    /// multiplied with the alpha of the original paint.
+    pub color_map: Vec<(Color, Color)>,
+    /// Refit circular arcs to this accuracy, see [`Svg::refit_arcs`].
+    pub arc_tolerance: Option<f32>,
+    /// Merge overlapping fills of the same color, see [`Svg::merge_same_color_fills`].
+    pub merge_fills: bool,
+    /// Flip documents authored with the y-axis pointing up, see [`Svg::flip_vertically`].
+    pub flip_y: bool,
+    /// Tessellation options of the fills, see [`Svg::set_default_options`].
+    pub fill: FillOptions,
+    /// Tessellation options of the strokes, see [`Svg::set_default_options`].
+    pub stroke: lyon_tessellation::StrokeOptions,
+}
+
+impl Default for SvgConvertOptions {
+    fn default() -> Self {
+        Self {
+            include_hidden: false,
+            color_map: Vec::new(),
+            arc_tolerance: None,
+            merge_fills: false,
+            flip_y: false,
+            fill: DEFAULT_FILL_OPTIONS,
+            stroke: DEFAULT_STROKE_OPTIONS,
+        }
+    }
+}
+
+impl SvgConvertOptions {
+    fn map_color(&self, color: Color) -> Color {
+        let srgba = color.to_srgba();
+        let rgb = |color: bevy::color::Srgba| color.to_u8_array_no_alpha();
+        self.color_map
+            .iter()
+            .find(|(from, _)| rgb(from.to_srgba()) == rgb(srgba))
+            .map_or(color, |(_, to)| {
+                let to = to.to_srgba();
+                Color::Srgba(to.with_alpha(to.alpha * srgba.alpha))
+            })
+    }
+}
+
+/// How an [`Svg`] is blended with what is behind it, see [`Svg::blend_mode`].
+///
+/// As component of an SVG entity, it overrides the blend mode of the asset for that entity,
+/// e.g. for a stack of translucent cards. The entity draws with a copy of the asset that has
+/// the blend mode, which is added to `Assets<Svg>` and shared by all entities with the same
+/// asset and blend mode. Removing it returns the entity to the asset's.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SvgBlendMode {
+    /// Alpha blending of 2D SVGs, which are drawn back to front. Overlapping translucent SVGs
+    /// only blend correctly if they sort, which fails for SVGs at the same depth. 3D SVGs are
+    /// drawn opaque.
+    #[default]
+    Blend,
+    /// Alpha to coverage: the alpha of a fragment decides how many MSAA samples it covers,
+    /// and the SVG is drawn like an opaque mesh, with depth testing instead of sorting. So
+    /// overlapping translucent SVGs look the same in any draw order, an approximation of
+    /// order-independent transparency.
+    ///
+    /// Requires MSAA, without it translucent paths are drawn opaque. Alpha is quantized to the
+    /// sample count, e.g. five levels with 4x MSAA, which shows as dithering in gradual
+    /// fades. Translucent paths with the same alpha cover the same samples, so where they
+    /// overlap the upper one hides the lower one instead of blending with it.
+    AlphaToCoverage,
+}
+
+impl From<&Svg> for SvgBlendMode {
+    fn from(svg: &Svg) -> Self {
+        svg.blend_mode
+    }
+}
+
+/// Counts of the paths and vertices of an [`Svg`], see [`Svg::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SvgStats {
+    /// Number of paths, fills and strokes are counted separately.
+    pub path_count: usize,
+    /// Number of paths outlining text.
+    pub text_path_count: usize,
+    /// Number of vertices of the mesh.
+    pub vertex_count: usize,
+    /// Number of vertices of the paths outlining text.
+    pub text_vertex_count: usize,
+}
+
+/// A stop of a gradient, see [`Svg::set_gradient_stops`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgGradientStop {
+    /// Position of the stop along the gradient, from 0 to 1.
+    pub offset: f32,
+    /// Color of the stop, including its opacity.
+    pub color: Color,
+}
+
+impl SvgGradientStop {
+    /// The mean of the colors along a gradient with `stops`, which gradients are drawn in.
+    /// Offsets are clamped to `0..=1` and to at least the offset of the previous stop, like
+    /// SVG does, and the first and last colors extend to the ends of the gradient.
+    pub(crate) fn mean(stops: &[Self]) -> Color {
+        let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+            return Color::NONE;
+        };
+        let color = |stop: &Self| Vec4::from_array(stop.color.to_srgba().to_f32_array());
+        let mut previous = first.offset.clamp(0.0, 1.0);
+        let mut sum = color(first) * previous;
+        for pair in stops.windows(2) {
+            let offset = pair[1].offset.clamp(previous, 1.0);
+            sum += (color(&pair[0]) + color(&pair[1])) / 2.0 * (offset - previous);
+            previous = offset;
         }
+        let mean = sum + color(last) * (1.0 - previous);
+        Color::srgba(mean.x, mean.y, mean.z, mean.w)
     }
 }
 
+/// An element of the SVG with an `id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgNode {
+    /// Transform from the element's coordinate system to the document's.
+    pub abs_transform: Transform,
+    /// The element's own `transform`, from its coordinate system to its parent's.
+    pub transform: Transform,
+    /// The element's own `opacity`, already included in the colors of its paths.
+    pub opacity: f32,
+    /// The number of elements containing this one.
+    pub depth: usize,
+    /// Indices of the [`PathDescriptor`]s created from this element and its children.
+    pub paths: Range<usize>,
+    /// The human-readable name an authoring tool gave the element: `inkscape:label`, or
+    /// `data-name` of Figma exports.
+    pub label: Option<String>,
+    /// Whether the element is a layer, see [`Svg::layers`].
+    pub layer: bool,
+}
+
+/// A layer of an SVG, see [`Svg::layers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgLayerInfo {
+    /// The label of the layer, or its `id` if it has none.
+    pub label: String,
+    /// The `id` of the layer's element.
+    pub id: String,
+    /// Indices of the [`PathDescriptor`]s in the layer.
+    pub path_indices: Range<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PathDescriptor {
-    pub segments: Vec<PathEvent>,
+    /// Range of this path's segments in the SVG, use [`Svg::path_segments`] to get them.
+    pub segments: Range<usize>,
+    /// Transform from the path's coordinate system to the document's. Skewed paths are stored
+    /// in document space with an identity transform, since a [`Transform`] can't hold a skew.
     pub abs_transform: Transform,
     pub color: Color,
     pub draw_type: DrawType,
+    /// Index of the SVG path this descriptor was created from. The fill and the stroke
+    /// of the same path share this index.
+    pub source_path_index: usize,
+    /// Whether the path outlines text.
+    pub text: bool,
+    /// Whether the path is painted with a gradient, see
+    /// [`Svg::set_gradient_stops`].
+    pub gradient: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DrawType {
-    Fill,
+    Fill(FillOptions),
     Stroke(lyon_tessellation::StrokeOptions),
 }
 
@@ -176,137 +1485,135 @@ pub struct PathConvIter<'iter> {
     iter: PathSegmentsIter<'iter>,
     prev: Point,
     first: Point,
-    needs_end: bool,
+    /// Whether a subpath was begun and not yet ended.
+    open: bool,
     deferred: Option<PathEvent>,
-    scale: Transform2D<f32>,
 }
 
 const fn convert_point(value: usvg::tiny_skia_path::Point) -> Point2D<f32, UnknownUnit> {
     Point2D::new(value.x, value.y)
 }
 
+impl PathConvIter<'_> {
+    /// Ends the current subpath, if there is one. Only an explicit `Close` closes it, so
+    /// strokes get caps on open subpaths and a join at the seam of closed ones.
+    const fn end(&mut self, close: bool) -> Option<PathEvent> {
+        if !self.open {
+            return None;
+        }
+        self.open = false;
+        let last = self.prev;
+        if close {
+            self.prev = self.first;
+        }
+        Some(PathEvent::End {
+            last,
+            first: self.first,
+            close,
+        })
+    }
+
+    /// Emits a segment, preceded by a `Begin` if a previous subpath was closed without a
+    /// following `MoveTo`. Such a subpath starts where the closed one ended.
+    fn segment(&mut self, event: PathEvent) -> PathEvent {
+        self.prev = event.to();
+        if self.open {
+            return event;
+        }
+        self.open = true;
+        self.first = event.from();
+        self.deferred = Some(event);
+        PathEvent::Begin { at: self.first }
+    }
+}
+
 impl Iterator for PathConvIter<'_> {
     type Item = PathEvent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.deferred.is_some() {
-            return self.deferred.take();
-        }
-        let mut return_event = None;
-        let next = self.iter.next();
-        match next {
-            Some(PathSegment::MoveTo(p)) => {
-                if self.needs_end {
-                    let last = self.prev;
-                    let first = self.first;
-                    self.needs_end = false;
-                    self.prev = convert_point(p);
-                    self.deferred = Some(PathEvent::Begin { at: self.prev });
-                    self.first = self.prev;
-                    return_event = Some(PathEvent::End {
-                        last,
-                        first,
-                        close: false,
-                    });
-                } else {
-                    self.first = convert_point(p);
-                    return_event = Some(PathEvent::Begin { at: self.first });
+        if let Some(event) = self.deferred.take() {
+            return Some(event);
+        }
+        let event = loop {
+            break match self.iter.next() {
+                Some(PathSegment::MoveTo(p)) => {
+                    let at = convert_point(p);
+                    let end = self.end(false);
+                    self.first = at;
+                    self.prev = at;
+                    self.open = true;
+                    let begin = PathEvent::Begin { at };
+                    match end {
+                        Some(end) => {
+                            self.deferred = Some(begin);
+                            end
+                        }
+                        None => begin,
+                    }
                 }
-            }
-            Some(PathSegment::LineTo(p)) => {
-                self.needs_end = true;
-                let from = self.prev;
-                self.prev = convert_point(p);
-                return_event = Some(PathEvent::Line {
-                    from,
-                    to: self.prev,
-                });
-            }
-            Some(PathSegment::QuadTo(_ctrl, p)) => {
-                self.needs_end = true;
-                let from = self.prev;
-                self.prev = convert_point(p);
-                return_event = Some(PathEvent::Quadratic {
-                    from,
-                    ctrl: convert_point(p),
-                    to: self.prev,
-                });
-            }
-            Some(PathSegment::CubicTo(ctrl1, ctrl2, p)) => {
-                self.needs_end = true;
-                let from = self.prev;
-                self.prev = convert_point(p);
-                return_event = Some(PathEvent::Cubic {
-                    from,
+                Some(PathSegment::LineTo(p)) => self.segment(PathEvent::Line {
+                    from: self.prev,
+                    to: convert_point(p),
+                }),
+                Some(PathSegment::QuadTo(ctrl, p)) => self.segment(PathEvent::Quadratic {
+                    from: self.prev,
+                    ctrl: convert_point(ctrl),
+                    to: convert_point(p),
+                }),
+                Some(PathSegment::CubicTo(ctrl1, ctrl2, p)) => self.segment(PathEvent::Cubic {
+                    from: self.prev,
                     ctrl1: convert_point(ctrl1),
                     ctrl2: convert_point(ctrl2),
-                    to: self.prev,
-                });
-            }
-            Some(PathSegment::Close) => {
-                self.needs_end = false;
-                self.prev = self.first;
-                return_event = Some(PathEvent::End {
-                    last: self.prev,
-                    first: self.first,
-                    close: true,
-                });
-            }
-            None => {
-                if self.needs_end {
-                    self.needs_end = false;
-                    let last = self.prev;
-                    let first = self.first;
-                    return_event = Some(PathEvent::End {
-                        last,
-                        first,
-                        close: false,
-                    });
-                }
-            }
-        }
+                    to: convert_point(p),
+                }),
+                Some(PathSegment::Close) => match self.end(true) {
+                    Some(end) => end,
+                    // A repeated close has nothing left to close.
+                    None => continue,
+                },
+                None => self.end(false)?,
+            };
+        };
 
-        return return_event.map(|event| event.transformed(&self.scale));
+        Some(event)
     }
 }
 
 impl<'iter> Convert<PathConvIter<'iter>> for &'iter usvg::Path {
     fn convert(self) -> PathConvIter<'iter> {
-        return PathConvIter {
+        PathConvIter {
             iter: self.data().segments(),
             first: Point::new(0.0, 0.0),
             prev: Point::new(0.0, 0.0),
             deferred: None,
-            needs_end: false,
-            // For some reason the local transform of some paths has negative scale values.
-            // Here we correct to positive values.
-            scale: lyon_geom::Transform::scale(
-                if self.abs_transform().sx < 0.0 {
-                    -1.0
-                } else {
-                    1.0
-                },
-                if self.abs_transform().sy < 0.0 {
-                    -1.0
-                } else {
-                    1.0
-                },
-            ),
+            open: false,
         }
     }
 }
 
+/// Whether `transform` skews, i.e. maps the axes to non-perpendicular directions, which a
+/// [`Transform`] can't represent.
+fn skews(transform: usvg::Transform) -> bool {
+    let dot = transform.sx * transform.kx + transform.ky * transform.sy;
+    let lengths = transform.sx.hypot(transform.ky) * transform.kx.hypot(transform.sy);
+    dot.abs() > 1e-5 * lengths
+}
+
+impl Convert<Transform> for usvg::Transform {
+    fn convert(self) -> Transform {
+        Transform::from_matrix(Mat4::from_cols(
+            [self.sx, self.ky, 0.0, 0.0].into(),
+            [self.kx, self.sy, 0.0, 0.0].into(),
+            [0.0, 0.0, 1.0, 0.0].into(),
+            [self.tx, self.ty, 0.0, 1.0].into(),
+        ))
+    }
+}
+
 impl Convert<(Color, DrawType)> for &usvg::Stroke {
     #[inline]
     fn convert(self) -> (Color, DrawType) {
-        let color = match self.paint() {
-            usvg::Paint::Color(c) => {
-                Color::srgba_u8(c.red, c.green, c.blue, self.opacity().to_u8())
-            }
-            usvg::Paint::LinearGradient(_)
-            | usvg::Paint::RadialGradient(_)
-            | usvg::Paint::Pattern(_) => Color::default(),
-        };
+        let color = capability::paint_color(self.paint(), self.opacity());
 
         let linecap = match self.linecap() {
             usvg::LineCap::Butt => lyon_tessellation::LineCap::Butt,
@@ -320,11 +1627,11 @@ impl Convert<(Color, DrawType)> for &usvg::Stroke {
             usvg::LineJoin::MiterClip => lyon_tessellation::LineJoin::MiterClip,
         };
 
-        let opt = lyon_tessellation::StrokeOptions::tolerance(0.01)
+        let opt = DEFAULT_STROKE_OPTIONS
             .with_line_width(self.width().get())
             .with_line_cap(linecap)
             .with_line_join(linejoin);
 
-        return (color, DrawType::Stroke(opt))
+        (color, DrawType::Stroke(opt))
     }
 }
@@ -0,0 +1,408 @@
+//! Golden image tests for the rendered output of SVGs.
+//!
+//! [`render_svg`] draws an SVG headlessly into a texture sized to the document and reads the
+//! pixels back, [`assert_golden`] compares them with a stored reference image:
+//!
+//! ```no_run
+//! use bevy_svg::testing::{assert_golden, render_svg, RenderSettings};
+//!
+//! let image = render_svg("box.svg", &RenderSettings::default());
+//! assert_golden("box", &image, &Default::default());
+//! ```
+//!
+//! References are PNGs in [`REFERENCE_DIR`] of the crate running the test. To create or update
+//! them, run the tests with the environment variable `BEVY_SVG_UPDATE_GOLDEN=1`, and review
+//! the new images before committing them. When a comparison fails, an image highlighting the
+//! differing pixels is written to [`DIFF_DIR`].
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy::{
+    app::{App, PluginsState},
+    asset::{AssetPlugin, RenderAssetUsages},
+    camera::{OrthographicProjection, Projection, RenderTarget, ScalingMode},
+    core_pipeline::tonemapping::Tonemapping,
+    image::Image,
+    prelude::*,
+    render::{
+        gpu_readback::{Readback, ReadbackComplete},
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    },
+    tasks::tick_global_task_pools_on_main_thread,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use image::{Rgba, RgbaImage};
+
+use crate::prelude::{Origin, Svg, Svg2d, SvgBlendMode, SvgPlugin, SvgSettings};
+#[cfg(feature = "3d")]
+use crate::prelude::{Svg3d, SvgPbr};
+
+/// Directory of the reference images, relative to the manifest of the crate running the tests.
+pub const REFERENCE_DIR: &str = "tests/golden";
+
+/// Directory the diff images of failed comparisons are written to, relative to the manifest of
+/// the crate running the tests.
+pub const DIFF_DIR: &str = "target/golden-diff";
+
+/// How [`render_svg`] draws an SVG.
+#[derive(Clone, Debug)]
+pub struct RenderSettings {
+    /// Directory assets are loaded from. Defaults to `assets` of the crate running the tests.
+    pub asset_dir: PathBuf,
+    /// Origin of the SVG. The camera always shows the area of the document at the `TopLeft`
+    /// origin, so other origins move the SVG out of view by design.
+    pub origin: Origin,
+    /// Color the SVG is drawn over.
+    pub background: Color,
+    /// Settings the SVG is loaded with.
+    pub loader_settings: SvgSettings,
+    /// Blend mode of the SVG entities, overriding that of the asset.
+    pub blend_mode: Option<SvgBlendMode>,
+    /// Draws the SVGs as [`Svg3d`] with a 3D camera looking at their front along `-Z`,
+    /// instead of in 2D.
+    #[cfg(feature = "3d")]
+    pub svg_3d: bool,
+    /// Draws the SVGs in 3D with this lit material, under a white directional light falling
+    /// in at an angle, regardless of [`svg_3d`](Self::svg_3d).
+    #[cfg(feature = "3d")]
+    pub pbr: Option<SvgPbr>,
+    /// Frames after which rendering is given up, e.g. because the asset failed to load.
+    pub max_frames: usize,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            asset_dir: Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default())
+                .join("assets"),
+            origin: Origin::TopLeft,
+            background: Color::WHITE,
+            loader_settings: SvgSettings::default(),
+            blend_mode: None,
+            #[cfg(feature = "3d")]
+            svg_3d: false,
+            #[cfg(feature = "3d")]
+            pbr: None,
+            max_frames: 1000,
+        }
+    }
+}
+
+/// How much a rendered image may differ from its reference in [`assert_golden`].
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// Perceived color difference between `0.0` and `1.0` from which two pixels differ. The
+    /// default ignores small differences from anti-aliasing and color conversions.
+    pub threshold: f32,
+    /// Number of differing pixels that is still accepted.
+    pub max_mismatched_pixels: usize,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            max_mismatched_pixels: 0,
+        }
+    }
+}
+
+/// The result of comparing two images with [`compare`].
+#[derive(Clone, Debug)]
+pub struct ImageDiff {
+    /// Number of pixels that differ by more than the threshold.
+    pub mismatched_pixels: usize,
+    /// The actual image, faded, with differing pixels in red.
+    pub diff: RgbaImage,
+}
+
+#[derive(Resource)]
+struct Rendered(Arc<Mutex<Option<Vec<u8>>>>);
+
+/// Renders the SVG at `asset_path` with a headless renderer into an image of the size of the
+/// document, one pixel per SVG unit.
+///
+/// # Panics
+///
+/// Panics if no GPU is available, or the SVG isn't rendered within
+/// [`RenderSettings::max_frames`].
+#[must_use]
+pub fn render_svg(asset_path: &str, settings: &RenderSettings) -> RgbaImage {
+    render_svgs(&[(asset_path, Vec3::ZERO)], settings)
+}
+
+/// Renders several SVGs on top of each other, each moved by its translation.
+///
+/// The image has the size of the first one. The SVGs are spawned in order, so swapping them tests whether
+/// the result depends on the draw order.
+///
+/// # Panics
+///
+/// Panics if no GPU is available, `svgs` is empty, or the SVGs aren't rendered within
+/// [`RenderSettings::max_frames`].
+#[must_use]
+pub fn render_svgs(svgs: &[(&str, Vec3)], settings: &RenderSettings) -> RgbaImage {
+    let mut app = App::new();
+    // Without a window or an event loop, which can't be created outside the main thread of
+    // test harnesses.
+    app.add_plugins((
+        DefaultPlugins
+            .set(AssetPlugin {
+                file_path: settings.asset_dir.to_string_lossy().into_owned(),
+                ..Default::default()
+            })
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+                ..Default::default()
+            })
+            .disable::<WinitPlugin>(),
+        SvgPlugin::default(),
+    ));
+    while app.plugins_state() == PluginsState::Adding {
+        tick_global_task_pools_on_main_thread();
+    }
+    app.finish();
+    app.cleanup();
+
+    let handles: Vec<Handle<Svg>> = svgs
+        .iter()
+        .map(|(asset_path, _)| {
+            let loader_settings = settings.loader_settings.clone();
+            app.world().resource::<AssetServer>().load_with_settings(
+                (*asset_path).to_owned(),
+                move |svg_settings: &mut SvgSettings| *svg_settings = loader_settings.clone(),
+            )
+        })
+        .collect();
+    let mut frames = 0;
+    let size = loop {
+        app.update();
+        let assets = app.world().resource::<Assets<Svg>>();
+        if handles.iter().all(|handle| assets.contains(handle)) {
+            break assets.get(&handles[0]).expect("SVGs are loaded").size;
+        }
+        frames += 1;
+        assert!(frames < settings.max_frames, "SVGs {svgs:?} didn't load");
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (width, height) = (size.x.ceil().max(1.0) as u32, size.y.ceil().max(1.0) as u32);
+    let mut target = Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    target.texture_descriptor.usage |=
+        TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING;
+    let target = app.world_mut().resource_mut::<Assets<Image>>().add(target);
+
+    let rendered = Arc::new(Mutex::new(None));
+    app.insert_resource(Rendered(rendered.clone()));
+    let world = app.world_mut();
+    // The mesh spans from the origin to the right and down, which the camera shows exactly.
+    // Without tonemapping, the pixels keep the colors of the document.
+    let mut camera = world.spawn((
+        Camera {
+            clear_color: ClearColorConfig::Custom(settings.background),
+            ..Default::default()
+        },
+        Tonemapping::None,
+        RenderTarget::Image(target.clone().into()),
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: size.x,
+                height: size.y,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+        Transform::from_xyz(size.x / 2.0, -size.y / 2.0, 0.0),
+    ));
+    #[cfg(feature = "3d")]
+    let svg_3d = settings.svg_3d || settings.pbr.is_some();
+    #[cfg(not(feature = "3d"))]
+    let svg_3d = false;
+    if svg_3d {
+        // In front of the SVGs, which face the camera along +Z.
+        camera.insert((
+            Camera3d::default(),
+            Transform::from_xyz(size.x / 2.0, -size.y / 2.0, 100.0),
+        ));
+        #[cfg(feature = "3d")]
+        world.spawn((
+            DirectionalLight::default(),
+            Transform::from_xyz(1.0, 1.0, 1.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ));
+    } else {
+        camera.insert(Camera2d);
+    }
+    for (handle, (_, translation)) in handles.into_iter().zip(svgs) {
+        let transform = Transform::from_translation(*translation);
+        #[cfg(feature = "3d")]
+        let mut svg = if svg_3d {
+            world.spawn((Svg3d(handle), settings.origin, transform))
+        } else {
+            world.spawn((Svg2d(handle), settings.origin, transform))
+        };
+        #[cfg(not(feature = "3d"))]
+        let mut svg = world.spawn((Svg2d(handle), settings.origin, transform));
+        #[cfg(feature = "3d")]
+        if let Some(pbr) = settings.pbr {
+            svg.insert(pbr);
+        }
+        if let Some(blend_mode) = settings.blend_mode {
+            svg.insert(blend_mode);
+        }
+    }
+    world.spawn(Readback::texture(target)).observe(
+        |readback: On<ReadbackComplete>, rendered: Res<Rendered>| {
+            *rendered.0.lock().unwrap_or_else(PoisonError::into_inner) =
+                Some(readback.data.clone());
+        },
+    );
+
+    // Give the mesh a few frames to be linked and uploaded before the first readback counts.
+    let mut frames = 0;
+    let data = loop {
+        app.update();
+        frames += 1;
+        let data = rendered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(data) = data.filter(|_| frames > 3) {
+            break data;
+        }
+        assert!(
+            frames < settings.max_frames,
+            "SVGs {svgs:?} weren't rendered"
+        );
+    };
+    RgbaImage::from_raw(width, height, data).expect("readback has the size of the target")
+}
+
+/// Compares two images of the same size, counting pixels whose perceived color differs by
+/// more than `threshold`, see [`Tolerance::threshold`].
+///
+/// # Panics
+///
+/// Panics if the images differ in size.
+#[must_use]
+pub fn compare(actual: &RgbaImage, reference: &RgbaImage, threshold: f32) -> ImageDiff {
+    assert_eq!(
+        actual.dimensions(),
+        reference.dimensions(),
+        "images differ in size"
+    );
+    let mut mismatched_pixels = 0;
+    let mut diff = RgbaImage::new(actual.width(), actual.height());
+    for ((a, r), d) in actual
+        .pixels()
+        .zip(reference.pixels())
+        .zip(diff.pixels_mut())
+    {
+        if color_delta(*a, *r) > threshold {
+            mismatched_pixels += 1;
+            *d = Rgba([255, 0, 0, 255]);
+        } else {
+            let gray = luma(*a).mul_add(0.25, 0.75);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let gray = (gray * 255.0) as u8;
+            *d = Rgba([gray, gray, gray, 255]);
+        }
+    }
+    ImageDiff {
+        mismatched_pixels,
+        diff,
+    }
+}
+
+/// Compares `actual` with the reference image `name`, or stores it as reference when the
+/// environment variable `BEVY_SVG_UPDATE_GOLDEN` is set.
+///
+/// # Panics
+///
+/// Panics if the images differ by more than `tolerance`, or the reference is missing.
+pub fn assert_golden(name: &str, actual: &RgbaImage, tolerance: &Tolerance) {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let reference_path = manifest_dir.join(REFERENCE_DIR).join(format!("{name}.png"));
+
+    if std::env::var_os("BEVY_SVG_UPDATE_GOLDEN").is_some() {
+        if let Some(dir) = reference_path.parent() {
+            std::fs::create_dir_all(dir).expect("can create the reference directory");
+        }
+        actual
+            .save(&reference_path)
+            .expect("can write the reference image");
+        return;
+    }
+
+    let reference = image::open(&reference_path)
+        .unwrap_or_else(|e| {
+            panic!(
+                "missing reference image {}: {e}, run with BEVY_SVG_UPDATE_GOLDEN=1 to create it",
+                reference_path.display()
+            )
+        })
+        .into_rgba8();
+    assert_eq!(
+        actual.dimensions(),
+        reference.dimensions(),
+        "rendered image of {name} differs in size from its reference"
+    );
+    let diff = compare(actual, &reference, tolerance.threshold);
+    if diff.mismatched_pixels > tolerance.max_mismatched_pixels {
+        let diff_path = manifest_dir.join(DIFF_DIR).join(format!("{name}.png"));
+        if let Some(dir) = diff_path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = diff.diff.save(&diff_path);
+        panic!(
+            "rendered image of {name} differs from its reference in {} pixels, see {}",
+            diff.mismatched_pixels,
+            diff_path.display()
+        );
+    }
+}
+
+/// Luma of a pixel between `0.0` and `1.0`, blended over white.
+fn luma(pixel: Rgba<u8>) -> f32 {
+    let [y, _, _] = yiq(pixel);
+    y
+}
+
+/// Perceived difference of two pixels between `0.0` and `1.0`, using the weighted YIQ distance
+/// of "Measuring perceived color difference using YIQ NTSC transmission color space in mobile
+/// applications" by Kotsarenko and Ramos.
+#[allow(clippy::many_single_char_names)]
+fn color_delta(a: Rgba<u8>, b: Rgba<u8>) -> f32 {
+    // The largest possible delta, between black and white.
+    const MAX_DELTA: f32 = 0.5053;
+
+    let [ya, ia, qa] = yiq(a);
+    let [yb, ib, qb] = yiq(b);
+    let (y, i, q) = (ya - yb, ia - ib, qa - qb);
+    (0.1957 * q).mul_add(q, (0.5053 * y).mul_add(y, 0.299 * i * i)) / MAX_DELTA
+}
+
+/// Converts a pixel, blended over white, to YIQ with all components in `0.0..=1.0` range.
+fn yiq(pixel: Rgba<u8>) -> [f32; 3] {
+    let alpha = f32::from(pixel[3]) / 255.0;
+    let [r, g, b] = [pixel[0], pixel[1], pixel[2]]
+        .map(|channel| (f32::from(channel) / 255.0).mul_add(alpha, 1.0 - alpha));
+    [
+        0.114f32.mul_add(b, 0.298_895_f32.mul_add(r, 0.586_622 * g)),
+        0.321_184_f32.mul_add(-b, 0.595_716_f32.mul_add(r, -0.274_453 * g)),
+        0.311_718_f32.mul_add(b, 0.211_456_f32.mul_add(r, -0.522_591 * g)),
+    ]
+}
@@ -0,0 +1,192 @@
+//! Rendering single SVG entities into textures.
+use bevy::{
+    asset::{AssetEvent, Assets, RenderAssetUsages},
+    camera::{
+        visibility::RenderLayers, OrthographicProjection, Projection, RenderTarget, ScalingMode,
+    },
+    image::Image,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+};
+
+use crate::{camera::SvgTextureCamera, origin::Origin, render::svg2d::Svg2d, svg::Svg};
+
+/// Renders an [`Svg2d`] entity into its own texture instead of the screen, e.g. to apply a
+/// glow or distortion with an effect material to the texture.
+///
+/// Once the SVG is loaded, the entity gets a [`SvgTexture`] with the texture. It is sized to
+/// the [`content_bounds`](Svg::content_bounds) of the SVG, or its size if it has no content,
+/// at `pixels_per_unit`, and has a transparent background.
+///
+/// The setup it creates:
+/// - The entity is moved to the [`RenderLayers`] `layer`, so cameras that don't render that
+///   layer, like the default ones, don't show it anymore.
+/// - A child entity with a [`Camera2d`] renders only `layer` into the texture. Its
+///   orthographic projection covers the bounds of the SVG in the entity's local space,
+///   including the offset of an [`Origin`] as long as the entity isn't rotated. It renders
+///   before the default cameras, with an order of `-1`. Camera-dependent features like
+///   [`SvgZoomLayers`](crate::prelude::SvgZoomLayers) don't track it.
+///
+/// Use a different `layer` for each entity rendered into a texture. Removing the component
+/// despawns the camera and returns the entity to the render layers it had before.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgRenderToTexture {
+    /// Render layer of the entity and its camera.
+    pub layer: usize,
+    /// Texture pixels per SVG unit.
+    pub pixels_per_unit: f32,
+}
+
+impl Default for SvgRenderToTexture {
+    fn default() -> Self {
+        Self {
+            layer: 1,
+            pixels_per_unit: 1.0,
+        }
+    }
+}
+
+/// The texture an entity with a [`SvgRenderToTexture`] is rendered into.
+#[derive(Clone, Component, Debug)]
+pub struct SvgTexture {
+    /// The texture, apply effect materials to it.
+    pub image: Handle<Image>,
+    /// The camera entity rendering into the texture.
+    pub camera: Entity,
+    /// The render layers of the entity before it was moved to the layer of the texture, `None`
+    /// for the default layer.
+    pub(crate) previous_layers: Option<RenderLayers>,
+}
+
+/// Sets up and updates the textures and cameras of entities with a [`SvgRenderToTexture`],
+/// and tears them down when it is removed.
+pub fn update_svg_textures(
+    mut commands: Commands,
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    images: Option<ResMut<Assets<Image>>>,
+    query: Query<(
+        Entity,
+        Ref<Svg2d>,
+        Ref<SvgRenderToTexture>,
+        Option<&Origin>,
+        Option<&SvgTexture>,
+        Option<&RenderLayers>,
+    )>,
+    mut cameras: Query<(&mut Transform, &mut Projection)>,
+    mut removed: RemovedComponents<SvgRenderToTexture>,
+    orphaned: Query<&SvgTexture, Without<SvgRenderToTexture>>,
+) {
+    // Without images, e.g. in headless apps without the `ImagePlugin`, there is nothing to
+    // render into.
+    let Some(mut images) = images else {
+        return;
+    };
+    for entity in removed.read() {
+        if let Ok(texture) = orphaned.get(entity) {
+            commands.entity(texture.camera).despawn();
+            let mut entity = commands.entity(entity);
+            entity.remove::<SvgTexture>();
+            match &texture.previous_layers {
+                Some(layers) => entity.insert(layers.clone()),
+                None => entity.remove::<RenderLayers>(),
+            };
+        }
+    }
+
+    let modified = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, svg_component, settings, origin, texture, layers) in &query {
+        let handle = &svg_component.0;
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        let up_to_date = texture.is_some()
+            && !svg_component.is_changed()
+            && !settings.is_changed()
+            && !modified.contains(&handle.id());
+        if up_to_date {
+            continue;
+        }
+
+        let bounds = svg
+            .content_bounds()
+            .unwrap_or_else(|| Rect::from_corners(Vec2::ZERO, svg.size));
+        // The mesh has a flipped y-axis.
+        let center = Vec2::new(bounds.center().x, -bounds.center().y).extend(0.0)
+            + origin.map_or(Vec3::ZERO, |origin| origin.compute_translation(svg.size));
+        let projection = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: bounds.width(),
+                height: bounds.height(),
+            },
+            ..OrthographicProjection::default_2d()
+        });
+        let pixels = (bounds.size() * settings.pixels_per_unit)
+            .ceil()
+            .max(Vec2::ONE);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let extent = Extent3d {
+            width: pixels.x as u32,
+            height: pixels.y as u32,
+            depth_or_array_layers: 1,
+        };
+        let layer = RenderLayers::layer(settings.layer);
+
+        if let Some(texture) = texture {
+            if let Some(image) = images.get_mut(&texture.image) {
+                if image.texture_descriptor.size != extent {
+                    image.resize(extent);
+                }
+            }
+            if let Ok((mut transform, mut camera_projection)) = cameras.get_mut(texture.camera) {
+                transform.translation = center;
+                *camera_projection = projection;
+            }
+            commands.entity(texture.camera).insert(layer.clone());
+            commands.entity(entity).insert(layer);
+            continue;
+        }
+
+        let mut image = Image::new_fill(
+            extent,
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage |=
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+        let image = images.add(image);
+        let camera = commands
+            .spawn((
+                Camera2d,
+                SvgTextureCamera,
+                Camera {
+                    order: -1,
+                    clear_color: ClearColorConfig::Custom(Color::NONE),
+                    ..Default::default()
+                },
+                RenderTarget::Image(image.clone().into()),
+                projection,
+                Transform::from_translation(center),
+                layer.clone(),
+                ChildOf(entity),
+            ))
+            .id();
+        commands.entity(entity).insert((
+            layer,
+            SvgTexture {
+                image,
+                camera,
+                previous_layers: layers.cloned(),
+            },
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::{asset::Handle, platform::collections::HashMap, prelude::*};
+
+use crate::{render::SvgComponent, svg::Svg};
+
+/// The name of the active theme, e.g. `"light"` or `"dark"`.
+///
+/// Every entity with a [`SvgTheme`] shows its variant for this theme.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+pub struct CurrentTheme(pub String);
+
+/// Variants of an SVG keyed by theme name.
+///
+/// When [`CurrentTheme`] changes, the handle of the entity's `Svg2d`/`Svg3d` is swapped for the
+/// variant of the new theme. If there is no variant for a theme, the current handle is kept.
+#[derive(Clone, Component, Debug, Default)]
+pub struct SvgTheme {
+    /// The SVG to show for each theme.
+    pub variants: HashMap<String, Handle<Svg>>,
+}
+
+impl SvgTheme {
+    /// Adds the variant to show for `theme`.
+    #[must_use]
+    pub fn with_variant(mut self, theme: impl Into<String>, svg: Handle<Svg>) -> Self {
+        self.variants.insert(theme.into(), svg);
+        self
+    }
+}
+
+/// Swaps the handles of themed SVGs when the theme or their variants change.
+pub fn apply_theme<C: SvgComponent>(
+    theme: Res<CurrentTheme>,
+    mut query: Query<(Ref<SvgTheme>, &mut C)>,
+) {
+    for (svg_theme, mut svg_component) in &mut query {
+        if !theme.is_changed() && !svg_theme.is_changed() {
+            continue;
+        }
+        if let Some(handle) = svg_theme.variants.get(&theme.0) {
+            if svg_component.get_handle() != handle {
+                *svg_component.get_handle_mut() = handle.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use core::hash::{Hash as _, Hasher as _};
+use std::{hash::DefaultHasher, sync::Arc};
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    mesh::{Mesh, VertexAttributeValues},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    camera::{self, SvgCamera, TrackableCameras},
+    facing::{DefaultSvgFacing, SvgFacing},
+    nine_slice::SvgNineSlice,
+    regen::{SvgRegenPriority, SvgRegenQueue},
+    render::{
+        tessellation::{Facing, TessellationOptions},
+        SvgComponent,
+    },
+    stroke::{self, SvgMinStrokePixels, SvgStrokeScale},
+    style::{SvgFillOverride, SvgStrokeStyleOverride},
+    svg::Svg,
+    zoom::{SvgZoomLayers, SvgZoomLayersState},
+};
+
+/// Cache of meshes that were tessellated from an [`Svg`] with non-default options.
+///
+/// Entities that need a variant of an SVG mesh share it through this cache, keyed by the
+/// asset and a caller-chosen variant key. Variants requested through the [`SvgRegenQueue`]
+/// end up here once they are tessellated.
+#[derive(Resource)]
+pub struct SvgMeshVariants {
+    /// Maximum number of cached variants. Beyond it, the least recently used variants that no
+    /// entity draws anymore are dropped, e.g. the stroke widths of zoom levels the camera left.
+    /// Variants still in use are kept even beyond it.
+    pub capacity: usize,
+    /// The variants, with the use count at which each was last used.
+    meshes: HashMap<(AssetId<Svg>, u64), (u64, Handle<Mesh>)>,
+    generations: HashMap<AssetId<Svg>, u64>,
+    /// Counts the uses of variants, to find the least recent one.
+    uses: u64,
+}
+
+impl Default for SvgMeshVariants {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            meshes: HashMap::default(),
+            generations: HashMap::default(),
+            uses: 0,
+        }
+    }
+}
+
+impl SvgMeshVariants {
+    /// Returns the cached variant `key` of the asset, creating its mesh with `tessellate` if it
+    /// doesn't exist yet.
+    pub fn get_or_insert_with(
+        &mut self,
+        id: AssetId<Svg>,
+        key: u64,
+        meshes: &mut Assets<Mesh>,
+        tessellate: impl FnOnce() -> Mesh,
+    ) -> Handle<Mesh> {
+        if let Some(mesh) = self.get(id, key) {
+            return mesh;
+        }
+        let mesh = meshes.add(tessellate());
+        self.insert(id, key, mesh.clone());
+        mesh
+    }
+
+    /// Returns the cached variant `key` of the asset, if it exists, and marks it as used.
+    pub fn get(&mut self, id: AssetId<Svg>, key: u64) -> Option<Handle<Mesh>> {
+        let (used, mesh) = self.meshes.get_mut(&(id, key))?;
+        self.uses += 1;
+        *used = self.uses;
+        Some(mesh.clone())
+    }
+
+    /// Caches `mesh` as variant `key` of the asset.
+    pub fn insert(&mut self, id: AssetId<Svg>, key: u64, mesh: Handle<Mesh>) {
+        self.uses += 1;
+        self.meshes.insert((id, key), (self.uses, mesh));
+        while self.meshes.len() > self.capacity {
+            // The new variant isn't used yet, the entity that requested it only picks it up later.
+            let oldest = self
+                .meshes
+                .iter()
+                .filter(|(cached, (_, mesh))| **cached != (id, key) && !is_used(mesh))
+                .min_by_key(|(_, (used, _))| *used)
+                .map(|(key, _)| *key);
+            let Some(oldest) = oldest else {
+                break;
+            };
+            self.meshes.remove(&oldest);
+        }
+    }
+
+    /// Returns `true` if the variant `key` of the asset is cached.
+    #[must_use]
+    pub fn contains(&self, id: AssetId<Svg>, key: u64) -> bool {
+        self.meshes.contains_key(&(id, key))
+    }
+
+    /// Number of cached variants.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Returns `true` if no variants are cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Number of times the asset was modified since its variants were first cached. Include it
+    /// in variant keys, so variants tessellated from an older version of the asset, e.g. by a
+    /// job that was still running when it was modified, are never used.
+    #[must_use]
+    pub fn generation(&self, id: AssetId<Svg>) -> u64 {
+        self.generations.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Drops all variants that are no longer used by any entity.
+    pub fn release_unused(&mut self) {
+        self.meshes.retain(|_, (_, mesh)| is_used(mesh));
+    }
+
+    /// Drops all variants of the given asset and starts a new generation of it.
+    pub fn invalidate(&mut self, id: AssetId<Svg>) {
+        self.meshes.retain(|(svg, _), _| *svg != id);
+        *self.generations.entry(id).or_default() += 1;
+    }
+}
+
+/// Whether an entity, or anything else, holds `mesh` besides the cache.
+fn is_used(mesh: &Handle<Mesh>) -> bool {
+    match mesh {
+        Handle::Strong(handle) => Arc::strong_count(handle) > 1,
+        Handle::Uuid(..) => true,
+    }
+}
+
+/// Drops cached variants of assets that were modified or removed, so they get re-tessellated
+/// from the new data.
+pub fn invalidate_mesh_variants(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    mut variants: ResMut<SvgMeshVariants>,
+) {
+    for event in svg_events.read() {
+        match event {
+            AssetEvent::Modified { id } => variants.invalidate(*id),
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                variants.invalidate(*id);
+                variants.generations.remove(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Marks entities that draw a mesh variant instead of the mesh of their asset.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct SvgMeshVariant;
+
+/// Everything that makes the mesh of an entity differ from the mesh of its asset. All of it
+/// goes into one variant, so the features combine instead of replacing each other's mesh.
+#[derive(Clone, Debug)]
+struct VariantParams {
+    stroke_style: Option<SvgStrokeStyleOverride>,
+    fill: Option<SvgFillOverride>,
+    stroke_scale: Option<i32>,
+    min_stroke_width: Option<i32>,
+    hidden: Vec<usize>,
+    nine_slice: Option<SvgNineSlice>,
+    facing: Facing,
+    normals: bool,
+}
+
+impl VariantParams {
+    /// Whether the mesh has to be tessellated again, rather than derived from the asset's.
+    fn tessellates(&self, svg: &Svg) -> bool {
+        self.stroke_style.is_some()
+            || self.fill.is_some()
+            || self.stroke_scale.is_some()
+            || self.min_stroke_width.is_some()
+            || !self.hidden.is_empty()
+            || self.facing != svg.tessellation_options.facing
+            || self.normals
+    }
+
+    fn key(&self, generation: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        "variant".hash(&mut hasher);
+        generation.hash(&mut hasher);
+        self.stroke_style
+            .as_ref()
+            .map(SvgStrokeStyleOverride::variant_key)
+            .hash(&mut hasher);
+        self.fill
+            .map(SvgFillOverride::variant_key)
+            .hash(&mut hasher);
+        (self.stroke_scale, self.min_stroke_width).hash(&mut hasher);
+        self.hidden.hash(&mut hasher);
+        self.nine_slice.is_some().hash(&mut hasher);
+        if let Some(nine_slice) = &self.nine_slice {
+            nine_slice.hash_into(&mut hasher);
+        }
+        (self.facing, self.normals).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Tessellates the variant of `svg`.
+    fn tessellate(&self, svg: &Svg) -> Mesh {
+        let mut svg = match &self.stroke_style {
+            Some(stroke_style) => stroke_style.apply(svg),
+            None => svg.clone(),
+        };
+        if let Some(fill) = &self.fill {
+            fill.recolor(&mut svg);
+        }
+        let authored = svg.tessellation_options;
+        let options = TessellationOptions {
+            stroke_scale: authored.stroke_scale * self.stroke_scale.map_or(1.0, stroke::step_value),
+            min_stroke_width: self
+                .min_stroke_width
+                .map_or(authored.min_stroke_width, stroke::step_value),
+            facing: self.facing,
+            ..authored
+        };
+        let (mut mesh, _) =
+            svg.tessellate_visible(&options, |index| self.hidden.binary_search(&index).is_err());
+        self.finish(&svg, &mut mesh);
+        mesh
+    }
+
+    /// Applies the parts of the variant that only move vertices or add attributes.
+    fn finish(&self, svg: &Svg, mesh: &mut Mesh) {
+        if let Some(nine_slice) = &self.nine_slice {
+            nine_slice.apply(svg, mesh);
+        }
+        if self.normals {
+            let normals = vec![self.facing.normal(); mesh.count_vertices()];
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_NORMAL,
+                VertexAttributeValues::Float32x3(normals),
+            );
+        }
+    }
+}
+
+/// The components that change the mesh of an SVG entity.
+type VariantComponents = (
+    Option<&'static SvgStrokeStyleOverride>,
+    Option<&'static SvgFillOverride>,
+    Option<&'static SvgStrokeScale>,
+    Option<&'static SvgMinStrokePixels>,
+    Option<Ref<'static, SvgZoomLayers>>,
+    Option<&'static mut SvgZoomLayersState>,
+    Option<&'static SvgNineSlice>,
+    Option<&'static SvgFacing>,
+);
+
+/// Gives each SVG entity the mesh variant composed of all its components that change its
+/// mesh, and returns entities to the asset's mesh once they have none left.
+///
+/// Variants that have to be tessellated again are requested from the [`SvgRegenQueue`], one
+/// job per variant however many entities need it, and the entities keep their previous mesh
+/// until the variant is ready.
+/// Variants that only stretch the asset's mesh, like a [`SvgNineSlice`] on its own, are
+/// derived right away.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn apply_mesh_variants<C: SvgComponent>(
+    mut commands: Commands,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut variants: ResMut<SvgMeshVariants>,
+    mut queue: ResMut<SvgRegenQueue>,
+    default_facing: Res<DefaultSvgFacing>,
+    svg_camera: Res<SvgCamera>,
+    cameras: TrackableCameras,
+    mut query: Query<(
+        Entity,
+        &C,
+        VariantComponents,
+        Option<&SvgRegenPriority>,
+        Has<SvgMeshVariant>,
+        &GlobalTransform,
+        &mut C::MeshComponent,
+    )>,
+    #[cfg(feature = "3d")] lit: Query<(), With<crate::render::svg3d::SvgPbr>>,
+) {
+    let tracked = camera::tracked_camera(*svg_camera, &cameras);
+    let zoom = tracked
+        .filter(|(_, ortho)| ortho.scale > 0.0)
+        .map(|(_, ortho)| ortho.scale.recip());
+    let world_per_pixel =
+        tracked.and_then(|(camera, ortho)| camera::world_per_pixel(camera, ortho));
+
+    let mut released = false;
+    for (entity, svg_component, components, priority, is_variant, transform, mut mesh) in &mut query
+    {
+        let handle = svg_component.get_handle();
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        let (
+            stroke_style,
+            fill,
+            stroke_scale,
+            min_stroke_pixels,
+            zoom_layers,
+            zoom_state,
+            nine_slice,
+            facing,
+        ) = components;
+
+        let scale = transform.scale().xy().abs().max_element();
+        let stroke_scale = stroke_scale.and_then(|stroke_scale| stroke_scale.step(scale));
+        let stroke_factor = stroke_scale.map_or(1.0, stroke::step_value);
+        let min_stroke_width =
+            min_stroke_pixels
+                .zip(world_per_pixel)
+                .and_then(|(min_pixels, world_per_pixel)| {
+                    min_pixels.step(svg, world_per_pixel, scale, stroke_factor)
+                });
+        let hidden = zoom_layers.map_or_else(Vec::new, |zoom_layers| {
+            let previous = zoom_state
+                .as_ref()
+                .filter(|_| !zoom_layers.is_changed())
+                .map(|state| state.visible.as_slice());
+            // Without a camera, the layers keep their visibility.
+            let visible = match zoom {
+                Some(zoom) => zoom_layers.visibility(zoom, previous),
+                None => {
+                    previous.map_or_else(|| vec![true; zoom_layers.layers.len()], <[bool]>::to_vec)
+                }
+            };
+            let hidden = zoom_layers.hidden_paths(svg, &visible);
+            match zoom_state {
+                Some(mut state) => {
+                    if state.visible != visible {
+                        state.visible = visible;
+                    }
+                }
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(SvgZoomLayersState { visible });
+                }
+            }
+            hidden
+        });
+        #[cfg(feature = "3d")]
+        let normals = lit.contains(entity);
+        #[cfg(not(feature = "3d"))]
+        let normals = false;
+        let params = VariantParams {
+            stroke_style: stroke_style.cloned(),
+            fill: fill.copied(),
+            stroke_scale,
+            min_stroke_width,
+            hidden,
+            nine_slice: nine_slice.copied(),
+            facing: facing.map_or(default_facing.0, |facing| facing.0),
+            normals,
+        };
+
+        if !params.tessellates(svg) && params.nine_slice.is_none() {
+            if is_variant {
+                queue.cancel(entity);
+                commands.entity(entity).remove::<SvgMeshVariant>();
+                *C::get_mesh_mut(&mut mesh) = svg.mesh.clone();
+                released = true;
+            }
+            continue;
+        }
+        if !is_variant {
+            commands.entity(entity).insert(SvgMeshVariant);
+        }
+
+        let id = handle.id();
+        let key = params.key(variants.generation(id));
+        let target = if let Some(target) = variants.get(id, key) {
+            target
+        } else if params.tessellates(svg) {
+            let priority = priority.map_or(0, |priority| priority.0);
+            // Entities that need the same variant share its job, so the document is only
+            // cloned for the first of them.
+            if !queue.is_requested(entity, id, key) && !queue.join(entity, id, key, priority) {
+                let svg = svg.clone();
+                queue.request(entity, id, key, priority, move || params.tessellate(&svg));
+            }
+            // Until the variant is ready, the previous mesh stays.
+            continue;
+        } else {
+            // Stretching the asset's mesh is much cheaper than tessellating again, which
+            // matters while a panel is being resized.
+            let Some(mut stretched) = meshes.get(&svg.mesh).cloned() else {
+                continue;
+            };
+            params.finish(svg, &mut stretched);
+            let target = meshes.add(stretched);
+            variants.insert(id, key, target.clone());
+            target
+        };
+        // An older request of the entity is obsolete now.
+        queue.cancel(entity);
+
+        if *C::get_mesh_mut(mesh.bypass_change_detection()) != target {
+            *C::get_mesh_mut(&mut mesh) = target;
+        }
+    }
+    if released {
+        variants.release_unused();
+    }
+}
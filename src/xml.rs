@@ -0,0 +1,179 @@
+//! Helpers for the passes over the raw XML that `usvg` doesn't cover.
+use std::borrow::Cow;
+
+use bevy::platform::collections::HashMap;
+
+const INKSCAPE_NS: &str = "http://www.inkscape.org/namespaces/inkscape";
+pub(crate) const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+
+/// Returns the text of the document in `bytes`, decompressing `svgz` first. Returns `None` if it
+/// isn't valid UTF-8.
+pub(crate) fn document_text(bytes: &[u8]) -> Option<Cow<'_, str>> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let data = usvg::decompress_svgz(bytes).ok()?;
+        String::from_utf8(data).ok().map(Cow::Owned)
+    } else {
+        core::str::from_utf8(bytes).ok().map(Cow::Borrowed)
+    }
+}
+
+/// Parses `text`, allowing a DTD with entity declarations like `usvg` does.
+pub(crate) fn parse_document(text: &str) -> Result<roxmltree::Document<'_>, roxmltree::Error> {
+    roxmltree::Document::parse_with_options(
+        text,
+        roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// The id an element references, from `href` or the `xlink:href` of SVG 1.1, whichever it has.
+/// Documents mix both, so every reference the crate resolves itself should go through this;
+/// `usvg` already resolves both for gradients, patterns, `use` and images. Returns `None` for
+/// references to other documents.
+pub(crate) fn href<'a>(node: &roxmltree::Node<'a, '_>) -> Option<&'a str> {
+    node.attribute("href")
+        .or_else(|| node.attribute((XLINK_NS, "href")))?
+        .trim()
+        .strip_prefix('#')
+}
+
+/// The name an authoring tool gave an element.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ElementLabel {
+    /// `inkscape:label`, or `data-name` of Figma exports.
+    pub label: Option<String>,
+    /// Whether the element is a layer: an Inkscape group with `inkscape:groupmode="layer"`, or
+    /// a labelled group directly in the root element, which is how Figma exports its layers.
+    pub layer: bool,
+}
+
+/// Collects the labels of all elements with an `id` in `document`. Elements without an `id`
+/// can't be matched with the elements `usvg` keeps, so they are skipped.
+pub(crate) fn element_labels(document: &roxmltree::Document<'_>) -> HashMap<String, ElementLabel> {
+    let root = document.root_element();
+    document
+        .descendants()
+        .filter(roxmltree::Node::is_element)
+        .filter_map(|node| {
+            let id = node.attribute("id").filter(|id| !id.is_empty())?;
+            let inkscape_layer = node.attribute((INKSCAPE_NS, "groupmode")) == Some("layer");
+            let label = node
+                .attribute((INKSCAPE_NS, "label"))
+                .or_else(|| node.attribute("data-name"))
+                .map(str::to_owned);
+            let top_level_group = node.tag_name().name() == "g"
+                && node.parent_element() == Some(root)
+                && label.is_some();
+            (label.is_some() || inkscape_layer).then(|| {
+                (
+                    id.to_owned(),
+                    ElementLabel {
+                        label,
+                        layer: inkscape_layer || top_level_group,
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+/// Whether the `width` and `height` of the root element are both given in physical units like
+/// `mm` or `in`, rather than in pixels, percentages or without a unit.
+pub(crate) fn has_physical_size(document: &roxmltree::Document<'_>) -> bool {
+    let root = document.root_element();
+    ["width", "height"].into_iter().all(|name| {
+        root.attribute(name).is_some_and(|value| {
+            let unit = value.trim().trim_start_matches(|c: char| {
+                c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')
+            });
+            matches!(unit.trim(), "mm" | "cm" | "in" | "pt" | "pc" | "Q")
+        })
+    })
+}
+
+/// Computes the size in bytes of the document after expanding its entity references, stopping
+/// once `limit` is exceeded. Nested entities like those of a "billion laughs" document would
+/// otherwise expand to gigabytes while parsing.
+///
+/// Only internal entities are considered, external ones are never loaded.
+pub(crate) fn entity_expansion_size(text: &str, limit: usize) -> usize {
+    let Some(subset_start) = text
+        .find("<!DOCTYPE")
+        .and_then(|start| text[start..].find('[').map(|offset| start + offset + 1))
+    else {
+        return text.len();
+    };
+    let subset_end = text[subset_start..]
+        .find("]>")
+        .map_or(text.len(), |offset| subset_start + offset);
+    let subset = &text[subset_start..subset_end];
+
+    let mut entities = HashMap::new();
+    let mut rest = subset;
+    while let Some(start) = rest.find("<!ENTITY") {
+        rest = &rest[start + "<!ENTITY".len()..];
+        let mut parts = rest.trim_start().splitn(2, char::is_whitespace);
+        let (Some(name), Some(value)) = (parts.next(), parts.next()) else {
+            break;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        if let Some(end) = value[1..].find(quote) {
+            entities.insert(name, &value[1..=end]);
+        }
+    }
+
+    let mut sizes = HashMap::new();
+    subset_end.saturating_add(expanded_size(
+        &text[subset_end..],
+        &entities,
+        &mut sizes,
+        limit,
+        0,
+    ))
+}
+
+/// Expanded size of `text` with all references to `entities` replaced, saturating at
+/// `limit + 1`.
+fn expanded_size<'a>(
+    text: &'a str,
+    entities: &HashMap<&'a str, &'a str>,
+    sizes: &mut HashMap<&'a str, usize>,
+    limit: usize,
+    depth: usize,
+) -> usize {
+    // Deeper nesting than this is either a loop or an attack.
+    const MAX_DEPTH: usize = 16;
+
+    let mut size = text.len();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let name = &rest[..end];
+        let Some(value) = entities.get(name) else {
+            continue;
+        };
+        let entity_size = match sizes.get(name) {
+            Some(size) => *size,
+            None if depth >= MAX_DEPTH => limit + 1,
+            None => {
+                let entity_size = expanded_size(value, entities, sizes, limit, depth + 1);
+                sizes.insert(name, entity_size);
+                entity_size
+            }
+        };
+        size = size.saturating_add(entity_size);
+        if size > limit {
+            return limit + 1;
+        }
+        rest = &rest[end + 1..];
+    }
+    size
+}
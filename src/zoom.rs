@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::svg::Svg;
+
+/// The zoom range in which an element of the SVG is visible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoomLayer {
+    /// The `id` or authoring-tool label of the element, usually a group, see [`Svg::node`].
+    pub id: String,
+    /// Minimum zoom at which the element is visible.
+    pub min_zoom: f32,
+    /// Maximum zoom at which the element is visible.
+    pub max_zoom: f32,
+}
+
+/// Shows elements of an SVG only in a configured zoom range, e.g. street names on a map
+/// only when zoomed in.
+///
+/// The zoom is the inverse of the scale of the [`SvgCamera`](crate::prelude::SvgCamera), or of
+/// the first active orthographic camera, so `2.0` means zoomed in to twice the size. Elements not listed here are always visible.
+#[derive(Clone, Component, Debug, PartialEq)]
+pub struct SvgZoomLayers {
+    /// The configured elements.
+    pub layers: Vec<ZoomLayer>,
+    /// Relative margin around the range limits, to avoid flickering when the zoom hovers around
+    /// a limit. An element only appears once the zoom is this far inside its range, and only
+    /// disappears once it is this far outside.
+    pub hysteresis: f32,
+}
+
+impl Default for SvgZoomLayers {
+    fn default() -> Self {
+        Self {
+            layers: Vec::new(),
+            hysteresis: 0.05,
+        }
+    }
+}
+
+impl SvgZoomLayers {
+    /// Adds an element that is visible between `min_zoom` and `max_zoom`.
+    #[must_use]
+    pub fn with_layer(mut self, id: impl Into<String>, min_zoom: f32, max_zoom: f32) -> Self {
+        self.layers.push(ZoomLayer {
+            id: id.into(),
+            min_zoom,
+            max_zoom,
+        });
+        self
+    }
+}
+
+impl SvgZoomLayers {
+    /// Returns which layers are visible at `zoom`. With the visibility of the previous frame,
+    /// layers only change once the zoom is past the hysteresis margin.
+    pub(crate) fn visibility(&self, zoom: f32, previous: Option<&[bool]>) -> Vec<bool> {
+        let margin = 1.0 + self.hysteresis.max(0.0);
+        let previous = previous.filter(|previous| previous.len() == self.layers.len());
+        self.layers
+            .iter()
+            .enumerate()
+            .map(
+                |(index, layer)| match previous.map(|visible| visible[index]) {
+                    Some(true) => {
+                        zoom >= layer.min_zoom / margin && zoom <= layer.max_zoom * margin
+                    }
+                    Some(false) => {
+                        zoom >= layer.min_zoom * margin && zoom <= layer.max_zoom / margin
+                    }
+                    None => zoom >= layer.min_zoom && zoom <= layer.max_zoom,
+                },
+            )
+            .collect()
+    }
+
+    /// Returns the sorted indices of the paths of `svg` that the layers hide, given which
+    /// layers are `visible`.
+    pub(crate) fn hidden_paths(&self, svg: &Svg, visible: &[bool]) -> Vec<usize> {
+        let mut hidden: Vec<usize> = self
+            .layers
+            .iter()
+            .zip(visible)
+            .filter(|(_, visible)| !**visible)
+            .filter_map(|(layer, _)| svg.node(&layer.id))
+            .flat_map(|node| node.paths.clone())
+            .collect();
+        hidden.sort_unstable();
+        hidden.dedup();
+        hidden
+    }
+}
+
+/// Which of the [`SvgZoomLayers`] of an entity are currently shown.
+#[derive(Clone, Component, Debug, Default)]
+pub struct SvgZoomLayersState {
+    pub(crate) visible: Vec<bool>,
+}
@@ -0,0 +1,215 @@
+//! SMIL animations played back on the mesh of an entity.
+use bevy::{diagnostic::DiagnosticsPlugin, mesh::VertexAttributeValues, prelude::*};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+type Frame = (Vec<[f32; 3]>, Vec<[f32; 4]>);
+
+/// Loads `gear.svg` with its animations, without anti-aliasing fringes.
+fn load_gear(app: &mut App) -> Handle<Svg> {
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load_with_settings(
+        "gear.svg",
+        |settings: &mut SvgSettings| {
+            settings.parse_animations = true;
+            settings.edge_aa_width = 0.0;
+        },
+    );
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            return handle;
+        }
+        app.update();
+    }
+    panic!("gear.svg didn't load");
+}
+
+/// Returns the vertex positions and colors of the mesh `entity` currently draws.
+fn current_frame(app: &App, entity: Entity) -> Frame {
+    let mesh = &app.world().get::<Mesh2d>(entity).unwrap().0;
+    let mesh = app.world().resource::<Assets<Mesh>>().get(mesh).unwrap();
+    let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(SVG_ATTRIBUTE_POSITION)
+    else {
+        panic!("no positions");
+    };
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(SVG_ATTRIBUTE_COLOR) else {
+        panic!("no colors");
+    };
+    (positions.clone(), colors.clone())
+}
+
+/// Returns the vertex positions and colors of `gear.svg` at `time` seconds.
+fn frame(time: f32) -> Frame {
+    let mut app = app();
+    let handle = load_gear(&mut app);
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle),
+            SvgAnimationPlayer {
+                elapsed: time,
+                paused: true,
+                ..Default::default()
+            },
+        ))
+        .id();
+    for _ in 0..3 {
+        app.update();
+    }
+    current_frame(&app, entity)
+}
+
+/// Returns the bounds in document coordinates of the vertices whose color is mostly `channel`.
+fn bounds(frame: &Frame, channel: usize) -> Rect {
+    frame
+        .0
+        .iter()
+        .zip(&frame.1)
+        .filter(|(_, color)| (0..3).all(|i| (color[i] > 0.5) == (i == channel)))
+        .fold(Rect::EMPTY, |bounds, (position, _)| {
+            // The mesh is y-up, the document y-down.
+            bounds.union_point(Vec2::new(position[0], -position[1]))
+        })
+}
+
+fn assert_bounds(bounds: Rect, min: Vec2, max: Vec2) {
+    assert!(
+        bounds.min.abs_diff_eq(min, 1e-3) && bounds.max.abs_diff_eq(max, 1e-3),
+        "{bounds:?} isn't from {min} to {max}"
+    );
+}
+
+#[test]
+fn gear_rotates_around_its_center() {
+    const GEAR: usize = 2;
+    assert_bounds(
+        bounds(&frame(0.0), GEAR),
+        Vec2::new(40.0, 10.0),
+        Vec2::new(60.0, 60.0),
+    );
+    // A quarter turn clockwise, the tooth points right.
+    assert_bounds(
+        bounds(&frame(1.0), GEAR),
+        Vec2::new(40.0, 40.0),
+        Vec2::new(90.0, 60.0),
+    );
+    // Half a turn, the tooth points down.
+    assert_bounds(
+        bounds(&frame(2.0), GEAR),
+        Vec2::new(40.0, 40.0),
+        Vec2::new(60.0, 90.0),
+    );
+}
+
+#[test]
+fn transform_animation_replaces_the_static_transform() {
+    const HAND: usize = 1;
+    // Without `additive="sum"`, the rotation replaces the offset of 1000.
+    assert_bounds(
+        bounds(&frame(1.0), HAND),
+        Vec2::new(50.0, 45.0),
+        Vec2::new(90.0, 55.0),
+    );
+}
+
+#[test]
+fn opacity_animation_starts_at_and_replaces_the_static_opacity() {
+    const FADE: usize = 0;
+    let frame = frame(1.0);
+    let alphas: Vec<f32> = frame
+        .1
+        .iter()
+        .filter(|color| color[FADE] > 0.5 && color[1] < 0.5 && color[2] < 0.5)
+        .map(|color| color[3])
+        .collect();
+    assert!(!alphas.is_empty());
+    // Halfway from the static 0.5 to 0.
+    for alpha in alphas {
+        assert!((alpha - 0.25).abs() < 1e-4, "alpha {alpha}");
+    }
+}
+
+/// An app whose time advances by 100 milliseconds every frame.
+fn timed_app() -> App {
+    let mut app = app();
+    app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+        core::time::Duration::from_millis(100),
+    ));
+    app
+}
+
+#[test]
+fn pausing_freezes_and_seeking_jumps() {
+    const GEAR: usize = 2;
+    let mut app = timed_app();
+    let handle = load_gear(&mut app);
+    let entity = app.world_mut().spawn(Svg2d(handle)).id();
+    for _ in 0..5 {
+        app.update();
+    }
+    app.world_mut()
+        .get_mut::<SvgAnimationPlayer>(entity)
+        .unwrap()
+        .pause();
+    app.update();
+    let paused = bounds(&current_frame(&app, entity), GEAR);
+    for _ in 0..5 {
+        app.update();
+        assert_eq!(bounds(&current_frame(&app, entity), GEAR), paused);
+    }
+
+    app.world_mut()
+        .get_mut::<SvgAnimationPlayer>(entity)
+        .unwrap()
+        .set_time(2.0);
+    app.update();
+    // Half a turn, the tooth points down.
+    assert_bounds(
+        bounds(&current_frame(&app, entity), GEAR),
+        Vec2::new(40.0, 40.0),
+        Vec2::new(60.0, 90.0),
+    );
+}
+
+#[test]
+fn entities_sharing_an_asset_play_independently() {
+    const GEAR: usize = 2;
+    let mut app = timed_app();
+    let handle = load_gear(&mut app);
+    let paused = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgAnimationPlayer {
+                paused: true,
+                ..Default::default()
+            },
+        ))
+        .id();
+    let playing = app.world_mut().spawn(Svg2d(handle)).id();
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let start = bounds(&frame(0.0), GEAR);
+    assert_eq!(bounds(&current_frame(&app, paused), GEAR), start);
+    assert_ne!(bounds(&current_frame(&app, playing), GEAR), start);
+    assert_ne!(
+        app.world().get::<Mesh2d>(paused).unwrap().0,
+        app.world().get::<Mesh2d>(playing).unwrap().0
+    );
+}
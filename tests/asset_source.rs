@@ -0,0 +1,54 @@
+//! SVGs loaded from an asset source that isn't backed by the filesystem.
+use std::path::Path;
+
+use bevy::{
+    asset::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetSourceBuilder,
+    },
+    diagnostic::DiagnosticsPlugin,
+    prelude::*,
+};
+use bevy_svg::prelude::*;
+
+const TEXT_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="40">
+    <text x="10" y="30" font-family="Fira Sans" font-size="20" fill="#ff0000">Hello text</text>
+</svg>"##;
+
+#[test]
+fn text_loads_from_an_in_memory_source_with_fonts_from_bytes() {
+    let dir = Dir::default();
+    dir.insert_asset(Path::new("text.svg"), TEXT_SVG.as_bytes().to_vec());
+
+    let mut app = App::new();
+    app.register_asset_source(
+        "memory",
+        AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+    )
+    .add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin::default(),
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app.world_mut()
+        .resource_mut::<SvgFonts>()
+        .load_font_data(include_bytes!("../assets/fonts/FiraSans-Bold.ttf").to_vec());
+
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load("memory://text.svg");
+    for _ in 0..1000 {
+        if let Some(svg) = app.world().resource::<Assets<Svg>>().get(&handle) {
+            assert_eq!(svg.name, "text.svg");
+            assert!(svg.paths.iter().any(|path| path.text));
+            assert!(app.world().resource::<Assets<Mesh>>().contains(&svg.mesh));
+            return;
+        }
+        app.update();
+    }
+    panic!("SVG didn't load");
+}
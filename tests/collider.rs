@@ -0,0 +1,30 @@
+//! Collider outlines of SVG entities.
+use bevy::math::Vec2;
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn outline_matches_shape() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="60">
+        <rect x="10" y="20" width="30" height="20" fill="#000000" stroke="#ff0000"/>
+        <circle cx="70" cy="30" r="20" fill="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    let SvgColliderOutline(polygons) = SvgColliderOutline::from_svg(&svg);
+    // The stroke of the rect isn't included.
+    assert_eq!(polygons.len(), 2);
+
+    let rect = &polygons[0];
+    assert_eq!(rect.len(), 4);
+    for corner in [(10.0, -20.0), (40.0, -20.0), (40.0, -40.0), (10.0, -40.0)] {
+        let corner = Vec2::from(corner);
+        assert!(rect.iter().any(|point| point.distance(corner) < 1e-3));
+    }
+
+    let center = Vec2::new(70.0, -30.0);
+    assert!(polygons[1].len() > 8);
+    for point in &polygons[1] {
+        assert!((point.distance(center) - 20.0).abs() < 0.1);
+    }
+}
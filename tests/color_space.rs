@@ -0,0 +1,41 @@
+//! Vertex colors in linear and sRGB color space.
+use bevy::{
+    color::{Color, ColorToComponents as _},
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn colors(color_space: VertexColorSpace) -> Vec<[f32; 4]> {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <rect x="2" y="2" width="6" height="6" fill="#808080" stroke="#808080"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    let mesh = svg.tessellate_with_options(&TessellationOptions {
+        color_space,
+        // The fringe fades out to transparent.
+        edge_aa_width: 0.0,
+        ..Default::default()
+    });
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    colors.clone()
+}
+
+#[test]
+fn swatch_in_both_color_spaces() {
+    let swatch = Color::srgb_u8(0x80, 0x80, 0x80);
+    for (color_space, expected) in [
+        (VertexColorSpace::Linear, swatch.to_linear().to_f32_array()),
+        (VertexColorSpace::Srgb, swatch.to_srgba().to_f32_array()),
+    ] {
+        // Fill and stroke vertices alike.
+        for color in colors(color_space) {
+            assert_eq!(color, expected, "{color_space:?}");
+        }
+    }
+    assert!(colors(VertexColorSpace::Linear)[0][0] < 0.25);
+    assert!((colors(VertexColorSpace::Srgb)[0][0] - 0.502).abs() < 0.001);
+}
@@ -0,0 +1,162 @@
+//! Mapping between SVG document and world coordinates.
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+fn svg() -> Svg {
+    let mut svg = Svg::default();
+    svg.size = Vec2::new(100.0, 50.0);
+    svg
+}
+
+#[test]
+fn svg_to_world_flips_y_and_applies_origin() {
+    let svg = svg();
+    let transform = Transform::from_xyz(10.0, 20.0, 0.0).with_scale(Vec3::splat(2.0));
+
+    let top_left = svg.svg_to_world(Vec2::ZERO, &transform, Origin::TopLeft);
+    assert_eq!(top_left, Vec2::new(10.0, 20.0));
+    let below = svg.svg_to_world(Vec2::new(0.0, 10.0), &transform, Origin::TopLeft);
+    assert_eq!(below, Vec2::new(10.0, 0.0));
+    let center = svg.svg_to_world(Vec2::new(50.0, 25.0), &transform, Origin::Center);
+    assert_eq!(center, Vec2::new(10.0, 20.0));
+}
+
+#[test]
+fn world_to_svg_round_trips() {
+    let svg = svg();
+    let transform = Transform::from_xyz(-30.0, 7.5, 1.0)
+        .with_rotation(Quat::from_rotation_z(0.7))
+        .with_scale(Vec3::new(1.5, 0.5, 1.0));
+    let origins = [
+        Origin::BottomLeft,
+        Origin::BottomRight,
+        Origin::Center,
+        Origin::TopLeft,
+        Origin::TopRight,
+    ];
+
+    for origin in origins {
+        for point in [Vec2::ZERO, Vec2::new(100.0, 50.0), Vec2::new(12.5, 40.0)] {
+            let world = svg.svg_to_world(point, &transform, origin);
+            let back = svg.world_to_svg(world, &transform, origin);
+            assert!(
+                back.abs_diff_eq(point, 1e-4),
+                "{origin:?}: {point} -> {back}"
+            );
+        }
+    }
+}
+
+#[test]
+fn mesh_to_viewbox_transform_undoes_tessellation() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="10 20 40 20">
+        <rect x="15" y="25" width="10" height="5" fill="#000000"/>
+    </svg>"##;
+    let tree = bevy_svg::usvg::Tree::from_str(text, &bevy_svg::usvg::Options::default()).unwrap();
+    let mut svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    // Without the anti-aliasing fringe, which reaches beyond the rectangle.
+    svg.tessellation_options.edge_aa_width = 0.0;
+    let mesh = svg.tessellate();
+    let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+
+    let to_view_box = svg.mesh_to_viewbox_transform();
+    let corners = positions.iter().map(|position| {
+        to_view_box
+            .transform_point(Vec3::from_array(*position))
+            .truncate()
+    });
+    let bounds = corners.fold(Rect::EMPTY, |bounds, corner| bounds.union_point(corner));
+    assert!(
+        bounds.min.distance(Vec2::new(15.0, 25.0)) < 1e-4,
+        "{bounds:?}"
+    );
+    assert!(
+        bounds.max.distance(Vec2::new(25.0, 30.0)) < 1e-4,
+        "{bounds:?}"
+    );
+}
+
+/// Bounds of the red vertices of `svg`, tessellated without the anti-aliasing fringe.
+fn red_bounds(svg: &mut Svg) -> Rect {
+    svg.tessellation_options.edge_aa_width = 0.0;
+    let mesh = svg.tessellate();
+    let (
+        Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)),
+        Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)),
+    ) = (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+        mesh.attribute(Mesh::ATTRIBUTE_COLOR),
+    )
+    else {
+        panic!("mesh has no positions or colors");
+    };
+    positions
+        .iter()
+        .zip(colors)
+        .filter(|(_, color)| **color == [1.0, 0.0, 0.0, 1.0])
+        .fold(Rect::EMPTY, |bounds, (position, _)| {
+            bounds.union_point(Vec2::new(position[0], position[1]))
+        })
+}
+
+fn load(bytes: &[u8], flip_y: bool) -> Svg {
+    let tree = bevy_svg::usvg::Tree::from_data(bytes, &bevy_svg::usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(
+        &tree,
+        &SvgConvertOptions {
+            flip_y,
+            ..Default::default()
+        },
+    )
+}
+
+fn assert_rect_eq(actual: Rect, min: Vec2, max: Vec2) {
+    assert!(
+        actual.min.abs_diff_eq(min, 1e-3) && actual.max.abs_diff_eq(max, 1e-3),
+        "{actual:?} != {min}..{max}"
+    );
+}
+
+#[test]
+fn flip_y_puts_the_top_left_of_y_up_content_at_the_mesh_origin() {
+    let bytes = include_bytes!("../assets/y_up.svg");
+    // The mesh has a flipped y-axis, so the top left of the document is at the origin and its
+    // bottom at -100.
+    let mut flipped = load(bytes, true);
+    assert_rect_eq(
+        red_bounds(&mut flipped),
+        Vec2::new(0.0, -20.0),
+        Vec2::new(20.0, 0.0),
+    );
+    let mut unflipped = load(bytes, false);
+    assert_rect_eq(
+        red_bounds(&mut unflipped),
+        Vec2::new(0.0, -100.0),
+        Vec2::new(20.0, -80.0),
+    );
+}
+
+#[test]
+fn content_mirrored_by_its_transform_is_drawn_the_right_way_up() {
+    let mut mirrored = load(include_bytes!("../assets/y_up_mirrored.svg"), false);
+    assert_rect_eq(
+        red_bounds(&mut mirrored),
+        Vec2::new(0.0, -20.0),
+        Vec2::new(20.0, 0.0),
+    );
+
+    // Skewed paths are moved into document space, which mirrors them the same way.
+    let skewed = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <rect x="0" y="80" width="20" height="20" fill="#ff0000" transform="matrix(1 0 0.5 -1 0 100)"/>
+    </svg>"##;
+    let mut skewed = load(skewed.as_bytes(), false);
+    assert_rect_eq(
+        red_bounds(&mut skewed),
+        Vec2::new(40.0, -20.0),
+        Vec2::new(70.0, 0.0),
+    );
+}
@@ -0,0 +1,29 @@
+//! Back-face culling of 3D SVGs. Needs a GPU.
+use bevy_svg::{
+    prelude::{Facing, SvgSettings},
+    testing::{render_svg, RenderSettings},
+};
+use image::Rgba;
+
+/// Renders the blue square in 3D, tessellated to face `facing`, and returns the pixel at its
+/// center.
+fn square_center(facing: Facing) -> Rgba<u8> {
+    let settings = RenderSettings {
+        svg_3d: true,
+        loader_settings: SvgSettings {
+            facing,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    *render_svg("golden/solid_fill.svg", &settings).get_pixel(16, 16)
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn front_faces_are_drawn_and_back_faces_culled() {
+    // The camera looks at the SVG along -Z.
+    let Rgba([red, green, blue, _]) = square_center(Facing::PositiveZ);
+    assert!(blue > 200 && red < 50 && green < 50, "{red} {green} {blue}");
+    assert_eq!(square_center(Facing::NegativeZ), Rgba([255, 255, 255, 255]));
+}
@@ -0,0 +1,41 @@
+//! `currentColor` resolved against the nearest `color` attribute.
+use bevy::{diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+#[test]
+fn nested_color_attributes_resolve_per_element() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load_with_settings("current_color.svg", |settings: &mut SvgSettings| {
+            settings.current_color = Some("#00ff00".to_owned())
+        });
+    for _ in 0..1000 {
+        if let Some(svg) = app.world().resource::<Assets<Svg>>().get(&handle) {
+            let colors: Vec<Srgba> = svg.paths.iter().map(|path| path.color.to_srgba()).collect();
+            assert_eq!(
+                colors,
+                [
+                    Srgba::rgb(0.0, 1.0, 0.0),
+                    Srgba::rgb(1.0, 0.0, 0.0),
+                    Srgba::rgb(0.0, 0.0, 1.0),
+                ]
+            );
+            return;
+        }
+        app.update();
+    }
+    panic!("SVG didn't load");
+}
@@ -0,0 +1,66 @@
+//! Bounds drawn by the `SvgDebugPlugin`.
+use bevy::{diagnostic::DiagnosticsPlugin, math::Rect, prelude::*};
+use bevy_svg::{debug::debug_rects, prelude::*};
+
+/// Maps `rect` from mesh space to world space with `transform`.
+fn to_world(transform: &GlobalTransform, rect: Rect) -> Rect {
+    Rect::from_corners(
+        transform.transform_point(rect.min.extend(0.0)).xy(),
+        transform.transform_point(rect.max.extend(0.0)).xy(),
+    )
+}
+
+fn assert_rect_eq(actual: Rect, expected: Rect) {
+    assert!(
+        (actual.min - expected.min).abs().max_element() < 1e-3
+            && (actual.max - expected.max).abs().max_element() < 1e-3,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn debug_rects_follow_the_origin_and_scale() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("box.svg");
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            Origin::Center,
+            Transform::from_scale(Vec3::splat(2.0)),
+        ))
+        .id();
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            break;
+        }
+        app.update();
+    }
+    app.update();
+
+    let svg = app.world().resource::<Assets<Svg>>().get(&handle).unwrap();
+    let transform = app.world().get::<GlobalTransform>(entity).unwrap();
+    let (size, content) = debug_rects(svg);
+    // The 100 units large document, scaled by 2 and centered on the entity.
+    assert_rect_eq(
+        to_world(transform, size),
+        Rect::new(-100.0, -100.0, 100.0, 100.0),
+    );
+    // The rectangle from 10.015 to 89.986, without its stroke width. The y-axis is flipped.
+    let (near, far) = (2.0 * 10.015 - 100.0, 2.0 * 89.986 - 100.0);
+    assert_rect_eq(
+        to_world(transform, content.unwrap()),
+        Rect::new(near, -far, far, -near),
+    );
+}
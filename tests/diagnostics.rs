@@ -0,0 +1,54 @@
+//! Errors of paths that don't tessellate completely.
+use bevy::{diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn diagnostics_are_recorded_when_the_asset_is_tessellated() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load("pathological.svg");
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            break;
+        }
+        app.update();
+    }
+    let loaded = app.world().resource::<Assets<Svg>>().get(&handle).unwrap();
+
+    // The loaded asset reports the errors of its mesh, without tessellating it again, and they
+    // are the same as those of tessellating the document from scratch.
+    let text = include_str!("../assets/pathological.svg");
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let fresh = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    let describe = |svg: &Svg| {
+        svg.tessellation_diagnostics()
+            .iter()
+            .map(|(index, error)| format!("{index}: {error:?}"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(describe(loaded), describe(&fresh));
+    let path_count = loaded.stats().path_count;
+    for (index, _) in loaded.tessellation_diagnostics() {
+        assert!(index < path_count);
+    }
+
+    // The paths that tessellate are still drawn.
+    let mesh = app
+        .world()
+        .resource::<Assets<Mesh>>()
+        .get(&loaded.mesh)
+        .unwrap();
+    assert!(mesh.count_vertices() >= 4);
+}
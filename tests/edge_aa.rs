@@ -0,0 +1,38 @@
+//! The anti-aliasing fringe around fills, which is off by default.
+use bevy::mesh::{Mesh, VertexAttributeValues};
+use bevy_svg::{prelude::*, usvg};
+
+fn alphas(svg: &Svg, options: &TessellationOptions) -> Vec<f32> {
+    let mesh = svg.tessellate_with_options(options);
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    colors.iter().map(|color| color[3]).collect()
+}
+
+#[test]
+fn fills_only_get_a_fringe_when_enabled() {
+    assert_eq!(TessellationOptions::default().edge_aa_width, 0.0);
+    assert_eq!(SvgSettings::default().edge_aa_width, 0.0);
+
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <rect x="2" y="2" width="6" height="6" fill="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    // The fringe fades out to transparent on the outside of the fill.
+    let fringed = alphas(
+        &svg,
+        &TessellationOptions {
+            edge_aa_width: 1.0,
+            ..Default::default()
+        },
+    );
+    assert!(fringed.contains(&0.0));
+    assert!(fringed.contains(&1.0));
+    let hard = alphas(&svg, &svg.tessellation_options);
+    assert!(hard.iter().all(|alpha| *alpha == 1.0));
+    assert!(hard.len() < fringed.len());
+}
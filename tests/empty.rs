@@ -0,0 +1,48 @@
+//! SVGs that load, but have nothing to draw.
+use bevy::{diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+/// The SVGs reported by `SvgEmpty` messages.
+#[derive(Default, Resource)]
+struct Reported(Vec<AssetId<Svg>>);
+
+#[test]
+fn empty_svgs_are_reported() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default())
+    .init_resource::<Reported>()
+    .add_systems(
+        Last,
+        |mut empty: MessageReader<SvgEmpty>, mut reported: ResMut<Reported>| {
+            reported.0.extend(empty.read().map(|empty| empty.id));
+        },
+    );
+    let server = app.world().resource::<AssetServer>();
+    let empty: Handle<Svg> = server.load("empty.svg");
+    let drawn: Handle<Svg> = server.load("box.svg");
+    for _ in 0..1000 {
+        let svgs = app.world().resource::<Assets<Svg>>();
+        if svgs.contains(&empty) && svgs.contains(&drawn) {
+            break;
+        }
+        app.update();
+    }
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let svgs = app.world().resource::<Assets<Svg>>();
+    assert!(svgs.get(&empty).unwrap().is_empty());
+    assert!(!svgs.get(&drawn).unwrap().is_empty());
+    assert_eq!(app.world().resource::<Reported>().0, [empty.id()]);
+}
@@ -0,0 +1,45 @@
+//! Documents with a DOCTYPE that declares XML entities.
+use bevy::{asset::LoadState, diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+/// Loads `path` and returns the SVG, or `None` if the load failed.
+fn load(path: &'static str) -> Option<Svg> {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load(path);
+    for _ in 0..1000 {
+        if let Some(svg) = app.world().resource::<Assets<Svg>>().get(&handle) {
+            return Some(svg.clone());
+        }
+        if let LoadState::Failed(_) = app.world().resource::<AssetServer>().load_state(&handle) {
+            return None;
+        }
+        app.update();
+    }
+    panic!("{path} neither loaded nor failed");
+}
+
+#[test]
+fn entity_for_a_color_is_expanded() {
+    let svg = load("entities.svg").expect("entities.svg didn't load");
+    assert_eq!(svg.paths.len(), 1);
+    assert_eq!(
+        svg.paths[0].color.to_srgba(),
+        Srgba::rgb_u8(0xff, 0x80, 0x00)
+    );
+}
+
+#[test]
+fn entity_expansion_bomb_is_rejected() {
+    assert!(load("billion_laughs.svg").is_none());
+}
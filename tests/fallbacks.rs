@@ -0,0 +1,92 @@
+//! What is drawn in place of the features this crate can't draw, see `SvgCapability`.
+use std::{ops::Range, sync::Arc};
+
+use bevy::{
+    color::Color,
+    math::{Rect, Vec2, Vec3},
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn fallbacks() -> Svg {
+    Svg::from_bytes_with_fontdb(
+        include_bytes!("../assets/fallbacks.svg"),
+        "fallbacks.svg",
+        Arc::new(usvg::fontdb::Database::new()),
+    )
+    .unwrap()
+}
+
+/// Indices of the paths of the element `id`.
+fn paths(svg: &Svg, id: &str) -> Range<usize> {
+    svg.node(id).map_or(0..0, |node| node.paths.clone())
+}
+
+/// Bounds in document coordinates of the paths of the element `id`.
+fn bounds(svg: &Svg, id: &str) -> Rect {
+    svg.paths[paths(svg, id)]
+        .iter()
+        .flat_map(|path| {
+            svg.path_segments(path).iter().map(move |event| {
+                let point = event.to();
+                path.abs_transform
+                    .transform_point(Vec3::new(point.x, point.y, 0.0))
+                    .truncate()
+            })
+        })
+        .fold(Rect::EMPTY, |bounds, point| bounds.union_point(point))
+}
+
+fn assert_bounds(bounds: Rect, min: Vec2, max: Vec2) {
+    assert!(
+        bounds.min.abs_diff_eq(min, 1e-3) && bounds.max.abs_diff_eq(max, 1e-3),
+        "{bounds:?} isn't from {min} to {max}"
+    );
+}
+
+fn assert_color(svg: &Svg, id: &str, color: Color) {
+    let paths = &svg.paths[paths(svg, id)];
+    assert!(!paths.is_empty(), "{id} isn't drawn");
+    for path in paths {
+        assert_eq!(path.color.to_srgba(), color.to_srgba(), "{id}");
+    }
+}
+
+#[test]
+fn missing_capabilities_are_drawn_with_their_fallbacks() {
+    let svg = fallbacks();
+    assert_eq!(
+        svg.missing_capabilities(),
+        [
+            SvgCapability::Gradient,
+            SvgCapability::Pattern,
+            SvgCapability::Image,
+            SvgCapability::ClipPath,
+            SvgCapability::Mask,
+            SvgCapability::Filter,
+        ]
+    );
+
+    // Flat colors.
+    assert_color(&svg, "gradient-fill", Color::srgb(0.5, 0.0, 0.5));
+    assert_color(&svg, "pattern-fill", Color::srgba(0.5, 0.5, 0.5, 0.5));
+    // Drawn without the effect.
+    assert_bounds(
+        bounds(&svg, "clipped"),
+        Vec2::new(110.0, 10.0),
+        Vec2::new(190.0, 90.0),
+    );
+    assert_bounds(
+        bounds(&svg, "filtered"),
+        Vec2::new(110.0, 110.0),
+        Vec2::new(190.0, 190.0),
+    );
+    // Skipped.
+    assert!(paths(&svg, "masked").is_empty());
+    // A placeholder with the bounds of the image.
+    assert_color(&svg, "image", PLACEHOLDER_COLOR);
+    assert_bounds(
+        bounds(&svg, "image"),
+        Vec2::new(210.0, 110.0),
+        Vec2::new(290.0, 190.0),
+    );
+}
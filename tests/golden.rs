@@ -0,0 +1,88 @@
+//! Renders SVGs and compares them with the reference images in `tests/golden`, or checks
+//! their pixels directly. Needs a GPU.
+use bevy::math::Vec3;
+use bevy_svg::{
+    prelude::{Origin, SvgBlendMode, SvgSettings},
+    testing::{assert_golden, compare, render_svg, render_svgs, RenderSettings, Tolerance},
+};
+
+#[test]
+#[ignore = "needs a GPU"]
+fn solid_fill() {
+    let image = render_svg("golden/solid_fill.svg", &RenderSettings::default());
+    assert_golden("solid_fill", &image, &Default::default());
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn stroke_width() {
+    // The 4 units wide stroke is centered on the outline of the 16 units wide square.
+    let image = render_svg("golden/stroke_width.svg", &RenderSettings::default());
+    assert_golden("stroke_width", &image, &Default::default());
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn origin_center() {
+    // The camera shows the document at the `TopLeft` origin, so centering it moves the
+    // bottom right quarter of the square into the top left of the image.
+    let settings = RenderSettings {
+        origin: Origin::Center,
+        ..Default::default()
+    };
+    let image = render_svg("golden/solid_fill.svg", &settings);
+    assert_golden("origin_center", &image, &Default::default());
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn seam_overdraw_closes_cracks() {
+    // Two black squares share a slightly rotated edge at x = 100, over a white rectangle.
+    let settings = RenderSettings {
+        loader_settings: SvgSettings {
+            seam_overdraw: 0.5,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let image = render_svg("seams.svg", &settings);
+
+    let background = (15..85)
+        .flat_map(|y| (97..=103).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let [r, g, b, _] = image.get_pixel(x, y).0;
+            u32::from(r) + u32::from(g) + u32::from(b) > 3 * 127
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        background.is_empty(),
+        "the background shows through the seam at {background:?}"
+    );
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn alpha_to_coverage_is_independent_of_draw_order() {
+    // Two overlapping translucent squares, the red one in front.
+    let red = ("golden/translucent_red.svg", Vec3::Z);
+    let blue = ("golden/translucent_blue.svg", Vec3::ZERO);
+    let settings = RenderSettings {
+        blend_mode: Some(SvgBlendMode::AlphaToCoverage),
+        ..Default::default()
+    };
+    let red_first = render_svgs(&[red, blue], &settings);
+    let blue_first = render_svgs(&[blue, red], &settings);
+
+    let diff = compare(&red_first, &blue_first, Tolerance::default().threshold);
+    assert_eq!(
+        diff.mismatched_pixels, 0,
+        "the draw order changes the image"
+    );
+    // Half the samples of each square are covered, so the white background shows through.
+    let [r, g, b, _] = red_first.get_pixel(6, 6).0;
+    assert!(
+        r > 240 && (150..230).contains(&g) && (150..230).contains(&b),
+        "{:?}",
+        [r, g, b]
+    );
+}
@@ -0,0 +1,127 @@
+//! Recoloring gradients without tessellating again.
+use bevy::{
+    color::{ColorToComponents as _, Srgba},
+    diagnostic::DiagnosticsPlugin,
+    mesh::VertexAttributeValues,
+    prelude::*,
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn colors(mesh: &Mesh) -> Vec<[f32; 4]> {
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    colors.clone()
+}
+
+fn stop(offset: f32, color: Srgba) -> SvgGradientStop {
+    SvgGradientStop {
+        offset,
+        color: color.into(),
+    }
+}
+
+#[test]
+fn gradient_stops_recolor_only_their_path() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("gradient.svg");
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            break;
+        }
+        app.update();
+    }
+
+    let svg = app.world().resource::<Assets<Svg>>().get(&handle).unwrap();
+    let (mesh_handle, ranges) = (
+        svg.mesh.clone(),
+        svg.tessellate_with_ranges(&svg.tessellation_options).1,
+    );
+    let mesh = |app: &App| {
+        app.world()
+            .resource::<Assets<Mesh>>()
+            .get(&mesh_handle)
+            .unwrap()
+            .clone()
+    };
+    let before = mesh(&app);
+
+    let stops = [stop(0.0, Srgba::BLUE), stop(1.0, Srgba::WHITE)];
+    {
+        let mut svgs = app.world_mut().resource_mut::<Assets<Svg>>();
+        let svg = svgs.get_mut(&handle).unwrap();
+        assert!(svg.set_gradient_stops(0, &stops));
+        assert!(!svg.is_dirty());
+    }
+    app.update();
+
+    let svg = app.world().resource::<Assets<Svg>>().get(&handle).unwrap();
+    assert_eq!(svg.mesh, mesh_handle);
+    assert!(!svg.is_dirty());
+    assert_eq!(svg.paths[0].color, Color::srgb(0.5, 0.5, 1.0));
+    let after = mesh(&app);
+    let expected = Color::srgb(0.5, 0.5, 1.0).to_linear().to_f32_array();
+    let (colors_before, colors_after) = (colors(&before), colors(&after));
+    for vertex in ranges[0].start as usize..ranges[0].end as usize {
+        assert_eq!(colors_after[vertex][..3], expected[..3]);
+        assert_eq!(colors_after[vertex][3], colors_before[vertex][3]);
+    }
+    for vertex in ranges[1].start as usize..ranges[1].end as usize {
+        assert_eq!(colors_after[vertex], colors_before[vertex]);
+    }
+    assert_eq!(
+        after.attribute(Mesh::ATTRIBUTE_POSITION),
+        before.attribute(Mesh::ATTRIBUTE_POSITION)
+    );
+}
+
+#[test]
+fn gradient_stops_are_weighted_by_their_offsets() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="10">
+        <linearGradient id="hue">
+            <stop offset="0" stop-color="#ff0000"/>
+            <stop offset="1" stop-color="#00ff00"/>
+        </linearGradient>
+        <linearGradient id="clear">
+            <stop offset="0" stop-color="#ff0000" stop-opacity="0"/>
+            <stop offset="1" stop-color="#00ff00" stop-opacity="0"/>
+        </linearGradient>
+        <rect width="10" height="10" fill="url(#hue)"/>
+        <rect x="10" width="10" height="10" fill="#000000"/>
+        <rect x="20" width="10" height="10" fill="url(#clear)"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let mut svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    // Red over the first three quarters, then fading to blue.
+    let stops = [
+        stop(0.0, Srgba::RED),
+        stop(0.75, Srgba::RED),
+        stop(1.0, Srgba::BLUE),
+    ];
+    assert!(svg.set_gradient_stops(0, &stops));
+    let mean = svg.paths[0].color.to_srgba();
+    assert!((mean.red - 0.875).abs() < 1e-6 && (mean.blue - 0.125).abs() < 1e-6);
+    assert!(!svg.is_dirty());
+
+    // Paths that aren't painted with a gradient are left alone.
+    let black = svg.paths[1].color;
+    assert!(!svg.set_gradient_stops(1, &stops));
+    assert_eq!(svg.paths[1].color, black);
+    assert!(!svg.set_gradient_stops(3, &stops));
+
+    // A transparent path has no coverage to keep, so it is tessellated again.
+    assert!(svg.set_gradient_stops(2, &stops));
+    assert!(svg.is_dirty());
+}
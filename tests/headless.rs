@@ -0,0 +1,754 @@
+//! Drives a headless `App` frame by frame to check how SVG entities get and keep their meshes,
+//! without a GPU.
+use bevy::{asset::RenderAssetUsages, diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+fn load(app: &mut App, path: &'static str) -> Handle<Svg> {
+    let handle = app.world().resource::<AssetServer>().load(path);
+    wait_for(app, &handle);
+    handle
+}
+
+fn wait_for(app: &mut App, handle: &Handle<Svg>) {
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(handle) {
+            return;
+        }
+        app.update();
+    }
+    panic!("SVG {handle:?} didn't load");
+}
+
+fn svg_mesh(app: &App, handle: &Handle<Svg>) -> Handle<Mesh> {
+    app.world()
+        .resource::<Assets<Svg>>()
+        .get(handle)
+        .unwrap()
+        .mesh
+        .clone()
+}
+
+fn mesh_2d(app: &App, entity: Entity) -> Handle<Mesh> {
+    app.world().get::<Mesh2d>(entity).unwrap().0.clone()
+}
+
+fn translation(app: &App, entity: Entity) -> Vec3 {
+    app.world()
+        .get::<GlobalTransform>(entity)
+        .unwrap()
+        .translation()
+}
+
+#[test]
+fn entity_gets_mesh_when_asset_loads() {
+    let mut app = app();
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("box.svg");
+    let entity = app.world_mut().spawn(Svg2d(handle.clone())).id();
+
+    wait_for(&mut app, &handle);
+    app.update();
+
+    assert_eq!(mesh_2d(&app, entity), svg_mesh(&app, &handle));
+}
+
+#[test]
+fn late_entity_gets_mesh() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let entity = app.world_mut().spawn(Svg2d(handle.clone())).id();
+    app.update();
+
+    assert_eq!(mesh_2d(&app, entity), svg_mesh(&app, &handle));
+}
+
+#[test]
+fn entity_3d_gets_mesh() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+
+    let entity = app.world_mut().spawn(Svg3d(handle.clone())).id();
+    app.update();
+
+    let mesh = app.world().get::<Mesh3d>(entity).unwrap().0.clone();
+    assert_eq!(mesh, svg_mesh(&app, &handle));
+}
+
+#[test]
+fn swapped_handle_swaps_mesh() {
+    let mut app = app();
+    let first = load(&mut app, "box.svg");
+    let second = load(&mut app, "neutron_star.svg");
+    let entity = app.world_mut().spawn(Svg2d(first.clone())).id();
+    app.update();
+
+    app.world_mut().get_mut::<Svg2d>(entity).unwrap().0 = second.clone();
+    app.update();
+
+    assert_eq!(mesh_2d(&app, entity), svg_mesh(&app, &second));
+}
+
+#[test]
+fn origin_is_applied_once() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let entity = app.world_mut().spawn((Svg2d(handle), Origin::Center)).id();
+    app.update();
+
+    let expected = Vec3::new(-50.0, 50.0, 0.0);
+    assert_eq!(translation(&app, entity), expected);
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(translation(&app, entity), expected);
+    }
+
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(Origin::BottomRight);
+    app.update();
+    assert_eq!(translation(&app, entity), Vec3::new(-100.0, 100.0, 0.0));
+}
+
+#[test]
+fn modified_asset_regenerates_mesh() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    let entity = app.world_mut().spawn(Svg2d(handle.clone())).id();
+    app.update();
+
+    let svg = app
+        .world()
+        .resource::<Assets<Svg>>()
+        .get(&handle)
+        .unwrap()
+        .clone();
+    let mesh = app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .add(svg.tessellate());
+    app.world_mut()
+        .resource_mut::<Assets<Svg>>()
+        .get_mut(&handle)
+        .unwrap()
+        .mesh = mesh.clone();
+    app.update();
+
+    assert_eq!(mesh_2d(&app, entity), mesh);
+}
+
+#[test]
+fn removed_origin_drops_offset() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let entity = app.world_mut().spawn((Svg2d(handle), Origin::Center)).id();
+    app.update();
+    assert_ne!(translation(&app, entity), Vec3::ZERO);
+
+    app.world_mut().entity_mut(entity).remove::<Origin>();
+    app.update();
+
+    assert_eq!(translation(&app, entity), Vec3::ZERO);
+}
+
+#[test]
+fn origin_is_applied_when_the_asset_loads_later() {
+    let mut app = app();
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("corners.svg");
+    let entity = app
+        .world_mut()
+        .spawn((Svg2d(handle.clone()), Origin::Center))
+        .id();
+
+    wait_for(&mut app, &handle);
+    app.update();
+    assert_eq!(translation(&app, entity), Vec3::new(-50.0, 50.0, 0.0));
+
+    // A reload with a different size moves the offset along.
+    app.world_mut()
+        .resource_mut::<Assets<Svg>>()
+        .get_mut(&handle)
+        .unwrap()
+        .size = Vec2::splat(200.0);
+    app.update();
+    assert_eq!(translation(&app, entity), Vec3::new(-100.0, 100.0, 0.0));
+}
+
+#[test]
+fn rotation_is_around_an_off_center_pivot() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let rotation = Transform::from_rotation(Quat::from_rotation_z(core::f32::consts::FRAC_PI_2));
+    // The tessellated mesh has a flipped y-axis, so the pivot at (20, 30) is at (20, -30).
+    let pivot = Vec3::new(20.0, -30.0, 0.0);
+    for (origin, offset) in [
+        (Origin::TopLeft, Vec3::ZERO),
+        (Origin::Center, Vec3::new(-50.0, 50.0, 0.0)),
+    ] {
+        let entity = app
+            .world_mut()
+            .spawn((
+                Svg2d(handle.clone()),
+                origin,
+                SvgPivot(Vec2::new(20.0, 30.0)),
+                rotation,
+            ))
+            .id();
+        app.update();
+
+        let transform = *app.world().get::<GlobalTransform>(entity).unwrap();
+        // The pivot stays in place while the rest of the SVG turns around it.
+        let at_pivot = transform.transform_point(pivot);
+        assert!(at_pivot.distance(pivot + offset) < 1e-4, "{at_pivot}");
+        let corner = transform.transform_point(Vec3::ZERO);
+        assert!(
+            corner.distance(Vec3::new(-10.0, -50.0, 0.0) + offset) < 1e-4,
+            "{corner}"
+        );
+    }
+}
+
+#[test]
+fn pivot_and_tween_need_no_origin() {
+    let mut app = app();
+    app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+        core::time::Duration::from_millis(100),
+    ));
+    let handle = load(&mut app, "corners.svg");
+    let rotation = Transform::from_rotation(Quat::from_rotation_z(core::f32::consts::FRAC_PI_2));
+    let pivoted = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgPivot(Vec2::new(20.0, 30.0)),
+            rotation,
+        ))
+        .id();
+    let tweened = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle),
+            OriginTween::new(Origin::TopLeft, Origin::Center, 0.5),
+        ))
+        .id();
+    for _ in 0..8 {
+        app.update();
+    }
+
+    // Only the offset of the pivot is applied, see `rotation_is_around_an_off_center_pivot`.
+    let pivot = Vec3::new(20.0, -30.0, 0.0);
+    let transform = *app.world().get::<GlobalTransform>(pivoted).unwrap();
+    assert!(transform.transform_point(pivot).distance(pivot) < 1e-4);
+    assert!(app.world().get::<Origin>(pivoted).is_none());
+    // The finished tween leaves its target origin behind.
+    assert_eq!(translation(&app, tweened), Vec3::new(-50.0, 50.0, 0.0));
+    assert_eq!(app.world().get::<Origin>(tweened), Some(&Origin::Center));
+
+    app.world_mut().entity_mut(pivoted).remove::<SvgPivot>();
+    app.update();
+    assert_eq!(
+        *app.world().get::<GlobalTransform>(pivoted).unwrap(),
+        GlobalTransform::from(rotation)
+    );
+}
+
+#[test]
+fn manual_origin_keeps_the_global_transform() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let transform = Transform::from_xyz(10.0, 20.0, 0.0);
+    let manual = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            Origin::Center,
+            SvgManualOrigin,
+            transform,
+        ))
+        .id();
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(translation(&app, manual), transform.translation);
+    }
+
+    // Adding the marker later drops the offset again.
+    let entity = app
+        .world_mut()
+        .spawn((Svg2d(handle), Origin::Center, transform))
+        .id();
+    app.update();
+    assert_ne!(translation(&app, entity), transform.translation);
+    app.world_mut().entity_mut(entity).insert(SvgManualOrigin);
+    app.update();
+    assert_eq!(translation(&app, entity), transform.translation);
+}
+
+#[test]
+fn svg_added_to_a_sprite_is_inert() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let entity = app.world_mut().spawn(Sprite::default()).id();
+    app.update();
+
+    app.world_mut()
+        .entity_mut(entity)
+        .insert((Svg2d(handle), Origin::Center));
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(translation(&app, entity), Vec3::ZERO);
+        assert!(app.world().get::<MeshMaterial2d<Svg>>(entity).is_none());
+    }
+
+    app.world_mut().entity_mut(entity).remove::<Sprite>();
+    app.update();
+    app.update();
+    assert_eq!(translation(&app, entity), Vec3::new(-50.0, 50.0, 0.0));
+    assert!(app.world().get::<MeshMaterial2d<Svg>>(entity).is_some());
+}
+
+#[test]
+fn sprite_added_to_an_svg_makes_it_inert() {
+    let mut app = app();
+    let handle = load(&mut app, "corners.svg");
+    let entity = app.world_mut().spawn((Svg2d(handle), Origin::Center)).id();
+    app.update();
+    assert_eq!(translation(&app, entity), Vec3::new(-50.0, 50.0, 0.0));
+    assert!(app.world().get::<MeshMaterial2d<Svg>>(entity).is_some());
+
+    app.world_mut().entity_mut(entity).insert(Sprite::default());
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(translation(&app, entity), Vec3::ZERO);
+        assert!(app.world().get::<MeshMaterial2d<Svg>>(entity).is_none());
+    }
+}
+
+#[test]
+fn fill_overrides_recolor_the_fills_per_entity() {
+    let mut app = app();
+    // A red square and a blue line.
+    let handle = load(&mut app, "y_up.svg");
+    let mut spawn = |color| {
+        app.world_mut()
+            .spawn((Svg2d(handle.clone()), SvgFillOverride(color)))
+            .id()
+    };
+    let green = spawn(Color::srgb(0.0, 1.0, 0.0));
+    let also_green = spawn(Color::srgb(0.0, 1.0, 0.0));
+    let blue = spawn(Color::srgb(0.0, 0.0, 1.0));
+
+    let asset_mesh = svg_mesh(&app, &handle);
+    for _ in 0..1000 {
+        app.update();
+        if [green, also_green, blue]
+            .iter()
+            .all(|entity| mesh_2d(&app, *entity) != asset_mesh)
+        {
+            break;
+        }
+    }
+
+    let colors = |app: &App, entity| {
+        let meshes = app.world().resource::<Assets<Mesh>>();
+        let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) = meshes
+            .get(&mesh_2d(app, entity))
+            .unwrap()
+            .attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("mesh has no colors");
+        };
+        let mut colors = colors.clone();
+        colors.dedup();
+        colors
+    };
+    // Only the fill takes on the override color, the stroke stays blue.
+    assert_eq!(
+        colors(&app, green),
+        [[0.0, 1.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]]
+    );
+    assert_eq!(colors(&app, blue), [[0.0, 0.0, 1.0, 1.0]]);
+    assert_eq!(mesh_2d(&app, green), mesh_2d(&app, also_green));
+    // The material stays the asset's, no copy is added.
+    assert_eq!(app.world().resource::<Assets<Svg>>().len(), 1);
+    assert_eq!(
+        app.world().get::<MeshMaterial2d<Svg>>(green).unwrap().0,
+        handle
+    );
+
+    app.world_mut()
+        .entity_mut(green)
+        .remove::<SvgFillOverride>();
+    app.update();
+    assert_eq!(mesh_2d(&app, green), asset_mesh);
+}
+
+#[test]
+fn dash_override_adds_geometry_until_removed() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgStrokeStyleOverride {
+                dash_array: Some(vec![4.0, 4.0]),
+                ..Default::default()
+            },
+        ))
+        .id();
+    let original = svg_mesh(&app, &handle);
+    for _ in 0..100 {
+        app.update();
+        if mesh_2d(&app, entity) != original {
+            break;
+        }
+    }
+
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let triangles = |mesh: &Handle<Mesh>| meshes.get(mesh).unwrap().indices().unwrap().len() / 3;
+    let dashed = mesh_2d(&app, entity);
+    assert_ne!(dashed, original);
+    assert!(triangles(&dashed) > triangles(&original));
+
+    app.world_mut()
+        .entity_mut(entity)
+        .remove::<SvgStrokeStyleOverride>();
+    app.update();
+    assert_eq!(mesh_2d(&app, entity), original);
+}
+
+#[test]
+fn blend_modes_override_per_entity() {
+    let mut app = app();
+    let handle = load(&mut app, "overlapping_circles.svg");
+    let covered = app
+        .world_mut()
+        .spawn((Svg2d(handle.clone()), SvgBlendMode::AlphaToCoverage))
+        .id();
+    let blended = app.world_mut().spawn(Svg2d(handle.clone())).id();
+
+    let material = |app: &App, entity| {
+        app.world()
+            .get::<MeshMaterial2d<Svg>>(entity)
+            .unwrap()
+            .0
+            .clone()
+    };
+    for _ in 0..1000 {
+        if material(&app, covered) != handle {
+            break;
+        }
+        app.update();
+    }
+    let blend_mode = |app: &App, entity| {
+        app.world()
+            .resource::<Assets<Svg>>()
+            .get(&material(app, entity))
+            .unwrap()
+            .blend_mode
+    };
+    assert_eq!(blend_mode(&app, covered), SvgBlendMode::AlphaToCoverage);
+    assert_eq!(material(&app, blended), handle);
+    assert_eq!(blend_mode(&app, blended), SvgBlendMode::Blend);
+
+    app.world_mut().entity_mut(covered).remove::<SvgBlendMode>();
+    app.update();
+    assert_eq!(material(&app, covered), handle);
+}
+
+#[test]
+fn blend_mode_comes_from_settings() {
+    let mut app = app();
+    let blended = load(&mut app, "box.svg");
+    let handle = app.world().resource::<AssetServer>().load_with_settings(
+        "overlapping_circles.svg",
+        |settings: &mut SvgSettings| {
+            settings.blend_mode = SvgBlendMode::AlphaToCoverage;
+        },
+    );
+    wait_for(&mut app, &handle);
+
+    let svgs = app.world().resource::<Assets<Svg>>();
+    assert_eq!(svgs.get(&blended).unwrap().blend_mode, SvgBlendMode::Blend);
+    assert_eq!(
+        svgs.get(&handle).unwrap().blend_mode,
+        SvgBlendMode::AlphaToCoverage
+    );
+}
+
+#[test]
+fn dirty_svg_is_tessellated_again() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    let entity = app.world_mut().spawn(Svg2d(handle.clone())).id();
+    app.update();
+
+    let red = Color::srgb(1.0, 0.0, 0.0);
+    {
+        let mut svgs = app.world_mut().resource_mut::<Assets<Svg>>();
+        let svg = svgs.get_mut(&handle).unwrap();
+        for path in &mut svg.paths {
+            path.color = red;
+        }
+        svg.mark_dirty();
+    }
+    app.update();
+
+    let svgs = app.world().resource::<Assets<Svg>>();
+    assert!(!svgs.get(&handle).unwrap().is_dirty());
+    let mesh = mesh_2d(&app, entity);
+    assert_eq!(mesh, svg_mesh(&app, &handle));
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) =
+        meshes.get(&mesh).unwrap().attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    // The alpha of the anti-aliasing fringe fades out, the colors are all red.
+    let expected = red.to_linear().to_f32_array();
+    assert!(colors.iter().all(|color| color[..3] == expected[..3]));
+}
+
+#[test]
+fn origin_tween_moves_smoothly() {
+    let mut app = app();
+    app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+        core::time::Duration::from_millis(100),
+    ));
+    let handle = load(&mut app, "corners.svg");
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle),
+            Origin::TopLeft,
+            OriginTween::new(Origin::TopLeft, Origin::Center, 1.0),
+        ))
+        .id();
+
+    let center = Vec3::new(-50.0, 50.0, 0.0);
+    let mut previous = Vec3::ZERO;
+    let mut steps = Vec::new();
+    for _ in 0..12 {
+        app.update();
+        let current = translation(&app, entity);
+        steps.push(current.distance(previous));
+        previous = current;
+    }
+
+    assert_eq!(previous, center);
+    assert_eq!(app.world().get::<Origin>(entity), Some(&Origin::Center));
+    assert!(app.world().get::<OriginTween>(entity).is_none());
+    // No frame jumps more than a fraction of the way.
+    let total = center.length();
+    assert!(steps.iter().all(|step| *step < total * 0.2), "{steps:?}");
+}
+
+#[test]
+fn toggling_the_theme_updates_all_themed_svgs() {
+    let mut app = app();
+    let light = load(&mut app, "box.svg");
+    let dark = load(&mut app, "corners.svg");
+    app.insert_resource(CurrentTheme("light".to_owned()));
+    let theme = SvgTheme::default()
+        .with_variant("light", light.clone())
+        .with_variant("dark", dark.clone());
+    let entities_2d = [
+        app.world_mut()
+            .spawn((Svg2d(light.clone()), theme.clone()))
+            .id(),
+        app.world_mut()
+            .spawn((Svg2d(light.clone()), theme.clone()))
+            .id(),
+    ];
+    let entity_3d = app.world_mut().spawn((Svg3d(light.clone()), theme)).id();
+    app.update();
+
+    for (name, handle) in [("dark", &dark), ("light", &light), ("dark", &dark)] {
+        app.world_mut().resource_mut::<CurrentTheme>().0 = name.to_owned();
+        app.update();
+        for entity in entities_2d {
+            assert_eq!(&app.world().get::<Svg2d>(entity).unwrap().0, handle);
+            assert_eq!(mesh_2d(&app, entity), svg_mesh(&app, handle));
+        }
+        assert_eq!(&app.world().get::<Svg3d>(entity_3d).unwrap().0, handle);
+        assert_eq!(
+            app.world().get::<Mesh3d>(entity_3d).unwrap().0,
+            svg_mesh(&app, handle)
+        );
+    }
+}
+
+#[test]
+fn lit_meshes_get_normals_along_their_facing() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    let original = svg_mesh(&app, &handle);
+    for facing in [Facing::PositiveZ, Facing::NegativeZ] {
+        let entity = app
+            .world_mut()
+            .spawn((Svg3d(handle.clone()), SvgPbr::default(), SvgFacing(facing)))
+            .id();
+        let mesh_3d = |app: &App| app.world().get::<Mesh3d>(entity).unwrap().0.clone();
+        for _ in 0..100 {
+            app.update();
+            if mesh_3d(&app) != original {
+                break;
+            }
+        }
+
+        let meshes = app.world().resource::<Assets<Mesh>>();
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(normals)) = meshes
+            .get(&mesh_3d(&app))
+            .unwrap()
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh has no normals");
+        };
+        assert!(normals.iter().all(|normal| *normal == facing.normal()));
+    }
+}
+
+#[test]
+fn stroke_scale_keeps_the_stroke_width_while_scaling_the_geometry() {
+    let mut app = app();
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load_with_settings("box.svg", |settings: &mut SvgSettings| {
+            settings.edge_aa_width = 0.0
+        });
+    wait_for(&mut app, &handle);
+    // A 4 units wide stroke around the square from 10.015 to 89.986, scaled up tenfold.
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgStrokeScale(1.0),
+            Transform::from_scale(Vec3::splat(10.0)),
+        ))
+        .id();
+    let original = svg_mesh(&app, &handle);
+    for _ in 0..100 {
+        app.update();
+        if mesh_2d(&app, entity) != original {
+            break;
+        }
+    }
+
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) = meshes
+        .get(&mesh_2d(&app, entity))
+        .unwrap()
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    let (min_x, max_x) = positions
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), position| {
+            (min.min(position[0]), max.max(position[0]))
+        });
+    // The geometry keeps its place, only the stroke gets thinner in the mesh.
+    assert!(((min_x + max_x) / 2.0 - 50.0).abs() < 1e-2);
+    let world_width = (max_x - 89.986) * 2.0 * 10.0;
+    // Up to an eighth of an octave off, from the quantization of the stroke factor.
+    assert!(
+        world_width > 4.0 / 1.1 && world_width < 4.0 * 1.1,
+        "{world_width}"
+    );
+}
+
+#[test]
+fn shadow_is_drawn_offset_behind_the_svg() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    let color = Color::BLACK.with_alpha(0.5);
+    // Rotated a quarter turn, the offset still points to the bottom right in world space.
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgShadow {
+                offset: Vec2::new(4.0, -4.0),
+                blur: 0.0,
+                color,
+            },
+            Transform::from_xyz(10.0, 20.0, 5.0)
+                .with_rotation(Quat::from_rotation_z(core::f32::consts::FRAC_PI_2)),
+        ))
+        .id();
+    let shadow_mesh = |app: &App| {
+        let shadow = app.world().get::<SvgShadowEntity>(entity)?.entity;
+        let handle = &app.world().get::<Mesh2d>(shadow)?.0;
+        app.world().resource::<Assets<Mesh>>().get(handle).cloned()
+    };
+    for _ in 0..100 {
+        app.update();
+        if shadow_mesh(&app).is_some() {
+            break;
+        }
+    }
+    // Once more, so the transform of the shadow is propagated.
+    app.update();
+
+    let shadow = app.world().get::<SvgShadowEntity>(entity).unwrap().entity;
+    assert_eq!(app.world().get::<ChildOf>(shadow).unwrap().parent(), entity);
+    assert_ne!(mesh_2d(&app, shadow), svg_mesh(&app, &handle));
+    let mesh = shadow_mesh(&app).unwrap();
+    let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) =
+        mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    assert!(!colors.is_empty());
+    let linear = color.to_linear().to_f32_array();
+    assert!(colors.iter().all(|vertex| *vertex == linear));
+
+    let offset = translation(&app, shadow) - translation(&app, entity);
+    assert!(
+        offset.xy().abs_diff_eq(Vec2::new(4.0, -4.0), 1e-4),
+        "{offset}"
+    );
+    assert!(offset.z < 0.0, "{offset}");
+}
+
+#[test]
+fn meshes_stay_in_the_main_world() {
+    let mut app = app();
+    let handle = load(&mut app, "box.svg");
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let mesh = meshes.get(&svg_mesh(&app, &handle)).unwrap();
+    assert_eq!(mesh.asset_usage, RenderAssetUsages::default());
+    assert!(mesh.count_vertices() > 0);
+}
+
+#[test]
+#[should_panic(expected = "Add it after `DefaultPlugins`")]
+fn renderer_added_after_the_plugin_panics() {
+    let mut app = app();
+    // Stands in for the render world of a `RenderPlugin` added after `SvgPlugin`.
+    app.insert_sub_app(bevy::render::RenderApp, bevy::app::SubApp::new());
+    app.finish();
+}
@@ -0,0 +1,40 @@
+//! References written as `href` and as the `xlink:href` of SVG 1.1.
+use bevy::color::Color;
+use bevy_svg::{prelude::*, usvg};
+
+fn convert(text: &str) -> Svg {
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(&tree, &SvgConvertOptions::default())
+}
+
+fn document(href: &str) -> String {
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"
+            width="20" height="10">
+            <defs>
+                <linearGradient id="stops">
+                    <stop offset="0" stop-color="#ff0000"/>
+                    <stop offset="1" stop-color="#0000ff"/>
+                </linearGradient>
+                <linearGradient id="gradient" {href}="#stops" x2="0.5"/>
+                <rect id="cell" width="10" height="10" fill="url(#gradient)"/>
+            </defs>
+            <use {href}="#cell"/>
+            <use {href}="#cell" x="10"/>
+        </svg>"##
+    )
+}
+
+#[test]
+fn xlink_href_resolves_like_href() {
+    let xlink = convert(&document("xlink:href"));
+    let plain = convert(&document("href"));
+
+    assert_eq!(xlink.paths.len(), 2);
+    assert_eq!(plain.paths.len(), 2);
+    for (xlink, plain) in xlink.paths.iter().zip(&plain.paths) {
+        // The stops are inherited from the referenced gradient, drawn as their average.
+        assert_eq!(xlink.color, plain.color);
+        assert_eq!(xlink.color, Color::srgba(0.5, 0.0, 0.5, 1.0));
+    }
+}
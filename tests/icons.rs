@@ -0,0 +1,35 @@
+//! Icon variants picked by display size.
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn pick_prefers_smallest_sufficient_variant() {
+    let icons = SvgIconSet::default()
+        .with_variant("gear", 64.0, "icons/gear-64.svg")
+        .with_variant("gear", 16.0, "icons/gear-16.svg");
+    let path = |size| {
+        icons
+            .pick("gear", size)
+            .map(|variant| variant.path.as_str())
+    };
+    assert_eq!(path(12.0), Some("icons/gear-16.svg"));
+    assert_eq!(path(16.0), Some("icons/gear-16.svg"));
+    assert_eq!(path(32.0), Some("icons/gear-64.svg"));
+    assert_eq!(path(256.0), Some("icons/gear-64.svg"));
+    assert!(icons.pick("cog", 16.0).is_none());
+}
+
+#[test]
+fn small_display_size_tessellates_coarser() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24">
+        <circle cx="12" cy="12" r="10" fill="#000000"/>
+        <circle cx="12" cy="12" r="6" fill="none" stroke="#ffffff" stroke-width="2"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let mut svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    svg.fit_tolerance(16.0);
+    let small = svg.tessellate().count_vertices();
+    svg.fit_tolerance(1024.0);
+    let large = svg.tessellate().count_vertices();
+    assert!(small < large, "{small} vs {large}");
+}
@@ -0,0 +1,54 @@
+//! Tessellating edited documents incrementally.
+use bevy::mesh::{Mesh, VertexAttributeValues};
+use bevy_svg::{prelude::*, usvg};
+
+fn svg(middle: &str) -> Svg {
+    let text = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="10">
+            <rect width="10" height="10" fill="#ff0000"/>
+            <circle cx="15" cy="5" r="4" fill="{middle}" stroke="#000000"/>
+            <rect x="20" width="10" height="10" fill="#0000ff"/>
+        </svg>"##
+    );
+    let tree = usvg::Tree::from_str(&text, &usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(&tree, &SvgConvertOptions::default())
+}
+
+fn assert_same_mesh(a: &Mesh, b: &Mesh) {
+    assert_eq!(a.count_vertices(), b.count_vertices());
+    for (attribute, values) in a.attributes() {
+        let other = b
+            .attribute(attribute.id)
+            .map(VertexAttributeValues::get_bytes);
+        assert_eq!(other, Some(values.get_bytes()), "{}", attribute.name);
+    }
+    let indices = |mesh: &Mesh| {
+        mesh.indices()
+            .map(|indices| indices.iter().collect::<Vec<_>>())
+    };
+    assert_eq!(indices(a), indices(b));
+}
+
+#[test]
+fn only_changed_paths_are_tessellated() {
+    let (_, _, previous) = svg("#00ff00").tessellate_incremental(None);
+    assert_eq!(previous.reused_count(), 0);
+
+    let edited = svg("#ffff00");
+    let (mesh, ranges, paths) = edited.tessellate_incremental(Some(&previous));
+    // The fill of the circle changed, its stroke and the rectangles didn't.
+    assert_eq!(paths.reused_count(), edited.paths.len() - 1);
+    let (expected_mesh, expected_ranges) =
+        edited.tessellate_with_ranges(&edited.tessellation_options);
+    assert_same_mesh(&mesh, &expected_mesh);
+    assert_eq!(ranges, expected_ranges);
+}
+
+#[test]
+fn other_options_tessellate_everything() {
+    let (_, _, previous) = svg("#00ff00").tessellate_incremental(None);
+    let mut edited = svg("#00ff00");
+    edited.tessellation_options.edge_aa_width = 2.0;
+    let (_, _, paths) = edited.tessellate_incremental(Some(&previous));
+    assert_eq!(paths.reused_count(), 0);
+}
@@ -0,0 +1,55 @@
+//! Scanning documents for the features they use.
+use bevy_svg::prelude::*;
+
+/// A snippet of a document and the feature it uses.
+type Case = (&'static str, fn(&SvgFeatures) -> bool);
+
+fn inspect(content: &str) -> SvgFeatures {
+    let text = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">{content}</svg>"#
+    );
+    Svg::inspect(text.as_bytes()).unwrap()
+}
+
+#[test]
+fn detects_each_feature() {
+    let cases: [Case; 9] = [
+        (r#"<linearGradient id="g"/>"#, |f| f.gradients),
+        (r#"<radialGradient id="g"/>"#, |f| f.gradients),
+        (r#"<pattern id="p"/>"#, |f| f.patterns),
+        (r#"<filter id="f"/>"#, |f| f.filters),
+        (r#"<mask id="m"/>"#, |f| f.masks),
+        (r#"<clipPath id="c"/>"#, |f| f.clips),
+        ("<text>Hi</text>", |f| f.text),
+        (r#"<image href="a.png"/>"#, |f| f.images),
+        (r#"<rect><animate attributeName="x" to="5"/></rect>"#, |f| {
+            f.animations
+        }),
+    ];
+    for (content, used) in cases {
+        let features = inspect(content);
+        assert!(used(&features), "{content} wasn't detected");
+        assert_ne!(features, SvgFeatures::default());
+    }
+    assert_eq!(
+        inspect(r#"<rect width="5" height="5"/>"#),
+        SvgFeatures::default()
+    );
+}
+
+#[test]
+fn reports_missing_capabilities() {
+    let features = Svg::inspect(include_bytes!("../assets/fallbacks.svg")).unwrap();
+    assert_eq!(
+        features.missing_capabilities(),
+        vec![
+            SvgCapability::Gradient,
+            SvgCapability::Pattern,
+            SvgCapability::Image,
+            SvgCapability::ClipPath,
+            SvgCapability::Mask,
+            SvgCapability::Filter,
+        ]
+    );
+    assert!(Svg::inspect(b"<svg").is_none());
+}
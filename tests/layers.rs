@@ -0,0 +1,33 @@
+//! Layers of authoring tools, which `usvg` drops.
+use std::sync::Arc;
+
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn inkscape_layers_are_enumerated_with_their_paths() {
+    let svg = Svg::from_bytes_with_fontdb(
+        include_bytes!("../assets/inkscape_layers.svg"),
+        "inkscape_layers.svg",
+        Arc::new(usvg::fontdb::Database::new()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        svg.layers(),
+        [
+            SvgLayerInfo {
+                label: "Background".to_owned(),
+                id: "layer1".to_owned(),
+                path_indices: 0..1,
+            },
+            SvgLayerInfo {
+                label: "Details".to_owned(),
+                id: "layer2".to_owned(),
+                path_indices: 1..3,
+            },
+        ]
+    );
+    // Nodes are found by their label as well as their id.
+    assert_eq!(svg.node("Details").unwrap().paths, 1..3);
+    assert_eq!(svg.node("layer1").unwrap().paths, 0..1);
+}
@@ -0,0 +1,77 @@
+//! Mesh and texture limits the loader checks SVGs against.
+use bevy::{asset::LoadState, diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+/// Loads `path` with the limits set up by `settings`, failing the load when one is exceeded,
+/// and returns whether it loaded.
+fn loads(path: &'static str, settings: fn(&mut SvgSettings)) -> bool {
+    let mut app = app();
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load_with_settings(
+        path,
+        move |svg_settings: &mut SvgSettings| {
+            svg_settings.error_on_exceeded_limits = true;
+            settings(svg_settings);
+        },
+    );
+    for _ in 0..1000 {
+        match app.world().resource::<AssetServer>().load_state(&handle) {
+            LoadState::Loaded => return true,
+            LoadState::Failed(_) => return false,
+            _ => app.update(),
+        }
+    }
+    panic!("{path} neither loaded nor failed");
+}
+
+#[test]
+fn texture_limit_applies_to_image_pixels() {
+    // 4096 pixels wide, but drawn 16 units large.
+    assert!(!loads("limits/large_image.svg", |_| {}));
+    assert!(loads("limits/large_image.svg", |settings| {
+        settings.max_texture_size = 4096;
+    }));
+    // 16 pixels wide, but drawn 5000 units large.
+    assert!(loads("limits/small_image_drawn_large.svg", |settings| {
+        settings.max_texture_size = 16;
+    }));
+}
+
+#[test]
+fn vertex_limit_fails_the_load() {
+    assert!(loads("box.svg", |_| {}));
+    assert!(!loads("box.svg", |settings| settings.max_vertex_count = 3));
+}
+
+#[test]
+fn exceeded_limits_only_warn_by_default() {
+    let mut app = app();
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load_with_settings("limits/large_image.svg", |settings: &mut SvgSettings| {
+            settings.max_vertex_count = 3
+        });
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            return;
+        }
+        app.update();
+    }
+    panic!("SVG didn't load");
+}
@@ -0,0 +1,136 @@
+//! Loads hundreds of files concurrently through the `SvgLoadCache`.
+use std::path::{Path, PathBuf};
+
+use bevy::{diagnostic::DiagnosticsPlugin, mesh::VertexAttributeValues, prelude::*};
+use bevy_svg::prelude::*;
+
+const FIXTURES: [&str; 6] = [
+    "box.svg",
+    "overlapping_circles.svg",
+    "neutron_star.svg",
+    "seams.svg",
+    "stroke_caps.svg",
+    "panel.svg",
+];
+const COPIES: usize = 50;
+
+fn app(dir: &Path) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: dir.to_string_lossy().into_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+fn load(app: &App, path: String, deduplicate: bool) -> Handle<Svg> {
+    app.world().resource::<AssetServer>().load_with_settings(
+        path,
+        move |settings: &mut SvgSettings| {
+            settings.deduplicate = deduplicate;
+        },
+    )
+}
+
+fn wait_for(app: &mut App, handles: &[Handle<Svg>]) {
+    for _ in 0..10_000 {
+        let server = app.world().resource::<AssetServer>();
+        assert!(
+            !handles
+                .iter()
+                .any(|handle| server.load_state(handle).is_failed()),
+            "a load failed"
+        );
+        if handles
+            .iter()
+            .all(|handle| server.load_state(handle).is_loaded())
+        {
+            return;
+        }
+        app.update();
+    }
+    panic!("loads didn't finish");
+}
+
+/// The number of segments, vertex positions and indices of a loaded SVG.
+fn geometry(app: &App, handle: &Handle<Svg>) -> (usize, Vec<[f32; 3]>, Vec<usize>) {
+    let svg = app.world().resource::<Assets<Svg>>().get(handle).unwrap();
+    let mesh = app
+        .world()
+        .resource::<Assets<Mesh>>()
+        .get(&svg.mesh)
+        .unwrap();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    let indices = mesh
+        .indices()
+        .map_or_else(Vec::new, |indices| indices.iter().collect());
+    (svg.segments.len(), positions.clone(), indices)
+}
+
+/// Copies each fixture `COPIES` times into a new directory, so the copies share their content
+/// and directory, and with it their cache entry.
+fn copy_fixtures() -> PathBuf {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    let dir = std::env::temp_dir().join(format!("bevy_svg_load_cache_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for fixture in FIXTURES {
+        for copy in 0..COPIES {
+            std::fs::copy(assets.join(fixture), dir.join(format!("{copy}_{fixture}"))).unwrap();
+        }
+    }
+    dir
+}
+
+#[test]
+fn concurrent_loads_match_sequential_loads() {
+    let dir = copy_fixtures();
+
+    let mut concurrent = app(&dir);
+    let handles: Vec<(usize, Handle<Svg>)> = FIXTURES
+        .iter()
+        .enumerate()
+        .flat_map(|(index, fixture)| {
+            (0..COPIES).map(move |copy| (index, format!("{copy}_{fixture}")))
+        })
+        .map(|(index, path)| (index, load(&concurrent, path, true)))
+        .collect();
+    let all: Vec<Handle<Svg>> = handles.iter().map(|(_, handle)| handle.clone()).collect();
+    wait_for(&mut concurrent, &all);
+    assert_eq!(
+        concurrent.world().resource::<SvgLoadCache>().len(),
+        FIXTURES.len(),
+        "copies of the same file got separate cache entries"
+    );
+
+    // Each fixture on its own, without the cache.
+    let mut sequential = app(&dir);
+    let expected: Vec<_> = FIXTURES
+        .iter()
+        .map(|fixture| {
+            let handle = load(&sequential, format!("0_{fixture}"), false);
+            wait_for(&mut sequential, core::slice::from_ref(&handle));
+            geometry(&sequential, &handle)
+        })
+        .collect();
+    assert!(sequential.world().resource::<SvgLoadCache>().is_empty());
+
+    for (index, handle) in &handles {
+        assert!(
+            geometry(&concurrent, handle) == expected[*index],
+            "a copy of {} differs from loading it on its own",
+            FIXTURES[*index]
+        );
+    }
+    let _ = std::fs::remove_dir_all(dir);
+}
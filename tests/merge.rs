@@ -0,0 +1,95 @@
+//! Merging of overlapping fills of the same color.
+use bevy::{
+    color::Color,
+    math::Vec2,
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn convert(text: &str, merge_fills: bool) -> Svg {
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(
+        &tree,
+        &SvgConvertOptions {
+            merge_fills,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn overlapping_same_color_fills_merge() {
+    let text = include_str!("../assets/overlapping_circles.svg");
+    assert_eq!(convert(text, false).paths.len(), 2);
+
+    let merged = convert(text, true);
+    assert_eq!(merged.paths.len(), 1);
+    // The union is one outline without holes.
+    let outlines = merged.flattened_outline(0, 0.01);
+    assert_eq!(outlines.len(), 1);
+    let bounds = merged.content_bounds().unwrap();
+    assert!((bounds.min.x - 10.0).abs() < 0.1 && (bounds.max.x - 90.0).abs() < 0.1);
+}
+
+#[test]
+fn fills_of_other_colors_stay_apart() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="60">
+        <circle cx="35" cy="30" r="25" fill="#2060c0"/>
+        <circle cx="50" cy="30" r="10" fill="#ff0000"/>
+        <circle cx="65" cy="30" r="25" fill="#2060c0"/>
+    </svg>"##;
+    assert_eq!(convert(text, true).paths.len(), 3);
+}
+
+#[test]
+fn flattened_fills_keep_their_holes() {
+    // Rings with a hole by the even-odd rule and by opposite windings, a plain square, and a
+    // stroke that crosses the square and runs below the fills.
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="120">
+        <path d="M0 0 H100 V100 H0 Z M25 25 H75 V75 H25 Z" fill-rule="evenodd" fill="#ff0000"/>
+        <path d="M100 0 H200 V100 H100 Z M125 25 V75 H175 V25 Z" fill="#00ff00"/>
+        <rect x="200" y="0" width="100" height="100" fill="#0000ff"/>
+        <path d="M250 50 V110 H10" fill="none" stroke="#000000" stroke-width="4"/>
+    </svg>"##;
+    let color = Color::srgba(0.2, 0.2, 0.2, 0.5);
+    let mut flattened = convert(text, false).flatten_to_color(color);
+    assert_eq!(flattened.paths.len(), 1);
+    assert_eq!(flattened.paths[0].color, color);
+
+    flattened.tessellation_options.edge_aa_width = 0.0;
+    let mesh = flattened.tessellate();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    let positions: Vec<Vec2> = positions
+        .iter()
+        .map(|position| Vec2::new(position[0], position[1]))
+        .collect();
+    let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+    // How often `point` is drawn, a translucent color would blend that many times.
+    let coverage = |point: Vec2| {
+        indices
+            .chunks_exact(3)
+            .filter(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|corner| positions[triangle[corner]]);
+                let sides =
+                    [(a, b), (b, c), (c, a)].map(|(from, to)| (to - from).perp_dot(point - from));
+                sides.iter().all(|side| *side > 0.0) || sides.iter().all(|side| *side < 0.0)
+            })
+            .count()
+    };
+    let covered = |point: Vec2| coverage(point) > 0;
+    // The mesh has a flipped y-axis.
+    assert!(covered(Vec2::new(10.0, -50.0)));
+    assert!(!covered(Vec2::new(50.0, -50.0)));
+    assert!(covered(Vec2::new(110.0, -50.0)));
+    assert!(!covered(Vec2::new(150.0, -50.0)));
+    assert!(covered(Vec2::new(250.0, -50.0)));
+    assert!(covered(Vec2::new(100.0, -110.0)));
+    assert!(!covered(Vec2::new(100.0, -115.0)));
+    // The stroke overlaps the square, the paths are still drawn once.
+    assert_eq!(coverage(Vec2::new(250.3, -80.7)), 1);
+    assert_eq!(coverage(Vec2::new(150.3, -110.7)), 1);
+}
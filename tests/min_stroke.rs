@@ -0,0 +1,106 @@
+//! Strokes kept a minimum number of pixels wide while the tracked camera zooms out.
+use bevy::{
+    camera::Viewport, diagnostic::DiagnosticsPlugin, mesh::VertexAttributeValues, prelude::*,
+};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+/// Sets the camera to show `world_per_pixel` world units in each of its 100 pixels.
+fn zoom(app: &mut App, camera: Entity, world_per_pixel: f32) {
+    let half = 50.0 * world_per_pixel;
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(-half, -half, half, half),
+            ..OrthographicProjection::default_2d()
+        }));
+}
+
+/// Size of the bounds of the blue vertices of the mesh of `entity`, the stroke of `y_up.svg`,
+/// once it has a mesh.
+fn stroke_extent(app: &App, entity: Entity) -> Option<Vec2> {
+    let mesh = &app.world().get::<Mesh2d>(entity)?.0;
+    let mesh = app.world().resource::<Assets<Mesh>>().get(mesh)?;
+    let (
+        Some(VertexAttributeValues::Float32x3(positions)),
+        Some(VertexAttributeValues::Float32x4(colors)),
+    ) = (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+        mesh.attribute(Mesh::ATTRIBUTE_COLOR),
+    )
+    else {
+        panic!("mesh has no positions or colors");
+    };
+    let bounds = positions
+        .iter()
+        .zip(colors)
+        .filter(|(_, color)| color[2] > 0.5 && color[0] < 0.5)
+        .fold(Rect::EMPTY, |bounds, (position, _)| {
+            bounds.union_point(Vec2::new(position[0], position[1]))
+        });
+    Some(bounds.size())
+}
+
+/// Runs the app until the stroke of `entity` is wider than `width`, or not.
+fn settle(app: &mut App, entity: Entity, width: f32, wider: bool) -> Vec2 {
+    for _ in 0..1000 {
+        app.update();
+        if let Some(extent) = stroke_extent(app, entity) {
+            if (extent.x > width) == wider {
+                return extent;
+            }
+        }
+    }
+    panic!("the stroke of {entity} didn't settle");
+}
+
+#[test]
+fn zooming_out_widens_thin_strokes() {
+    let mut app = app();
+    let handle = app.world().resource::<AssetServer>().load("y_up.svg");
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera2d,
+            Camera {
+                viewport: Some(Viewport {
+                    physical_size: UVec2::splat(100),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ))
+        .id();
+    zoom(&mut app, camera, 0.1);
+    let entity = app
+        .world_mut()
+        .spawn((Svg2d(handle), SvgMinStrokePixels(4.0)))
+        .id();
+
+    // The line runs 80 units to the right and is 2 units wide, 4 pixels are 0.4 units.
+    let authored = settle(&mut app, entity, 0.0, true);
+    assert!(authored.x < 82.0, "{authored}");
+
+    // 4 pixels are now 40 units, so the stroke becomes at least that wide.
+    zoom(&mut app, camera, 10.0);
+    let widened = settle(&mut app, entity, 90.0, true);
+    assert!(widened.y > authored.y + 30.0, "{widened} {authored}");
+
+    zoom(&mut app, camera, 0.1);
+    assert_eq!(settle(&mut app, entity, 90.0, false), authored);
+}
@@ -0,0 +1,63 @@
+//! Nine-slice scaling of a rounded-rect panel.
+use bevy::{
+    math::{Rect, Vec2},
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn positions(mesh: &Mesh) -> Vec<Vec2> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    positions.iter().map(|p| Vec2::new(p[0], p[1])).collect()
+}
+
+#[test]
+fn corners_keep_their_shape() {
+    let tree = usvg::Tree::from_str(
+        include_str!("../assets/panel.svg"),
+        &usvg::Options::default(),
+    )
+    .unwrap();
+    let mut svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    // Without the anti-aliasing fringe, which reaches beyond the size.
+    svg.tessellation_options.edge_aa_width = 0.0;
+    let mesh = svg.tessellate();
+    let nine_slice = SvgNineSlice {
+        border: Rect {
+            min: Vec2::splat(16.0),
+            max: Vec2::splat(16.0),
+        },
+        size: Vec2::new(300.0, 150.0),
+    };
+    let mut sliced = mesh.clone();
+    nine_slice.apply(&svg, &mut sliced);
+
+    let (before, after) = (positions(&mesh), positions(&sliced));
+    let offset = Vec2::new(200.0, -90.0);
+    for (before, after) in before.iter().zip(&after) {
+        let (left, top) = (before.x <= 16.0, before.y >= -16.0);
+        let (right, bottom) = (before.x >= 84.0, before.y <= -44.0);
+        // Each corner moves as a whole, so its rounding isn't distorted.
+        let expected = match (left, right, top, bottom) {
+            (true, _, true, _) => Some(*before),
+            (_, true, true, _) => Some(*before + Vec2::new(offset.x, 0.0)),
+            (true, _, _, true) => Some(*before + Vec2::new(0.0, offset.y)),
+            (_, true, _, true) => Some(*before + offset),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            assert!(
+                after.abs_diff_eq(expected, 1e-3),
+                "{before} went to {after}"
+            );
+        }
+    }
+
+    let (min, max) = after.iter().fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+        (min.min(*p), max.max(*p))
+    });
+    assert!((max - min - Vec2::new(300.0, 150.0)).abs().max_element() < 1.0);
+}
@@ -0,0 +1,50 @@
+//! Opacity of the root element and of groups.
+use bevy::{
+    color::Alpha as _,
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn svg(text: &str) -> Svg {
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(&tree, &SvgConvertOptions::default())
+}
+
+#[test]
+fn root_opacity_fades_the_document() {
+    let svg = svg(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" opacity="0.5">
+        <rect width="10" height="10" fill="#ff0000"/>
+        <rect x="10" width="10" height="10" fill="#0000ff" stroke="#000000"/>
+    </svg>"##,
+    );
+    for path in &svg.paths {
+        assert!((path.color.alpha() - 0.5).abs() < 0.01);
+    }
+    // The anti-aliasing fringe fades out to transparent.
+    let mesh = svg.tessellate_with_options(&TessellationOptions {
+        edge_aa_width: 0.0,
+        ..svg.tessellation_options
+    });
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    for color in colors {
+        assert!((color[3] - 0.5).abs() < 0.01);
+    }
+}
+
+#[test]
+fn group_opacity_multiplies() {
+    let svg = svg(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" opacity="0.5">
+        <g opacity="0.5">
+            <rect width="10" height="10" fill="#ff0000" fill-opacity="0.5"/>
+        </g>
+        <rect x="10" width="10" height="10" fill="#0000ff"/>
+    </svg>"##,
+    );
+    assert!((svg.paths[0].color.alpha() - 0.125).abs() < 0.01);
+    assert!((svg.paths[1].color.alpha() - 0.5).abs() < 0.01);
+}
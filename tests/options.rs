@@ -0,0 +1,44 @@
+//! Default tessellation options configured on the plugin.
+use bevy::{diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+/// Loads `overlapping_circles.svg` with `plugin` and returns the vertex count of its mesh.
+fn vertex_count(plugin: SvgPlugin) -> usize {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(plugin);
+    let handle: Handle<Svg> = app
+        .world()
+        .resource::<AssetServer>()
+        .load("overlapping_circles.svg");
+    for _ in 0..1000 {
+        let world = app.world();
+        if let Some(svg) = world.resource::<Assets<Svg>>().get(&handle) {
+            if let Some(mesh) = world.resource::<Assets<Mesh>>().get(&svg.mesh) {
+                return mesh.count_vertices();
+            }
+        }
+        app.update();
+    }
+    panic!("SVG didn't load");
+}
+
+#[test]
+fn custom_default_tolerance_applies_to_loaded_svgs() {
+    let default = vertex_count(SvgPlugin::default());
+    let coarse = vertex_count(SvgPlugin::with_options(
+        FillOptions::tolerance(1.0),
+        StrokeOptions::tolerance(1.0),
+    ));
+    assert!(coarse > 0);
+    assert!(coarse < default, "{coarse} >= {default}");
+}
@@ -0,0 +1,111 @@
+//! Outlines of paths in document space: their segments, simplified polylines and JSON export.
+use bevy::{
+    math::Vec2,
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+use lyon_path::{math::Point, PathEvent};
+
+fn distance_to_polyline(point: Vec2, polyline: &[Vec2]) -> f32 {
+    polyline
+        .windows(2)
+        .map(|segment| {
+            let (a, b) = (segment[0], segment[1]);
+            let t = ((point - a).dot(b - a) / (b - a).length_squared()).clamp(0.0, 1.0);
+            point.distance(a + (b - a) * t)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[test]
+fn simplification_stays_within_epsilon() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <circle cx="50" cy="50" r="40" fill="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    let epsilon = 0.5;
+    let flattened = &svg.flattened_outline(0, epsilon * 0.1)[0];
+    let simplified = &svg.simplified_outline(0, epsilon)[0];
+    assert!(simplified.len() < flattened.len() / 2);
+    assert_eq!(simplified.first(), simplified.last());
+    for point in flattened {
+        let point = Vec2::new(point.x, point.y);
+        assert!(distance_to_polyline(point, simplified) <= epsilon + 1e-4);
+    }
+}
+
+#[test]
+fn transformed_segments_are_in_document_space() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <g transform="translate(10 20) scale(2)">
+            <path d="M0 0 H5 V5 Z" fill="#000000"/>
+        </g>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    // The segments are stored untransformed.
+    assert_eq!(
+        svg.path_segments(&svg.paths[0])[1],
+        PathEvent::Line {
+            from: Point::new(0.0, 0.0),
+            to: Point::new(5.0, 0.0)
+        }
+    );
+    // Mapped through the group, with the y-axis pointing down like the document's.
+    let points: Vec<Point> = svg
+        .transformed_segments(0)
+        .filter_map(|event| match event {
+            PathEvent::Begin { at } => Some(at),
+            PathEvent::Line { to, .. } => Some(to),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        points,
+        [
+            Point::new(10.0, 20.0),
+            Point::new(20.0, 20.0),
+            Point::new(20.0, 30.0)
+        ]
+    );
+    assert!(matches!(
+        svg.transformed_segments(0).last(),
+        Some(PathEvent::End { close: true, .. })
+    ));
+    assert_eq!(svg.transformed_segments(1).count(), 0);
+
+    // The mesh has a flipped y-axis instead.
+    let mesh = svg.tessellate();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    assert!(positions
+        .iter()
+        .all(|position| (-30.0..=-20.0).contains(&position[1])));
+}
+
+#[test]
+fn outlines_export_as_json() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+        <rect x="5" y="5" width="10" height="10" fill="#ff0000" stroke="#0000ff"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    // Whole numbers are written without a fraction.
+    let json: serde_json::Value = serde_json::from_str(&svg.outlines_to_json()).unwrap();
+    let square = serde_json::json!([[[5, 5], [15, 5], [15, 15], [5, 15], [5, 5]]]);
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {"index": 0, "kind": "fill", "color": [1, 0, 0, 1], "polylines": square},
+            {"index": 1, "kind": "stroke", "color": [0, 0, 1, 1], "polylines": square},
+        ])
+    );
+    assert_eq!(Svg::default().outlines_to_json(), "[]");
+}
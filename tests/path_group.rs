@@ -0,0 +1,28 @@
+//! Descriptors created from the same source path.
+use bevy::color::Color;
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn fill_and_stroke_of_a_path_are_grouped() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="10">
+        <rect width="10" height="10" fill="#ff0000" stroke="#0000ff"/>
+        <rect x="10" width="10" height="10" fill="#00ff00"/>
+        <polyline points="20,0 30,10" fill="none" stroke="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    let sources: Vec<usize> = svg
+        .paths
+        .iter()
+        .map(|path| path.source_path_index)
+        .collect();
+    assert_eq!(sources, [0, 0, 1, 2]);
+    // The fill comes first, then the stroke.
+    assert_eq!(svg.paths[0].color, Color::srgb(1.0, 0.0, 0.0));
+    assert_eq!(svg.paths[1].color, Color::srgb(0.0, 0.0, 1.0));
+    assert_eq!(svg.path_group(0), [0, 1]);
+    assert_eq!(svg.path_group(1), [2]);
+    assert_eq!(svg.path_group(2), [3]);
+    assert!(svg.path_group(3).is_empty());
+}
@@ -0,0 +1,37 @@
+//! Lit 3D SVGs with a `SvgPbr` material. Needs a GPU.
+use bevy_svg::{
+    prelude::SvgPbr,
+    testing::{render_svg, RenderSettings},
+};
+use image::Rgba;
+
+/// Renders the gold logo and returns the pixel at its center.
+fn logo_center(pbr: Option<SvgPbr>) -> Rgba<u8> {
+    let settings = RenderSettings {
+        pbr,
+        ..Default::default()
+    };
+    *render_svg("golden/logo.svg", &settings).get_pixel(16, 16)
+}
+
+#[test]
+#[ignore = "needs a GPU"]
+fn metallic_logo_is_lit() {
+    let flat = logo_center(None);
+
+    let rough = logo_center(Some(SvgPbr {
+        metallic: 0.0,
+        perceptual_roughness: 1.0,
+        ..Default::default()
+    }));
+    let metallic = logo_center(Some(SvgPbr {
+        metallic: 1.0,
+        perceptual_roughness: 0.3,
+        ..Default::default()
+    }));
+    // The light shades the logo instead of it keeping the flat color of the document, and
+    // metals reflect it differently than rough plastic.
+    assert_ne!(rough, flat);
+    assert_ne!(metallic, flat);
+    assert_ne!(metallic, rough);
+}
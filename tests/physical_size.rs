@@ -0,0 +1,51 @@
+//! Sizes of documents given in physical units.
+use std::sync::Arc;
+
+use bevy::math::Vec2;
+use bevy_svg::{prelude::*, usvg};
+
+fn load(width: &str, height: &str) -> Svg {
+    let text = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 10 10"/>"#
+    );
+    Svg::from_bytes_with_fontdb(
+        text.as_bytes(),
+        "physical.svg",
+        Arc::new(usvg::fontdb::Database::new()),
+    )
+    .unwrap()
+}
+
+fn assert_vec2_eq(actual: Vec2, expected: Vec2) {
+    assert!(actual.abs_diff_eq(expected, 1e-3), "{actual} != {expected}");
+}
+
+#[test]
+fn physical_units_are_converted_at_96_dpi() {
+    for (width, height) in [("25.4mm", "50.8mm"), ("1in", "2in"), ("2.54cm", "5.08cm")] {
+        let svg = load(width, height);
+        // One inch is 96 pixels.
+        assert_vec2_eq(svg.size, Vec2::new(96.0, 192.0));
+        assert_vec2_eq(svg.physical_size().unwrap(), Vec2::new(25.4, 50.8));
+    }
+    // Points are 1/72 inch.
+    assert_vec2_eq(
+        load("72pt", "36pt").physical_size().unwrap(),
+        Vec2::new(25.4, 12.7),
+    );
+}
+
+#[test]
+fn pixel_sizes_have_no_physical_size() {
+    for (width, height) in [("96", "192"), ("96px", "192px")] {
+        let svg = load(width, height);
+        assert_eq!(svg.size, Vec2::new(96.0, 192.0));
+        assert_eq!(svg.physical_size(), None);
+    }
+
+    let text = r#"<svg xmlns="http://www.w3.org/2000/svg" width="25.4mm" height="50.8mm"/>"#;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    assert_vec2_eq(svg.size, Vec2::new(96.0, 192.0));
+    assert_eq!(svg.physical_size(), None);
+}
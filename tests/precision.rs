@@ -0,0 +1,73 @@
+//! SVGs with tiny view boxes drawn large.
+use bevy::{
+    math::Vec2,
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn tiny_view_box_tessellates_cleanly() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="1000" height="1000" viewBox="0 0 1 1">
+        <circle cx="0.5" cy="0.5" r="0.5" fill="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let mut svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+    assert_eq!(svg.view_box_scale(), 1000.0);
+    assert!(svg.view_box_scale() > PRECISION_WARNING_SCALE);
+
+    let coarse = svg.tessellate().count_vertices();
+    svg.tessellation_options.scale_tolerance = true;
+    assert!(svg.tessellation_diagnostics().is_empty());
+    let mesh = svg.tessellate();
+    assert!(mesh.count_vertices() > 10 * coarse);
+
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    let center = Vec2::new(500.0, -500.0);
+    let radii = positions
+        .iter()
+        .map(|position| Vec2::new(position[0], position[1]).distance(center));
+    let outermost = radii.fold(0.0, f32::max);
+    // `usvg` draws circles with four cubic curves, which bulge out by 0.027% of the radius.
+    assert!((outermost - 500.0).abs() < 500.0 * 0.0003, "{outermost}");
+}
+
+/// Maximum distance of the tessellated arc of a 500 units large quarter circle from the true
+/// circle.
+fn quarter_arc_radial_error(arc_tolerance: f32) -> f32 {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="500" height="500">
+        <path d="M 0 0 L 500 0 A 500 500 0 0 1 0 500 Z" fill="#000000"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let options = SvgConvertOptions {
+        arc_tolerance: Some(arc_tolerance),
+        ..Default::default()
+    };
+    let mut svg = Svg::from_usvg_tree(&tree, &options);
+    // Only the outline, without the anti-aliasing fringe outside of it.
+    svg.tessellation_options.edge_aa_width = 0.0;
+    let mesh = svg.tessellate();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    // Apart from the center, every vertex lies on the arc.
+    positions
+        .iter()
+        .map(|position| Vec2::new(position[0], position[1]).length())
+        .filter(|radius| *radius > 1.0)
+        .map(|radius| (radius - 500.0).abs())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn refitted_arcs_stay_within_the_arc_tolerance() {
+    const EPSILON: f32 = 0.01;
+    // The flattening tolerance of fills adds to the error of the curve.
+    let refitted = quarter_arc_radial_error(EPSILON);
+    assert!(refitted < EPSILON + 0.001, "{refitted}");
+}
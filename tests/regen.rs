@@ -0,0 +1,284 @@
+//! Background regeneration of mesh variants through the `SvgRegenQueue`.
+use bevy::{
+    asset::{AssetId, RenderAssetUsages},
+    diagnostic::DiagnosticsPlugin,
+    mesh::{PrimitiveTopology, VertexAttributeValues},
+    prelude::*,
+};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+fn empty_mesh() -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+}
+
+fn is_done(app: &App, svg: AssetId<Svg>, key: u64) -> bool {
+    app.world().resource::<SvgMeshVariants>().contains(svg, key)
+}
+
+#[test]
+fn jobs_finish_by_priority_over_several_frames() {
+    const BUDGET: usize = 10;
+    const STALE: u64 = 1000;
+    let mut app = app();
+    let svg = Handle::<Svg>::default().id();
+    let entities: Vec<Entity> = (0..100)
+        .map(|_| app.world_mut().spawn_empty().id())
+        .collect();
+    // Distinct priorities in shuffled order, the job of rank 0 has the highest.
+    let priority = |index: usize| (index * 37 % 100) as i32;
+    let rank = |index: usize| 99 - priority(index) as usize;
+
+    {
+        let mut queue = app.world_mut().resource_mut::<SvgRegenQueue>();
+        queue.budget = BUDGET;
+        for (index, entity) in entities.iter().enumerate() {
+            queue.request(*entity, svg, index as u64, priority(index), empty_mesh);
+        }
+    }
+    app.update();
+    // Change the parameters of jobs that already started and of jobs still waiting.
+    {
+        let mut queue = app.world_mut().resource_mut::<SvgRegenQueue>();
+        for (index, entity) in entities.iter().enumerate().step_by(7) {
+            queue.request(
+                *entity,
+                svg,
+                STALE + index as u64,
+                priority(index),
+                empty_mesh,
+            );
+        }
+    }
+
+    let mut frames = 1;
+    while !app.world().resource::<SvgRegenQueue>().is_empty() {
+        assert!(frames < 10_000, "the queue didn't drain");
+        app.update();
+        frames += 1;
+        for index in 0..entities.len() {
+            let key = index as u64;
+            if index % 7 == 0 {
+                assert!(!is_done(&app, svg, key), "stale job {index} was applied");
+            } else if is_done(&app, svg, key) {
+                // At most `BUDGET` jobs start per frame, highest priority first.
+                assert!(
+                    rank(index) < BUDGET * frames,
+                    "job {index} jumped the queue"
+                );
+            }
+        }
+    }
+
+    assert!(
+        frames > 100 / BUDGET,
+        "all jobs finished in {frames} frames"
+    );
+    for index in 0..entities.len() {
+        let key = if index % 7 == 0 {
+            STALE + index as u64
+        } else {
+            index as u64
+        };
+        assert!(is_done(&app, svg, key), "job {index} didn't finish");
+    }
+}
+
+/// The horizontal extent of the entity's mesh, once it has one.
+fn x_range(app: &App, entity: Entity) -> Option<(f32, f32)> {
+    let handle = &app.world().get::<Mesh2d>(entity)?.0;
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        meshes.get(handle)?.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+    Some(
+        positions
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), p| {
+                (min.min(p[0]), max.max(p[0]))
+            }),
+    )
+}
+
+fn update_until(app: &mut App, mut done: impl FnMut(&App) -> bool) {
+    for _ in 0..1000 {
+        app.update();
+        if done(app) {
+            return;
+        }
+    }
+    panic!("condition not reached");
+}
+
+#[test]
+fn mesh_features_combine_into_one_variant() {
+    let mut app = app();
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("box.svg");
+    let nine_slice = SvgNineSlice {
+        border: Rect {
+            min: Vec2::splat(20.0),
+            max: Vec2::splat(20.0),
+        },
+        size: Vec2::splat(200.0),
+    };
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            nine_slice,
+            SvgStrokeStyleOverride {
+                width_multiplier: 3.0,
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    // The 4 unit wide stroke around x = 10 is widened to 12 units, and the right side is
+    // stretched to x = 190.
+    update_until(&mut app, |app| {
+        x_range(app, entity).is_some_and(|(min, _)| min < 5.0)
+    });
+    let (min, max) = x_range(&app, entity).unwrap();
+    assert!(max > 150.0, "{min}..{max}");
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(x_range(&app, entity), Some((min, max)));
+    }
+
+    // Removing one feature keeps the other.
+    app.world_mut()
+        .entity_mut(entity)
+        .remove::<SvgStrokeStyleOverride>();
+    update_until(&mut app, |app| {
+        x_range(app, entity).is_some_and(|(min, _)| min > 7.0)
+    });
+    assert!(x_range(&app, entity).unwrap().1 > 150.0);
+
+    app.world_mut().entity_mut(entity).remove::<SvgNineSlice>();
+    app.update();
+    let asset_mesh = app
+        .world()
+        .resource::<Assets<Svg>>()
+        .get(&handle)
+        .unwrap()
+        .mesh
+        .clone();
+    assert_eq!(app.world().get::<Mesh2d>(entity).unwrap().0, asset_mesh);
+}
+
+#[test]
+fn least_recently_used_variants_beyond_the_capacity_are_dropped() {
+    let mut app = app();
+    let svg = Handle::<Svg>::default().id();
+    let held = app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .add(empty_mesh());
+    app.world_mut()
+        .resource_scope(|world, mut variants: Mut<SvgMeshVariants>| {
+            let mut meshes = world.resource_mut::<Assets<Mesh>>();
+            variants.capacity = 4;
+            variants.insert(svg, 0, held.clone());
+            for key in 1..10 {
+                variants.insert(svg, key, meshes.add(empty_mesh()));
+            }
+            // The held variant stays although it's the oldest.
+            let cached = |variants: &SvgMeshVariants| {
+                (0..=10)
+                    .filter(|key| variants.contains(svg, *key))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(cached(&variants), [0, 7, 8, 9]);
+
+            assert!(variants.get(svg, 7).is_some());
+            variants.insert(svg, 10, meshes.add(empty_mesh()));
+            assert_eq!(cached(&variants), [0, 7, 9, 10]);
+        });
+}
+
+#[test]
+fn entities_needing_the_same_variant_share_one_job() {
+    let mut app = app();
+    let handle: Handle<Svg> = app.world().resource::<AssetServer>().load("box.svg");
+    let entities: Vec<Entity> = (0..5)
+        .map(|_| {
+            app.world_mut()
+                .spawn((
+                    Svg2d(handle.clone()),
+                    SvgStrokeStyleOverride {
+                        width_multiplier: 3.0,
+                        ..Default::default()
+                    },
+                ))
+                .id()
+        })
+        .collect();
+
+    update_until(&mut app, |app| {
+        !app.world().resource::<SvgRegenQueue>().is_empty()
+    });
+    assert_eq!(app.world().resource::<SvgRegenQueue>().len(), 1);
+
+    let mesh = |app: &App, entity: Entity| app.world().get::<Mesh2d>(entity).unwrap().0.clone();
+    let asset_mesh = |app: &App| {
+        app.world()
+            .resource::<Assets<Svg>>()
+            .get(&handle)
+            .unwrap()
+            .mesh
+            .clone()
+    };
+    update_until(&mut app, |app| {
+        entities
+            .iter()
+            .all(|entity| mesh(app, *entity) != asset_mesh(app))
+    });
+    let shared = mesh(&app, entities[0]);
+    assert!(entities.iter().all(|entity| mesh(&app, *entity) == shared));
+}
+
+#[test]
+fn jobs_of_despawned_entities_are_dropped() {
+    let mut app = app();
+    let svg = Handle::<Svg>::default().id();
+    let kept = app.world_mut().spawn_empty().id();
+    let despawned = app.world_mut().spawn_empty().id();
+    {
+        let mut queue = app.world_mut().resource_mut::<SvgRegenQueue>();
+        // The budget is exhausted by the job of `kept`, so the other one is still waiting.
+        queue.budget = 1;
+        queue.request(kept, svg, 0, 1, empty_mesh);
+        queue.request(despawned, svg, 1, 0, empty_mesh);
+    }
+    app.world_mut().despawn(despawned);
+
+    for _ in 0..100 {
+        app.update();
+        if app.world().resource::<SvgRegenQueue>().is_empty() {
+            break;
+        }
+    }
+    assert!(app.world().resource::<SvgRegenQueue>().is_empty());
+    assert!(is_done(&app, svg, 0));
+    assert!(!is_done(&app, svg, 1));
+}
@@ -0,0 +1,50 @@
+//! Skewed paths, which a `Transform` can't represent.
+use bevy::{
+    math::{Vec2, Vec3},
+    mesh::{Mesh, VertexAttributeValues},
+};
+use bevy_svg::{prelude::*, usvg};
+
+#[test]
+fn skewed_rect_is_a_parallelogram() {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20">
+        <rect x="0" y="0" width="10" height="10" fill="#000000" transform="translate(5 5) skewX(20)"/>
+    </svg>"##;
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    let shift = 10.0 * 20.0_f32.to_radians().tan();
+    let corners = [
+        Vec2::new(5.0, 5.0),
+        Vec2::new(15.0, 5.0),
+        Vec2::new(15.0 + shift, 15.0),
+        Vec2::new(5.0 + shift, 15.0),
+    ];
+
+    let outline = &svg.flattened_outline(0, 0.01)[0];
+    for corner in corners {
+        assert!(
+            outline
+                .iter()
+                .any(|point| Vec2::new(point.x, point.y).distance(corner) < 1e-3),
+            "outline misses {corner}"
+        );
+    }
+
+    let mesh = svg.tessellate();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    for corner in corners {
+        // The mesh has a flipped y-axis.
+        let corner = Vec3::new(corner.x, -corner.y, 0.0);
+        assert!(
+            positions
+                .iter()
+                .any(|position| Vec3::from(*position).distance(corner) < 1e-3),
+            "mesh misses {corner}"
+        );
+    }
+}
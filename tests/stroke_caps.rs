@@ -0,0 +1,56 @@
+//! Open and closed subpaths of strokes, which decide where caps and joins are drawn.
+use bevy_svg::{prelude::*, usvg};
+use lyon_path::PathEvent;
+
+/// Caps and joins of the strokes of a path: two caps per open subpath, and a join between
+/// consecutive segments, plus one at the seam of closed subpaths.
+fn caps_and_joins(segments: &[PathEvent]) -> (usize, usize) {
+    let (mut caps, mut joins, mut edges) = (0, 0, 0);
+    for event in segments {
+        match event {
+            PathEvent::Begin { .. } => edges = 0,
+            PathEvent::Line { .. } | PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {
+                edges += 1;
+            }
+            // Closing adds a segment back to the start, unless the path already returned there.
+            PathEvent::End {
+                last,
+                first,
+                close: true,
+            } => joins += edges + usize::from(last != first),
+            PathEvent::End { close: false, .. } => {
+                caps += 2;
+                joins += edges - 1;
+            }
+        }
+    }
+    (caps, joins)
+}
+
+#[test]
+fn stroke_caps_fixture_has_expected_caps_and_joins() {
+    let tree = usvg::Tree::from_str(
+        include_str!("../assets/stroke_caps.svg"),
+        &usvg::Options::default(),
+    )
+    .unwrap();
+    let svg = Svg::from_usvg_tree(&tree, &SvgConvertOptions::default());
+
+    let counts: Vec<_> = svg
+        .paths
+        .iter()
+        .map(|path| caps_and_joins(svg.path_segments(path)))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            // Open zig-zag of four segments.
+            (2, 3),
+            // Closed triangle, joined at its seam.
+            (0, 3),
+            // An open segment, a closed triangle and an open polyline that restarts where
+            // the triangle was closed.
+            (4, 4),
+        ]
+    );
+}
@@ -0,0 +1,158 @@
+//! Tessellated meshes: their vertex layout, winding, tessellating into an existing mesh, and
+//! tessellating single strokes.
+use bevy::mesh::{
+    Mesh, MeshVertexAttribute, MeshVertexAttributeId, VertexAttributeValues, VertexFormat,
+};
+use bevy_svg::{prelude::*, usvg};
+
+fn convert(text: &str) -> Svg {
+    let tree = usvg::Tree::from_str(text, &usvg::Options::default()).unwrap();
+    Svg::from_usvg_tree(&tree, &SvgConvertOptions::default())
+}
+
+fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("mesh has no positions");
+    };
+    positions
+}
+
+#[test]
+fn tessellate_into_matches_tessellate() {
+    let circles = convert(include_str!("../assets/overlapping_circles.svg"));
+    let panel = convert(include_str!("../assets/panel.svg"));
+
+    let mut mesh = circles.tessellate();
+    panel.tessellate_into(&mut mesh);
+
+    let expected = panel.tessellate();
+    assert_eq!(positions(&mesh), positions(&expected));
+    assert_eq!(
+        mesh.indices().unwrap().iter().collect::<Vec<_>>(),
+        expected.indices().unwrap().iter().collect::<Vec<_>>()
+    );
+    assert_eq!(mesh.attributes().count(), expected.attributes().count());
+}
+
+/// Twice the signed area of every triangle of `mesh`, positive for counter-clockwise ones.
+fn signed_areas(mesh: &Mesh) -> Vec<f32> {
+    let positions = positions(mesh);
+    let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+    indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let [a, b, c] = [0, 1, 2].map(|corner| positions[triangle[corner]]);
+            (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+        })
+        .collect()
+}
+
+#[test]
+fn facings_mirror_the_winding() {
+    // Fills and strokes.
+    for text in [
+        include_str!("../assets/overlapping_circles.svg"),
+        include_str!("../assets/box.svg"),
+    ] {
+        let svg = convert(text);
+        let tessellate = |facing| {
+            svg.tessellate_with_options(&TessellationOptions {
+                facing,
+                ..svg.tessellation_options
+            })
+        };
+        let positive = tessellate(Facing::PositiveZ);
+        let negative = tessellate(Facing::NegativeZ);
+
+        assert_eq!(positions(&positive), positions(&negative));
+        let positive = signed_areas(&positive);
+        let negative = signed_areas(&negative);
+        assert_eq!(positive.len(), negative.len());
+        for (positive, negative) in positive.iter().zip(&negative) {
+            assert!(*positive >= 0.0 && *negative <= 0.0);
+            assert!((positive + negative).abs() <= 1e-3 * positive.abs().max(1.0));
+        }
+    }
+    assert_eq!(
+        Facing::NegativeZ.normal(),
+        Facing::PositiveZ.normal().map(|n| -n)
+    );
+}
+
+/// The ids and formats of the attributes of `mesh`, sorted by id.
+fn layout(mesh: &Mesh) -> Vec<(MeshVertexAttributeId, VertexFormat)> {
+    let mut layout: Vec<_> = mesh
+        .attributes()
+        .map(|(attribute, _)| (attribute.id, attribute.format))
+        .collect();
+    layout.sort_by_key(|(id, _)| *id);
+    layout
+}
+
+fn expected_layout(
+    attributes: &[MeshVertexAttribute],
+) -> Vec<(MeshVertexAttributeId, VertexFormat)> {
+    let mut layout: Vec<_> = attributes
+        .iter()
+        .map(|attribute| (attribute.id, attribute.format))
+        .collect();
+    layout.sort_by_key(|(id, _)| *id);
+    layout
+}
+
+#[test]
+fn meshes_have_exactly_the_documented_attributes() {
+    let mut svg = convert(include_str!("../assets/overlapping_circles.svg"));
+    let mesh = svg.tessellate();
+    assert_eq!(
+        layout(&mesh),
+        expected_layout(&[SVG_ATTRIBUTE_POSITION, SVG_ATTRIBUTE_COLOR])
+    );
+    assert_eq!(SVG_ATTRIBUTE_POSITION.format, VertexFormat::Float32x3);
+    assert_eq!(SVG_ATTRIBUTE_COLOR.format, VertexFormat::Float32x4);
+    assert!(mesh.indices().is_some());
+
+    svg.tessellation_options.barycentric = true;
+    let mesh = svg.tessellate();
+    assert_eq!(
+        layout(&mesh),
+        expected_layout(&[
+            SVG_ATTRIBUTE_POSITION,
+            SVG_ATTRIBUTE_COLOR,
+            SVG_ATTRIBUTE_BARYCENTRIC
+        ])
+    );
+    assert_eq!(SVG_ATTRIBUTE_BARYCENTRIC.format, VertexFormat::Float32x3);
+    assert!(mesh.indices().is_none());
+}
+
+#[test]
+fn stroke_geometry_has_only_the_stroke() {
+    let mut svg = convert(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+            <rect x="5" y="5" width="10" height="10" fill="#ff0000" stroke="#0000ff" stroke-width="2"/>
+        </svg>"##,
+    );
+    assert!(svg.stroke_geometry(0).is_none());
+    assert!(svg.stroke_geometry(2).is_none());
+
+    let (_, ranges) = svg.tessellate_with_ranges(&svg.tessellation_options);
+    let stroke = svg.stroke_geometry(1).unwrap();
+    assert_eq!(stroke.count_vertices(), ranges[1].len());
+    let Some(VertexAttributeValues::Float32x4(colors)) = stroke.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        panic!("mesh has no colors");
+    };
+    assert!(colors.iter().all(|color| color[..3] == [0.0, 0.0, 1.0]));
+    // The stroke is 2 units wide around the outline of the rect, with a flipped y-axis, and
+    // leaves out the fill inside it.
+    for position in positions(&stroke) {
+        let from_center = (position[0] - 10.0).abs().max((position[1] + 10.0).abs());
+        assert!((4.0..=6.0).contains(&from_center), "{position:?}");
+    }
+
+    svg.tessellation_options.barycentric = true;
+    assert!(svg.stroke_geometry(1).unwrap().indices().is_some());
+}
@@ -0,0 +1,55 @@
+//! Text outlines, their vertex counts and their simplification.
+use std::sync::Arc;
+
+use bevy_svg::{prelude::*, usvg};
+
+fn text_svg() -> Svg {
+    let text = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="40">
+        <rect width="10" height="10" fill="#000000"/>
+        <text x="10" y="30" font-family="Fira Sans" font-size="20" fill="#ff0000">Hello text</text>
+    </svg>"##;
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb
+        .load_font_file(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/fonts/FiraSans-Bold.ttf"
+        ))
+        .unwrap();
+    let options = usvg::Options {
+        fontdb: Arc::new(fontdb),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(text, &options).unwrap();
+    Svg::from_usvg_tree(&tree, &SvgConvertOptions::default())
+}
+
+#[test]
+fn text_vertices_are_counted_separately() {
+    let svg = text_svg();
+    assert!(!svg.paths[0].text);
+    assert!(svg.paths[1..].iter().all(|path| path.text));
+
+    let stats = svg.stats();
+    assert_eq!(stats.path_count, svg.paths.len());
+    assert_eq!(stats.text_path_count, svg.paths.len() - 1);
+    assert_eq!(stats.vertex_count, svg.tessellate().count_vertices());
+    // Far more than the four corners of the rectangle.
+    assert!(stats.text_vertex_count > 40);
+    assert!(stats.text_vertex_count < stats.vertex_count);
+}
+
+#[test]
+fn simplified_text_is_drawn_as_rectangles() {
+    let mut svg = text_svg();
+    // Count only the corners, without the vertices of the anti-aliasing fringe.
+    svg.tessellation_options.edge_aa_width = 0.0;
+    let before = svg.stats();
+    svg.simplify_text();
+    let after = svg.stats();
+
+    assert_eq!(after.text_path_count, before.text_path_count);
+    assert_eq!(after.vertex_count - after.text_vertex_count, 4);
+    assert!(after.text_vertex_count <= 4 * after.text_path_count);
+    let bounds = svg.content_bounds().unwrap();
+    assert!(bounds.max.x > 50.0 && bounds.max.y > 20.0);
+}
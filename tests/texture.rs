@@ -0,0 +1,117 @@
+//! Rendering single SVG entities into textures.
+use bevy::{camera::visibility::RenderLayers, diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .init_asset::<Image>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+/// Spawns the Inkscape fixture into a texture, with its "Details" layer shown only from a zoom
+/// of `0.5` on, and runs the app until the texture is set up.
+fn spawn(app: &mut App, layers: Option<RenderLayers>) -> Entity {
+    let handle = app
+        .world()
+        .resource::<AssetServer>()
+        .load("inkscape_layers.svg");
+    let mut entity = app.world_mut().spawn((
+        Svg2d(handle),
+        SvgZoomLayers::default().with_layer("Details", 0.5, f32::INFINITY),
+        SvgRenderToTexture {
+            layer: 3,
+            ..Default::default()
+        },
+    ));
+    if let Some(layers) = layers {
+        entity.insert(layers);
+    }
+    let entity = entity.id();
+    for _ in 0..1000 {
+        app.update();
+        if app.world().get::<SvgTexture>(entity).is_some() {
+            return entity;
+        }
+    }
+    panic!("{entity} didn't get a texture");
+}
+
+#[test]
+fn render_to_texture_sets_up_a_camera_on_its_layer() {
+    let mut app = app();
+    let entity = spawn(&mut app, Some(RenderLayers::layer(2)));
+    // Zoomed out so far that the "Details" layer is hidden, and spawned after the texture
+    // camera, so that one comes first.
+    app.world_mut().spawn((
+        Camera2d,
+        Projection::Orthographic(OrthographicProjection {
+            scale: 10.0,
+            ..OrthographicProjection::default_2d()
+        }),
+    ));
+
+    let texture = app.world().get::<SvgTexture>(entity).unwrap().clone();
+    assert!(app
+        .world()
+        .resource::<Assets<Image>>()
+        .contains(&texture.image));
+    let layer = RenderLayers::layer(3);
+    assert_eq!(app.world().get::<RenderLayers>(entity), Some(&layer));
+    let camera = app.world().entity(texture.camera);
+    assert_eq!(camera.get::<ChildOf>().map(ChildOf::parent), Some(entity));
+    assert_eq!(camera.get::<RenderLayers>(), Some(&layer));
+    assert_eq!(camera.get::<Camera>().unwrap().order, -1);
+
+    // The texture camera isn't zoomed out, but the zoom layers keep tracking the other camera.
+    for _ in 0..10 {
+        app.update();
+    }
+    let handle = app.world().get::<Svg2d>(entity).unwrap().0.clone();
+    let asset_mesh = app
+        .world()
+        .resource::<Assets<Svg>>()
+        .get(&handle)
+        .unwrap()
+        .mesh
+        .clone();
+    assert_ne!(app.world().get::<Mesh2d>(entity).unwrap().0, asset_mesh);
+}
+
+#[test]
+fn removing_render_to_texture_restores_the_render_layers() {
+    let mut app = app();
+    let layered = spawn(&mut app, Some(RenderLayers::layer(2)));
+    let default = spawn(&mut app, None);
+    let cameras =
+        [layered, default].map(|entity| app.world().get::<SvgTexture>(entity).unwrap().camera);
+
+    for entity in [layered, default] {
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<SvgRenderToTexture>();
+    }
+    app.update();
+
+    for camera in cameras {
+        assert!(app.world().get_entity(camera).is_err());
+    }
+    for entity in [layered, default] {
+        assert!(app.world().get::<SvgTexture>(entity).is_none());
+    }
+    assert_eq!(
+        app.world().get::<RenderLayers>(layered),
+        Some(&RenderLayers::layer(2))
+    );
+    assert_eq!(app.world().get::<RenderLayers>(default), None);
+}
@@ -0,0 +1,118 @@
+//! Elements of an SVG shown only in a zoom range of the tracked camera.
+use bevy::{diagnostic::DiagnosticsPlugin, prelude::*};
+use bevy_svg::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        DiagnosticsPlugin,
+        TransformPlugin,
+        AssetPlugin {
+            file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_owned(),
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Mesh>()
+    .add_plugins(SvgPlugin::default());
+    app
+}
+
+fn load(app: &mut App, path: &'static str) -> Handle<Svg> {
+    let handle = app.world().resource::<AssetServer>().load(path);
+    for _ in 0..1000 {
+        if app.world().resource::<Assets<Svg>>().contains(&handle) {
+            return handle;
+        }
+        app.update();
+    }
+    panic!("SVG {path} didn't load");
+}
+
+fn camera(app: &mut App, scale: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Camera2d,
+            Projection::Orthographic(OrthographicProjection {
+                scale,
+                ..OrthographicProjection::default_2d()
+            }),
+        ))
+        .id()
+}
+
+/// Spawns the Inkscape fixture with its "Details" layer shown only from a zoom of `0.5` on.
+fn spawn(app: &mut App) -> (Handle<Svg>, Entity) {
+    let handle = load(app, "inkscape_layers.svg");
+    let entity = app
+        .world_mut()
+        .spawn((
+            Svg2d(handle.clone()),
+            SvgZoomLayers::default().with_layer("Details", 0.5, f32::INFINITY),
+        ))
+        .id();
+    (handle, entity)
+}
+
+fn asset_mesh(app: &App, handle: &Handle<Svg>) -> Handle<Mesh> {
+    app.world()
+        .resource::<Assets<Svg>>()
+        .get(handle)
+        .unwrap()
+        .mesh
+        .clone()
+}
+
+/// Runs the app until the mesh of `entity` is, or isn't, the mesh of the asset.
+fn settle(app: &mut App, handle: &Handle<Svg>, entity: Entity, asset: bool) {
+    for _ in 0..1000 {
+        app.update();
+        let mesh = &app.world().get::<Mesh2d>(entity).unwrap().0;
+        if (*mesh == asset_mesh(app, handle)) == asset {
+            return;
+        }
+    }
+    panic!("the mesh of {entity} didn't settle");
+}
+
+fn vertex_count(app: &App, entity: Entity) -> usize {
+    let mesh = &app.world().get::<Mesh2d>(entity).unwrap().0;
+    app.world()
+        .resource::<Assets<Mesh>>()
+        .get(mesh)
+        .unwrap()
+        .count_vertices()
+}
+
+#[test]
+fn removing_zoom_layers_restores_the_asset_mesh() {
+    let mut app = app();
+    camera(&mut app, 10.0);
+    let (handle, entity) = spawn(&mut app);
+
+    settle(&mut app, &handle, entity, false);
+    let all = app
+        .world()
+        .resource::<Assets<Mesh>>()
+        .get(&asset_mesh(&app, &handle))
+        .unwrap()
+        .count_vertices();
+    assert!(vertex_count(&app, entity) < all);
+
+    app.world_mut().entity_mut(entity).remove::<SvgZoomLayers>();
+    settle(&mut app, &handle, entity, true);
+}
+
+#[test]
+fn svg_camera_chooses_among_several_cameras() {
+    let mut app = app();
+    let near = camera(&mut app, 1.0);
+    let far = camera(&mut app, 10.0);
+    let (handle, entity) = spawn(&mut app);
+
+    app.insert_resource(SvgCamera(Some(far)));
+    settle(&mut app, &handle, entity, false);
+
+    app.insert_resource(SvgCamera(Some(near)));
+    settle(&mut app, &handle, entity, true);
+}